@@ -0,0 +1,30 @@
+CREATE TYPE dao_config_event_type as ENUM (
+    'ProposalConfigurationUpdated',
+    'ProposalVotingDelayUpdated',
+    'ProposalVotingPeriodUpdated',
+    'ProposalQuorumVotesUpdated',
+    'ProposalTimeLockUpdated',
+    'ProposalThresholdUpdated',
+    'ProposalGracePeriodUpdated'
+    );
+
+CREATE TABLE IF NOT EXISTS dao_config_history
+(
+    id                  BIGSERIAL             NOT NULL,
+    kind                dao_config_event_type NOT NULL,
+    voting_delay        BIGINT,
+    voting_period       BIGINT,
+    quorum_votes        NUMERIC,
+    time_lock           BIGINT,
+    threshold           NUMERIC,
+    grace_period        BIGINT,
+    message_hash        BYTEA                 NOT NULL,
+    transaction_hash    BYTEA                 NOT NULL,
+    timestamp_block     INTEGER               NOT NULL,
+    created_at          BIGINT                NOT NULL DEFAULT extract(epoch from (CURRENT_TIMESTAMP(3) at time zone 'utc')) * 1000,
+    PRIMARY KEY (id),
+    UNIQUE (transaction_hash, message_hash)
+);
+
+CREATE INDEX dao_config_history_timestamp_block_idx ON dao_config_history (timestamp_block);
+CREATE INDEX dao_config_history_kind_idx ON dao_config_history (kind);
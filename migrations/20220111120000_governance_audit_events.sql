@@ -0,0 +1,27 @@
+CREATE TYPE governance_audit_event_type as ENUM (
+    'RequestedAdminTransfer',
+    'AdminTransferAccepted',
+    'ProposalCodeUpgraded',
+    'RootCodeUpgraded',
+    'StakingRootUpdated',
+    'CodeUpgradeRequested',
+    'ProposalUpgraded'
+    );
+
+CREATE TABLE IF NOT EXISTS governance_audit_events
+(
+    id                  BIGSERIAL                   NOT NULL,
+    kind                governance_audit_event_type NOT NULL,
+    contract_address    VARCHAR                     NOT NULL,
+    data                jsonb                       NOT NULL,
+    message_hash        BYTEA                       NOT NULL,
+    transaction_hash    BYTEA                       NOT NULL,
+    timestamp_block     INTEGER                     NOT NULL,
+    created_at          BIGINT                      NOT NULL DEFAULT extract(epoch from (CURRENT_TIMESTAMP(3) at time zone 'utc')) * 1000,
+    PRIMARY KEY (id),
+    UNIQUE (transaction_hash, message_hash)
+);
+
+CREATE INDEX governance_audit_events_kind_idx ON governance_audit_events (kind);
+CREATE INDEX governance_audit_events_contract_address_idx ON governance_audit_events (contract_address);
+CREATE INDEX governance_audit_events_timestamp_block_idx ON governance_audit_events (timestamp_block);
@@ -0,0 +1,16 @@
+-- existing rows are assigned to the first configured root on startup
+ALTER TABLE proposals ADD COLUMN dao_root VARCHAR NOT NULL DEFAULT '';
+ALTER TABLE proposals DROP CONSTRAINT proposals_pkey;
+ALTER TABLE proposals ADD PRIMARY KEY (dao_root, id);
+
+ALTER TABLE votes ADD COLUMN dao_root VARCHAR NOT NULL DEFAULT '';
+ALTER TABLE votes DROP CONSTRAINT votes_pkey;
+ALTER TABLE votes ADD PRIMARY KEY (dao_root, proposal_id, voter);
+
+ALTER TABLE dao_config_history ADD COLUMN dao_root VARCHAR NOT NULL DEFAULT '';
+ALTER TABLE governance_audit_events ADD COLUMN dao_root VARCHAR NOT NULL DEFAULT '';
+
+CREATE INDEX proposals_dao_root_idx ON proposals (dao_root);
+CREATE INDEX votes_dao_root_idx ON votes (dao_root);
+CREATE INDEX dao_config_history_dao_root_idx ON dao_config_history (dao_root);
+CREATE INDEX governance_audit_events_dao_root_idx ON governance_audit_events (dao_root);
@@ -0,0 +1,24 @@
+CREATE TYPE pending_action_type as ENUM (
+    'Executed',
+    'Canceled',
+    'Queued',
+    'UnlockVote'
+    );
+
+CREATE TABLE IF NOT EXISTS pending_actions
+(
+    id                  BIGSERIAL           NOT NULL,
+    kind                pending_action_type NOT NULL,
+    proposal_address    VARCHAR,
+    dao_root            VARCHAR,
+    proposal_id         INTEGER,
+    voter               VARCHAR,
+    execution_time      BIGINT,
+    timestamp_block     INTEGER             NOT NULL,
+    created_at          BIGINT              NOT NULL DEFAULT extract(epoch from (CURRENT_TIMESTAMP(3) at time zone 'utc')) * 1000,
+    PRIMARY KEY (id)
+);
+
+CREATE INDEX pending_actions_proposal_address_idx ON pending_actions (proposal_address);
+CREATE UNIQUE INDEX pending_actions_unlock_vote_idx ON pending_actions (dao_root, proposal_id, voter) WHERE kind = 'UnlockVote';
+CREATE INDEX pending_actions_created_at_idx ON pending_actions (created_at);
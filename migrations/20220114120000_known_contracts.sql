@@ -0,0 +1,14 @@
+CREATE TYPE known_contract_type as ENUM (
+    'Proposal',
+    'UserData'
+    );
+
+CREATE TABLE IF NOT EXISTS known_contracts
+(
+    address             VARCHAR             NOT NULL,
+    kind                known_contract_type NOT NULL,
+    dao_root            VARCHAR             NOT NULL,
+    owner               VARCHAR,
+    created_at          BIGINT              NOT NULL DEFAULT extract(epoch from (CURRENT_TIMESTAMP(3) at time zone 'utc')) * 1000,
+    PRIMARY KEY (address)
+);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS applied_events
+(
+    transaction_hash    BYTEA   NOT NULL,
+    message_hash        BYTEA   NOT NULL,
+    created_at          BIGINT  NOT NULL DEFAULT extract(epoch from (CURRENT_TIMESTAMP(3) at time zone 'utc')) * 1000,
+    PRIMARY KEY (transaction_hash, message_hash)
+);
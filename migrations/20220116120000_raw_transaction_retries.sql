@@ -0,0 +1,8 @@
+ALTER TYPE raw_transaction_state_type ADD VALUE IF NOT EXISTS 'DeadLetter';
+
+ALTER TABLE raw_transactions_service
+    ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN last_error VARCHAR,
+    ADD COLUMN next_retry_at BIGINT;
+
+CREATE INDEX raw_transactions_service_state_idx ON raw_transactions_service (state, next_retry_at);
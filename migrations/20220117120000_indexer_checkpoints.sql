@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS indexer_checkpoints
+(
+    source              VARCHAR NOT NULL,
+    timestamp_lt        BIGINT  NOT NULL,
+    timestamp_block     INTEGER NOT NULL,
+    updated_at          BIGINT  NOT NULL DEFAULT extract(epoch from (CURRENT_TIMESTAMP(3) at time zone 'utc')) * 1000,
+    PRIMARY KEY (source)
+);
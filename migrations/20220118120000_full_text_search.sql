@@ -0,0 +1,8 @@
+ALTER TABLE proposals
+    ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (to_tsvector('english', description)) STORED;
+
+ALTER TABLE votes
+    ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (to_tsvector('english', reason)) STORED;
+
+CREATE INDEX proposals_search_vector_idx ON proposals USING GIN (search_vector);
+CREATE INDEX votes_search_vector_idx ON votes USING GIN (search_vector);
@@ -0,0 +1,14 @@
+-- Orderings are followed by the (timestamp_block, id, dao_root) tie-breaker
+DROP INDEX IF EXISTS proposals_timestamp_block_idx;
+DROP INDEX IF EXISTS proposals_end_time_idx;
+DROP INDEX IF EXISTS votes_timestamp_block_idx;
+
+CREATE INDEX proposals_created_at_ordering_idx ON proposals (timestamp_block, id, dao_root);
+CREATE INDEX proposals_end_time_ordering_idx ON proposals (end_time, timestamp_block, id, dao_root);
+CREATE INDEX proposals_total_votes_ordering_idx ON proposals ((for_votes + against_votes), timestamp_block, id, dao_root);
+CREATE INDEX proposals_for_votes_ordering_idx ON proposals (for_votes, timestamp_block, id, dao_root);
+CREATE INDEX proposals_against_votes_ordering_idx ON proposals (against_votes, timestamp_block, id, dao_root);
+CREATE INDEX proposals_quorum_progress_ordering_idx ON proposals ((for_votes / NULLIF(quorum_votes, 0)), timestamp_block, id, dao_root);
+
+CREATE INDEX votes_created_at_ordering_idx ON votes (timestamp_block, proposal_id, voter, dao_root);
+CREATE INDEX votes_weight_ordering_idx ON votes (votes, timestamp_block, proposal_id, voter, dao_root);
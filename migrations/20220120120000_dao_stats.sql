@@ -0,0 +1,66 @@
+-- Additive aggregates per DAO root, so the stats of several roots can be combined.
+-- Proposal states depend on the time and are evaluated on refresh
+CREATE MATERIALIZED VIEW dao_stats AS
+WITH proposal_states AS (
+    SELECT dao_root,
+           id,
+           for_votes,
+           against_votes,
+           CASE
+               WHEN canceled THEN 'Canceled'
+               WHEN executed THEN 'Executed'
+               WHEN now_ <= start_time THEN 'Pending'
+               WHEN now_ <= end_time THEN 'Active'
+               WHEN for_votes <= against_votes OR for_votes < quorum_votes THEN 'Failed'
+               WHEN execution_time = 0 THEN 'Succeeded'
+               WHEN now_ > execution_time + grace_period THEN 'Expired'
+               ELSE 'Queued'
+           END AS state
+    FROM proposals,
+         (SELECT extract(epoch FROM now())::BIGINT AS now_) AS clock
+),
+proposal_aggregates AS (
+    SELECT dao_root,
+           COUNT(*) AS proposals_count,
+           COUNT(*) FILTER (WHERE state = 'Pending') AS pending_count,
+           COUNT(*) FILTER (WHERE state = 'Active') AS active_count,
+           COUNT(*) FILTER (WHERE state = 'Canceled') AS canceled_count,
+           COUNT(*) FILTER (WHERE state = 'Failed') AS failed_count,
+           COUNT(*) FILTER (WHERE state = 'Succeeded') AS succeeded_count,
+           COUNT(*) FILTER (WHERE state = 'Expired') AS expired_count,
+           COUNT(*) FILTER (WHERE state = 'Queued') AS queued_count,
+           COUNT(*) FILTER (WHERE state = 'Executed') AS executed_count,
+           COUNT(*) FILTER (WHERE for_votes + against_votes > 0) AS voted_proposals_count,
+           COALESCE(SUM(for_votes / NULLIF(for_votes + against_votes, 0)), 0) AS for_ratio_sum
+    FROM proposal_states
+    GROUP BY dao_root
+),
+vote_aggregates AS (
+    SELECT dao_root,
+           COUNT(DISTINCT voter) AS voters_count,
+           COUNT(*) AS votes_count,
+           COALESCE(SUM(votes) FILTER (WHERE locked), 0) AS locked_votes
+    FROM votes
+    GROUP BY dao_root
+)
+SELECT COALESCE(p.dao_root, v.dao_root) AS dao_root,
+       COALESCE(p.proposals_count, 0) AS proposals_count,
+       COALESCE(p.pending_count, 0) AS pending_count,
+       COALESCE(p.active_count, 0) AS active_count,
+       COALESCE(p.canceled_count, 0) AS canceled_count,
+       COALESCE(p.failed_count, 0) AS failed_count,
+       COALESCE(p.succeeded_count, 0) AS succeeded_count,
+       COALESCE(p.expired_count, 0) AS expired_count,
+       COALESCE(p.queued_count, 0) AS queued_count,
+       COALESCE(p.executed_count, 0) AS executed_count,
+       COALESCE(p.voted_proposals_count, 0) AS voted_proposals_count,
+       COALESCE(p.for_ratio_sum, 0) AS for_ratio_sum,
+       COALESCE(v.voters_count, 0) AS voters_count,
+       COALESCE(v.votes_count, 0) AS votes_count,
+       COALESCE(v.locked_votes, 0) AS locked_votes,
+       (extract(epoch FROM now()) * 1000)::BIGINT AS refreshed_at
+FROM proposal_aggregates AS p
+         FULL JOIN vote_aggregates AS v ON p.dao_root = v.dao_root;
+
+-- required by REFRESH MATERIALIZED VIEW CONCURRENTLY
+CREATE UNIQUE INDEX dao_stats_dao_root_idx ON dao_stats (dao_root);
@@ -0,0 +1,36 @@
+CREATE TABLE IF NOT EXISTS daily_voting_stats
+(
+    dao_root        VARCHAR NOT NULL,
+    day_start       INTEGER NOT NULL,
+    votes_count     BIGINT  NOT NULL DEFAULT 0,
+    proposals_count BIGINT  NOT NULL DEFAULT 0,
+    voters_count    BIGINT  NOT NULL DEFAULT 0,
+    vote_weight     NUMERIC NOT NULL DEFAULT 0,
+    PRIMARY KEY (dao_root, day_start)
+);
+
+INSERT INTO daily_voting_stats (dao_root, day_start, votes_count, proposals_count, voters_count, vote_weight)
+SELECT dao_root,
+       day_start,
+       SUM(votes_count),
+       SUM(proposals_count),
+       SUM(voters_count),
+       SUM(vote_weight)
+FROM (SELECT dao_root,
+             timestamp_block - timestamp_block % 86400 AS day_start,
+             COUNT(*)                                  AS votes_count,
+             0                                         AS proposals_count,
+             COUNT(DISTINCT voter)                     AS voters_count,
+             SUM(votes)                                AS vote_weight
+      FROM votes
+      GROUP BY dao_root, day_start
+      UNION ALL
+      SELECT dao_root,
+             timestamp_block - timestamp_block % 86400 AS day_start,
+             0,
+             COUNT(*),
+             0,
+             0
+      FROM proposals
+      GROUP BY dao_root, day_start) AS days
+GROUP BY dao_root, day_start;
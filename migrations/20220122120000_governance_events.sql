@@ -0,0 +1,25 @@
+CREATE TYPE governance_event_type as ENUM (
+    'ProposalCreated',
+    'VoteCast',
+    'VoteUnlocked',
+    'Queued',
+    'Executed',
+    'Canceled'
+    );
+
+-- Log of applied governance events, ids are used to resume event streams
+CREATE TABLE IF NOT EXISTS governance_events
+(
+    id                  BIGSERIAL             NOT NULL,
+    kind                governance_event_type NOT NULL,
+    dao_root            VARCHAR               NOT NULL,
+    proposal_id         INTEGER               NOT NULL,
+    voter               VARCHAR,
+    data                jsonb                 NOT NULL,
+    timestamp_block     INTEGER               NOT NULL,
+    created_at          BIGINT                NOT NULL DEFAULT extract(epoch from (CURRENT_TIMESTAMP(3) at time zone 'utc')) * 1000,
+    PRIMARY KEY (id)
+);
+
+CREATE INDEX governance_events_proposal_idx ON governance_events (dao_root, proposal_id, id);
+CREATE INDEX governance_events_voter_idx ON governance_events (voter, id) WHERE voter IS NOT NULL;
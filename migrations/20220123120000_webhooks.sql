@@ -0,0 +1,40 @@
+CREATE TYPE webhook_delivery_state_type as ENUM (
+    'Pending',
+    'Success',
+    'Fail',
+    'DeadLetter'
+    );
+
+CREATE TABLE IF NOT EXISTS webhooks
+(
+    id                  BIGSERIAL               NOT NULL,
+    url                 VARCHAR                 NOT NULL,
+    secret              VARCHAR                 NOT NULL,
+    -- all DAO roots if not set
+    dao_root            VARCHAR,
+    -- all event kinds if empty
+    kinds               governance_event_type[] NOT NULL DEFAULT '{}',
+    -- only votes with at least this weight if set
+    min_vote_weight     NUMERIC,
+    created_at          BIGINT                  NOT NULL DEFAULT extract(epoch from (CURRENT_TIMESTAMP(3) at time zone 'utc')) * 1000,
+    PRIMARY KEY (id)
+);
+
+CREATE TABLE IF NOT EXISTS webhook_deliveries
+(
+    id                  BIGSERIAL                   NOT NULL,
+    webhook_id          BIGINT                      NOT NULL REFERENCES webhooks (id) ON DELETE CASCADE,
+    event_id            BIGINT                      NOT NULL,
+    payload             jsonb                       NOT NULL,
+    state               webhook_delivery_state_type NOT NULL DEFAULT 'Pending',
+    attempts            INTEGER                     NOT NULL DEFAULT 0,
+    last_error          VARCHAR,
+    response_status     INTEGER,
+    next_attempt_at     BIGINT                      NOT NULL DEFAULT extract(epoch from (CURRENT_TIMESTAMP(3) at time zone 'utc')) * 1000,
+    created_at          BIGINT                      NOT NULL DEFAULT extract(epoch from (CURRENT_TIMESTAMP(3) at time zone 'utc')) * 1000,
+    updated_at          BIGINT,
+    PRIMARY KEY (id),
+    UNIQUE (webhook_id, event_id)
+);
+
+CREATE INDEX webhook_deliveries_due_idx ON webhook_deliveries (next_attempt_at) WHERE state IN ('Pending', 'Fail');
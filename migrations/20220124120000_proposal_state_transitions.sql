@@ -0,0 +1,43 @@
+ALTER TYPE governance_event_type ADD VALUE IF NOT EXISTS 'StateChanged';
+
+-- Transitions which happen by the clock: Active, Succeeded, Failed and Expired
+CREATE TABLE IF NOT EXISTS proposal_state_transitions
+(
+    dao_root            VARCHAR NOT NULL,
+    proposal_id         INTEGER NOT NULL,
+    state               VARCHAR NOT NULL,
+    transition_time     BIGINT  NOT NULL,
+    created_at          BIGINT  NOT NULL DEFAULT extract(epoch from (CURRENT_TIMESTAMP(3) at time zone 'utc')) * 1000,
+    PRIMARY KEY (dao_root, proposal_id, state)
+);
+
+-- Past transitions are recorded without notifying subscribers
+WITH proposal_times AS (
+    SELECT dao_root,
+           id,
+           start_time,
+           end_time,
+           execution_time,
+           execution_time + grace_period                                       AS expires_at,
+           executed,
+           for_votes <= against_votes OR for_votes < quorum_votes              AS failed,
+           COALESCE(CASE WHEN canceled THEN canceled_at END,
+                    extract(epoch FROM now())::BIGINT)                         AS closed_at
+    FROM proposals
+)
+INSERT INTO proposal_state_transitions (dao_root, proposal_id, state, transition_time)
+SELECT dao_root, id, 'Active', start_time
+FROM proposal_times
+WHERE start_time < closed_at
+UNION ALL
+SELECT dao_root, id, CASE WHEN failed THEN 'Failed' ELSE 'Succeeded' END, end_time
+FROM proposal_times
+WHERE end_time < closed_at
+UNION ALL
+SELECT dao_root, id, 'Expired', expires_at
+FROM proposal_times
+WHERE end_time < closed_at
+  AND NOT failed
+  AND execution_time <> 0
+  AND NOT executed
+  AND expires_at < closed_at;
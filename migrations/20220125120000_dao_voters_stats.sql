@@ -0,0 +1,8 @@
+-- Distinct voters can't be summed over DAO roots, so the total is kept separately
+CREATE MATERIALIZED VIEW dao_voters_stats AS
+SELECT TRUE                  AS total,
+       COUNT(DISTINCT voter) AS voters_count
+FROM votes;
+
+-- required by REFRESH MATERIALIZED VIEW CONCURRENTLY
+CREATE UNIQUE INDEX dao_voters_stats_total_idx ON dao_voters_stats (total);
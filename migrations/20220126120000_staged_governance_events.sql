@@ -0,0 +1,13 @@
+-- Events of a transaction are moved to the log right before its commit,
+-- so the log is locked only for the move
+CREATE TABLE IF NOT EXISTS staged_governance_events
+(
+    id                  BIGSERIAL             NOT NULL,
+    kind                governance_event_type NOT NULL,
+    dao_root            VARCHAR               NOT NULL,
+    proposal_id         INTEGER               NOT NULL,
+    voter               VARCHAR,
+    data                jsonb                 NOT NULL,
+    timestamp_block     INTEGER               NOT NULL,
+    PRIMARY KEY (id)
+);
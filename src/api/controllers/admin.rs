@@ -0,0 +1,156 @@
+use super::Context;
+
+use crate::api::requests::*;
+use crate::api::responses::*;
+use crate::api::utils::*;
+
+pub async fn post_pending_actions_search(
+    ctx: Context,
+    input: PendingActionsRequest,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let (pending_actions, total_count) = ctx
+        .services
+        .search_pending_actions(input.into())
+        .await
+        .map_err(BadRequestError)?;
+
+    Ok(warp::reply::json(&PendingActionsResponse {
+        pending_actions: pending_actions
+            .map(PendingActionResponse::from)
+            .collect::<Vec<_>>(),
+        total_count,
+    }))
+}
+
+pub async fn post_failed_transactions_search(
+    ctx: Context,
+    input: FailedTransactionsRequest,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let (transactions, total_count) = ctx
+        .services
+        .search_failed_transactions(input.into())
+        .await
+        .map_err(BadRequestError)?;
+
+    Ok(warp::reply::json(&FailedTransactionsResponse {
+        transactions: transactions
+            .map(FailedTransactionResponse::from)
+            .collect::<Vec<_>>(),
+        total_count,
+    }))
+}
+
+pub async fn get_failed_transaction(
+    transaction_hash: String,
+    ctx: Context,
+) -> Result<Box<dyn warp::Reply>, warp::Rejection> {
+    let transaction_hash =
+        hex::decode(transaction_hash).map_err(|e| BadRequestError(anyhow::Error::new(e)))?;
+
+    let transaction = ctx
+        .services
+        .get_failed_transaction(&transaction_hash)
+        .await
+        .map_err(BadRequestError)?;
+
+    Ok(match transaction {
+        Some(transaction) => Box::new(warp::reply::json(&FailedTransactionResponse::from(
+            transaction,
+        ))),
+        None => Box::new(not_found()),
+    })
+}
+
+pub async fn post_failed_transaction_requeue(
+    transaction_hash: String,
+    ctx: Context,
+) -> Result<Box<dyn warp::Reply>, warp::Rejection> {
+    let transaction_hash =
+        hex::decode(transaction_hash).map_err(|e| BadRequestError(anyhow::Error::new(e)))?;
+
+    let transaction = ctx
+        .services
+        .requeue_failed_transaction(&transaction_hash)
+        .await
+        .map_err(BadRequestError)?;
+
+    Ok(match transaction {
+        Some(transaction) => Box::new(warp::reply::json(&FailedTransactionResponse::from(
+            transaction,
+        ))),
+        None => Box::new(not_found()),
+    })
+}
+
+pub async fn get_unknown_ton_functions(ctx: Context) -> Result<impl warp::Reply, warp::Rejection> {
+    let functions = ctx
+        .services
+        .unknown_ton_functions()
+        .await
+        .map_err(BadRequestError)?;
+
+    Ok(warp::reply::json(
+        &functions
+            .into_iter()
+            .map(UnknownTonFunctionResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+pub async fn post_webhook(
+    ctx: Context,
+    input: WebhookRequest,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let webhook = ctx
+        .services
+        .create_webhook(input.into())
+        .await
+        .map_err(BadRequestError)?;
+
+    Ok(warp::reply::json(&WebhookResponse::from(webhook)))
+}
+
+pub async fn get_webhooks(ctx: Context) -> Result<impl warp::Reply, warp::Rejection> {
+    let webhooks = ctx.services.webhooks().await.map_err(BadRequestError)?;
+
+    Ok(warp::reply::json(
+        &webhooks
+            .into_iter()
+            .map(WebhookResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+pub async fn delete_webhook(
+    id: i64,
+    ctx: Context,
+) -> Result<Box<dyn warp::Reply>, warp::Rejection> {
+    let webhook = ctx
+        .services
+        .delete_webhook(id)
+        .await
+        .map_err(BadRequestError)?;
+
+    Ok(match webhook {
+        Some(webhook) => Box::new(warp::reply::json(&WebhookResponse::from(webhook))),
+        None => Box::new(not_found()),
+    })
+}
+
+pub async fn post_webhook_deliveries_search(
+    ctx: Context,
+    input: WebhookDeliveriesRequest,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let (deliveries, total_count) = ctx
+        .services
+        .search_webhook_deliveries(input.into())
+        .await
+        .map_err(BadRequestError)?;
+
+    Ok(warp::reply::json(&WebhookDeliveriesResponse {
+        deliveries: deliveries
+            .map(WebhookDeliveryResponse::from)
+            .collect::<Vec<_>>(),
+        total_count,
+    }))
+}
@@ -0,0 +1,21 @@
+use super::Context;
+
+use crate::api::requests::*;
+use crate::api::responses::*;
+use crate::api::utils::*;
+
+pub async fn post_audit_search(
+    ctx: Context,
+    input: AuditEventsRequest,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let (events, total_count) = ctx
+        .services
+        .search_audit_events(input.into())
+        .await
+        .map_err(BadRequestError)?;
+
+    Ok(warp::reply::json(&AuditEventsResponse {
+        events: events.map(AuditEventResponse::from).collect::<Vec<_>>(),
+        total_count,
+    }))
+}
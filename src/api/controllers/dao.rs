@@ -0,0 +1,50 @@
+use super::Context;
+
+use crate::api::requests::*;
+use crate::api::responses::*;
+use crate::api::utils::*;
+
+pub async fn get_dao_config(
+    ctx: Context,
+    input: DaoConfigRequest,
+) -> Result<Box<dyn warp::Reply>, warp::Rejection> {
+    let config = ctx
+        .services
+        .current_dao_config(input.dao_root)
+        .await
+        .map_err(BadRequestError)?;
+
+    Ok(match config {
+        Some(config) => Box::new(warp::reply::json(&DaoConfigResponse::from(config))),
+        None => Box::new(not_found()),
+    })
+}
+
+pub async fn post_dao_config_history(
+    ctx: Context,
+    input: DaoConfigHistoryRequest,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let (history, total_count) = ctx
+        .services
+        .search_dao_config_history(input.into())
+        .await
+        .map_err(BadRequestError)?;
+
+    Ok(warp::reply::json(&DaoConfigHistoryResponse {
+        history: history.map(DaoConfigResponse::from).collect::<Vec<_>>(),
+        total_count,
+    }))
+}
+
+pub async fn get_dao_stats(
+    ctx: Context,
+    input: DaoStatsRequest,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let stats = ctx
+        .services
+        .dao_stats(input.dao_root)
+        .await
+        .map_err(BadRequestError)?;
+
+    Ok(warp::reply::json(&DaoStatsResponse::from(stats)))
+}
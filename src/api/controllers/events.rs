@@ -0,0 +1,28 @@
+use futures::StreamExt;
+use warp::sse::Event;
+
+use super::Context;
+
+use crate::api::requests::*;
+use crate::api::responses::*;
+use crate::api::utils::*;
+
+pub async fn get_events_stream(
+    ctx: Context,
+    last_event_id: Option<i64>,
+    input: EventsStreamRequest,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let events = ctx
+        .services
+        .governance_events_stream(input.into(), last_event_id)
+        .await
+        .map_err(BadRequestError)?
+        .map(|event| {
+            Event::default()
+                .id(event.id.to_string())
+                .event(event.kind.to_string())
+                .json_data(GovernanceEventResponse::from(event))
+        });
+
+    Ok(warp::sse::reply(warp::sse::keep_alive().stream(events)))
+}
@@ -0,0 +1,15 @@
+use super::Context;
+
+use crate::api::graphql;
+use crate::api::requests::*;
+use crate::api::responses::*;
+
+pub async fn post_graphql(
+    ctx: Context,
+    input: GraphqlRequest,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let request = graphql::with_loaders(input.into(), &ctx.services);
+    let response = ctx.graphql_schema.execute(request).await;
+
+    Ok(warp::reply::json(&GraphqlResponse::from(response)))
+}
@@ -1,9 +1,16 @@
 use std::sync::Arc;
 
+use crate::api::graphql::GraphqlSchema;
 use crate::services::*;
 use crate::sqlx_client::*;
 
+pub mod admin;
+pub mod audit;
+pub mod dao;
+pub mod events;
+pub mod graphql;
 pub mod proposals;
+pub mod stats;
 pub mod voters;
 pub mod votes;
 
@@ -11,6 +18,8 @@ pub mod votes;
 pub struct Context {
     pub services: Arc<Services>,
     pub sqlx_client: SqlxClient,
+    pub graphql_schema: GraphqlSchema,
     pub prod_url: String,
     pub test_url: String,
+    pub admin_token: Option<String>,
 }
@@ -8,18 +8,74 @@ pub async fn post_proposals_search(
     ctx: Context,
     input: ProposalsRequest,
 ) -> Result<impl warp::Reply, warp::Rejection> {
-    let (proposals, total_count) = ctx
+    let with_total_count = input.with_total_count.unwrap_or(true);
+    let page = ctx
         .services
-        .search_proposals(input.into())
+        .search_proposals(input.into(), with_total_count)
         .await
         .map_err(BadRequestError)?;
 
     Ok(warp::reply::json(&ProposalsResponse {
-        proposals: proposals.map(ProposalResponse::from).collect::<Vec<_>>(),
-        total_count,
+        proposals: page
+            .items
+            .into_iter()
+            .map(ProposalResponse::from)
+            .collect::<Vec<_>>(),
+        total_count: page.total_count,
+        next_cursor: page.next_cursor,
     }))
 }
 
+pub async fn get_proposal(
+    proposal_id: i32,
+    ctx: Context,
+    input: ProposalRequest,
+) -> Result<Box<dyn warp::Reply>, warp::Rejection> {
+    let proposal = ctx
+        .services
+        .get_proposal(input.dao_root, proposal_id)
+        .await
+        .map_err(BadRequestError)?;
+
+    Ok(match proposal {
+        Some(proposal) => Box::new(warp::reply::json(&ProposalDetailsResponse::from(proposal))),
+        None => Box::new(not_found()),
+    })
+}
+
+pub async fn get_proposal_timeline(
+    proposal_id: i32,
+    ctx: Context,
+    input: ProposalTimelineRequest,
+) -> Result<Box<dyn warp::Reply>, warp::Rejection> {
+    let timeline = ctx
+        .services
+        .get_proposal_timeline(input.dao_root, proposal_id, input.interval)
+        .await
+        .map_err(BadRequestError)?;
+
+    Ok(match timeline {
+        Some(timeline) => Box::new(warp::reply::json(&ProposalTimelineResponse::from(timeline))),
+        None => Box::new(not_found()),
+    })
+}
+
+pub async fn get_proposal_by_address(
+    address: String,
+    ctx: Context,
+) -> Result<Box<dyn warp::Reply>, warp::Rejection> {
+    let proposal = ctx
+        .services
+        .get_proposal_by_address(address)
+        .await
+        .map_err(BadRequestError)?;
+
+    Ok(match proposal {
+        Some(proposal) => Box::new(warp::reply::json(&ProposalDetailsResponse::from(proposal))),
+        None => Box::new(not_found()),
+    })
+}
+
 pub async fn get_proposals_overview(ctx: Context) -> Result<impl warp::Reply, warp::Rejection> {
     let overview = ctx.services.overview().await.map_err(BadRequestError)?;
 
@@ -0,0 +1,23 @@
+use super::Context;
+
+use crate::api::requests::*;
+use crate::api::responses::*;
+use crate::api::utils::*;
+
+pub async fn post_timeseries(
+    ctx: Context,
+    input: TimeseriesRequest,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let points = ctx
+        .services
+        .timeseries(input.into())
+        .await
+        .map_err(BadRequestError)?;
+
+    Ok(warp::reply::json(&TimeseriesResponse {
+        points: points
+            .into_iter()
+            .map(TimeseriesPointResponse::from)
+            .collect(),
+    }))
+}
@@ -9,20 +9,24 @@ pub async fn post_voters_search(
     ctx: Context,
     input: VotersRequest,
 ) -> Result<impl warp::Reply, warp::Rejection> {
-    let (resp, total_count) = ctx
+    let with_total_count = input.with_total_count.unwrap_or(true);
+    let page = ctx
         .services
-        .search_proposals_with_votes(address, input.into())
+        .search_proposals_with_votes(address, input.into(), with_total_count)
         .await
         .map_err(BadRequestError)?;
 
     Ok(warp::reply::json(&ProposalsWithVotesResponse {
-        proposal_with_votes: resp
+        proposal_with_votes: page
+            .items
+            .into_iter()
             .map(|(proposal, vote)| ProposalWithVoteResponse {
                 vote: vote.into(),
                 proposal: proposal.into(),
             })
             .collect::<Vec<_>>(),
-        total_count,
+        total_count: page.total_count,
+        next_cursor: page.next_cursor,
     }))
 }
 
@@ -32,7 +36,7 @@ pub async fn post_voters_proposal_count(
 ) -> Result<impl warp::Reply, warp::Rejection> {
     let resp = ctx
         .services
-        .proposals_count(input.voters)
+        .proposals_count(input.dao_root, input.voters)
         .await
         .map_err(BadRequestError)?;
 
@@ -8,14 +8,20 @@ pub async fn post_votes_search(
     ctx: Context,
     input: VotesRequest,
 ) -> Result<impl warp::Reply, warp::Rejection> {
-    let (votes, total_count) = ctx
+    let with_total_count = input.with_total_count.unwrap_or(true);
+    let page = ctx
         .services
-        .search_votes(input.into())
+        .search_votes(input.into(), with_total_count)
         .await
         .map_err(BadRequestError)?;
 
     Ok(warp::reply::json(&VotesResponse {
-        votes: votes.map(VoteResponse::from).collect::<Vec<_>>(),
-        total_count,
+        votes: page
+            .items
+            .into_iter()
+            .map(VoteResponse::from)
+            .collect::<Vec<_>>(),
+        total_count: page.total_count,
+        next_cursor: page.next_cursor,
     }))
 }
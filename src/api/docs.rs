@@ -11,18 +11,210 @@ pub fn swagger(prod_url: &str, test_url: &str) -> String {
         info: {
             title: "DAO",
             version: "1.0.0",
-            description: r##"This API allows you to get the information of DAO"##,
+            description: r##"This API allows you to get the information of DAO. Admin routes require the `Authorization: Bearer <ADMIN_TOKEN>` header"##,
         },
         servers: {
             prod_url,
             test_url
         },
         tags: {
+            admin,
+            audit,
+            dao,
+            events,
+            graphql,
             proposals,
+            stats,
             voters,
             votes,
         },
         paths: {
+            ("admin" / "failed_transactions" / "search" ): {
+                POST: {
+                    tags: { admin },
+                    summary: "Failed transactions search",
+                    description: "Get transactions which failed to be indexed.",
+                    body: requests::FailedTransactionsRequest,
+                    200: responses::FailedTransactionsResponse,
+                }
+            },
+            ("admin" / "failed_transactions" / { transactionHash: String } ): {
+                GET: {
+                    tags: { admin },
+                    summary: "Failed transaction",
+                    description: "Get failed transaction with its last error.",
+                    200: responses::FailedTransactionResponse,
+                }
+            },
+            ("admin" / "failed_transactions" / { transactionHash: String } / "requeue" ): {
+                POST: {
+                    tags: { admin },
+                    summary: "Requeue failed transaction",
+                    description: "Reset attempts of a failed or dead letter transaction and retry it.",
+                    200: responses::FailedTransactionResponse,
+                }
+            },
+            ("admin" / "pending_actions" / "search" ): {
+                POST: {
+                    tags: { admin },
+                    summary: "Pending actions search",
+                    description: "Get proposal and vote actions waiting for their proposal or vote to be indexed.",
+                    body: requests::PendingActionsRequest,
+                    200: responses::PendingActionsResponse,
+                }
+            },
+            ("admin" / "unknown_ton_functions" ): {
+                GET: {
+                    tags: { admin },
+                    summary: "Unknown TON functions",
+                    description: "Get function IDs of proposal TON actions missing in the ABI registry.",
+                    200: Vec<responses::UnknownTonFunctionResponse>,
+                }
+            },
+            ("admin" / "webhooks" ): {
+                GET: {
+                    tags: { admin },
+                    summary: "Webhooks",
+                    description: "Get registered webhooks.",
+                    200: Vec<responses::WebhookResponse>,
+                },
+                POST: {
+                    tags: { admin },
+                    summary: "Register webhook",
+                    description: "Register a URL receiving governance events. Payloads are signed with HMAC-SHA256 of `{X-Dao-Timestamp}.{body}` passed in the X-Dao-Signature header.",
+                    body: requests::WebhookRequest,
+                    200: responses::WebhookResponse,
+                }
+            },
+            ("admin" / "webhooks" / { webhookId: i64 } ): {
+                DELETE: {
+                    tags: { admin },
+                    summary: "Delete webhook",
+                    description: "Delete webhook with its deliveries.",
+                    200: responses::WebhookResponse,
+                }
+            },
+            ("admin" / "webhooks" / "deliveries" / "search" ): {
+                POST: {
+                    tags: { admin },
+                    summary: "Webhook deliveries search",
+                    description: "Get webhook deliveries with their last error.",
+                    body: requests::WebhookDeliveriesRequest,
+                    200: responses::WebhookDeliveriesResponse,
+                }
+            },
+            ("audit" / "search" ): {
+                POST: {
+                    tags: { audit },
+                    summary: "Audit events search",
+                    description: "Get privileged DAO changes.",
+                    body: requests::AuditEventsRequest,
+                    200: responses::AuditEventsResponse,
+                }
+            },
+            ("dao" / "config" ): {
+                GET: {
+                    tags: { dao },
+                    summary: "Dao config",
+                    description: "Get current governance parameters, `daoRoot` is required when several DAOs are indexed.",
+                    parameters: {
+                        (query daoRoot: String): {
+                            required: false,
+                        },
+                    },
+                    200: responses::DaoConfigResponse,
+                }
+            },
+            ("dao" / "config" / "history" ): {
+                POST: {
+                    tags: { dao },
+                    summary: "Dao config history",
+                    description: "Get governance parameters changes.",
+                    body: requests::DaoConfigHistoryRequest,
+                    200: responses::DaoConfigHistoryResponse,
+                }
+            },
+            ("dao" / "stats" ): {
+                GET: {
+                    tags: { dao },
+                    summary: "Dao stats",
+                    description: "Get governance statistics, refreshed every minute.",
+                    parameters: {
+                        (query daoRoot: String): {
+                            required: false,
+                        },
+                    },
+                    200: responses::DaoStatsResponse,
+                }
+            },
+            ("events" / "stream" ): {
+                GET: {
+                    tags: { events },
+                    summary: "Events stream",
+                    description: "Server-sent events of created proposals, cast and unlocked votes, queued, executed and canceled proposals, and proposal state changes which happen by time (StateChanged). Event ids can be passed in the Last-Event-ID header to resume the stream. After a full reindex the history is logged again with new ids, so resumed streams receive it again.",
+                    parameters: {
+                        (query daoRoot: String): {
+                            required: false,
+                        },
+                        (query proposalId: i32): {
+                            required: false,
+                        },
+                        (query voter: String): {
+                            required: false,
+                        },
+                        (header "Last-Event-ID"): {
+                            required: false,
+                        },
+                    },
+                    200: responses::GovernanceEventResponse,
+                }
+            },
+            ("graphql" ): {
+                POST: {
+                    tags: { graphql },
+                    summary: "GraphQL",
+                    description: "Query proposals, votes and voters with nested proposal votes, vote proposal and voter votes. Nested fields of sibling objects are loaded in batches.",
+                    body: requests::GraphqlRequest,
+                    200: responses::GraphqlResponse,
+                }
+            },
+            ("proposals" / { proposalId: i32 } ): {
+                GET: {
+                    tags: { proposals },
+                    summary: "Proposal",
+                    description: "Get proposal with votes count, quorum progress and state history. Without daoRoot the latest proposal with the id is returned.",
+                    parameters: {
+                        (query daoRoot: String): {
+                            required: false,
+                        },
+                    },
+                    200: responses::ProposalDetailsResponse,
+                }
+            },
+            ("proposals" / { proposalId: i32 } / "timeline" ): {
+                GET: {
+                    tags: { proposals },
+                    summary: "Proposal timeline",
+                    description: "Get cumulative for and against votes and voters count after each vote, and the moment the quorum was reached.",
+                    parameters: {
+                        (query daoRoot: String): {
+                            required: false,
+                        },
+                        (query interval: String): {
+                            required: false,
+                        },
+                    },
+                    200: responses::ProposalTimelineResponse,
+                }
+            },
+            ("proposals" / "by-address" / { proposalAddress: String } ): {
+                GET: {
+                    tags: { proposals },
+                    summary: "Proposal by address",
+                    description: "Get proposal with votes count, quorum progress and state history.",
+                    200: responses::ProposalDetailsResponse,
+                }
+            },
             ("proposals" / "overview" ): {
                 GET: {
                     tags: { proposals },
@@ -40,6 +232,15 @@ pub fn swagger(prod_url: &str, test_url: &str) -> String {
                     200: responses::ProposalsResponse,
                 }
             },
+            ("stats" / "timeseries" ): {
+                POST: {
+                    tags: { stats },
+                    summary: "Voting timeseries",
+                    description: "Get votes, new proposals, distinct voters and vote weight per hour, day or week.",
+                    body: requests::TimeseriesRequest,
+                    200: responses::TimeseriesResponse,
+                }
+            },
             ("votes" / "search" ): {
                 POST: {
                     tags: { votes },
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_graphql::dataloader::Loader;
+use itertools::Itertools;
+
+use crate::models::*;
+use crate::services::Services;
+use crate::utils::*;
+
+/// Proposals by `(dao_root, id)`
+pub struct ProposalsLoader(pub Arc<Services>);
+
+#[async_trait::async_trait]
+impl Loader<(String, i32)> for ProposalsLoader {
+    type Value = ProposalFromDb;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(
+        &self,
+        keys: &[(String, i32)],
+    ) -> Result<HashMap<(String, i32), Self::Value>, Self::Error> {
+        let proposals = self
+            .0
+            .get_proposals_by_ids(keys.to_vec())
+            .await
+            .map_err(Arc::new)?;
+
+        Ok(proposals
+            .into_iter()
+            .map(|x| ((x.dao_root.clone(), x.id), x))
+            .collect())
+    }
+}
+
+/// Pages of votes, the searches of sibling fields are sent as one query
+pub struct VotesLoader(pub Arc<Services>);
+
+#[async_trait::async_trait]
+impl Loader<VotesSearch> for VotesLoader {
+    type Value = Vec<VoteFromDb>;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(
+        &self,
+        keys: &[VotesSearch],
+    ) -> Result<HashMap<VotesSearch, Self::Value>, Self::Error> {
+        let pages = self
+            .0
+            .search_votes_batch(keys.to_vec())
+            .await
+            .map_err(Arc::new)?;
+
+        Ok(keys.iter().cloned().zip(pages).collect())
+    }
+}
+
+/// Voted proposals count by `(dao_root, voter)`, voters without votes are missing
+pub struct ProposalsCountLoader(pub Arc<Services>);
+
+#[async_trait::async_trait]
+impl Loader<(Option<String>, String)> for ProposalsCountLoader {
+    type Value = i64;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(
+        &self,
+        keys: &[(Option<String>, String)],
+    ) -> Result<HashMap<(Option<String>, String), Self::Value>, Self::Error> {
+        let mut counts = HashMap::with_capacity(keys.len());
+
+        for (dao_root, voters) in keys.iter().cloned().into_group_map() {
+            for voters in voters.chunks(MAX_LIMIT as usize) {
+                let voters_counts = self
+                    .0
+                    .proposals_count(dao_root.clone(), voters.to_vec())
+                    .await
+                    .map_err(Arc::new)?;
+
+                counts
+                    .extend(voters_counts.map(|(voter, count)| ((dao_root.clone(), voter), count)));
+            }
+        }
+
+        Ok(counts)
+    }
+}
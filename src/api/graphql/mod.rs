@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Result, Schema};
+
+use self::loaders::*;
+use self::types::*;
+use crate::models::*;
+use crate::services::Services;
+use crate::utils::*;
+
+mod loaders;
+mod types;
+
+pub type GraphqlSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+pub fn schema(services: Arc<Services>) -> GraphqlSchema {
+    const MAX_DEPTH: usize = 8;
+    // a full page of proposals with the first votes of each
+    const MAX_COMPLEXITY: usize = 20_000;
+
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(services)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
+
+/// Loaders cache rows for a single request, so they are created per request
+pub fn with_loaders(
+    request: async_graphql::Request,
+    services: &Arc<Services>,
+) -> async_graphql::Request {
+    request
+        .data(DataLoader::new(
+            ProposalsLoader(services.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(VotesLoader(services.clone()), tokio::spawn))
+        .data(DataLoader::new(
+            ProposalsCountLoader(services.clone()),
+            tokio::spawn,
+        ))
+}
+
+pub struct Query;
+
+#[Object]
+impl Query {
+    #[graphql(complexity = "list_complexity(limit, child_complexity)")]
+    async fn proposals(
+        &self,
+        ctx: &Context<'_>,
+        filters: Option<ProposalFiltersInput>,
+        limit: i32,
+        #[graphql(default)] offset: i32,
+    ) -> Result<Vec<Proposal>> {
+        let input = ProposalFilters::from(filters.unwrap_or_default())
+            .ordered(None)
+            .paginated(limit, offset);
+
+        let page = ctx
+            .data_unchecked::<Arc<Services>>()
+            .search_proposals(input, false)
+            .await?;
+
+        Ok(page
+            .items
+            .into_iter()
+            .map(|(proposal, _)| Proposal::from(proposal))
+            .collect())
+    }
+
+    async fn proposal(
+        &self,
+        ctx: &Context<'_>,
+        dao_root: Option<String>,
+        proposal_id: i32,
+    ) -> Result<Option<Proposal>> {
+        let proposal = ctx
+            .data_unchecked::<Arc<Services>>()
+            .get_proposal(dao_root, proposal_id)
+            .await?;
+
+        Ok(proposal.map(|(proposal, _)| Proposal::from(proposal)))
+    }
+
+    #[graphql(complexity = "list_complexity(limit, child_complexity)")]
+    async fn votes(
+        &self,
+        ctx: &Context<'_>,
+        filters: Option<VoteFiltersInput>,
+        limit: i32,
+        #[graphql(default)] offset: i32,
+    ) -> Result<Vec<Vote>> {
+        let input = VoteFilters::from(filters.unwrap_or_default())
+            .ordered(None)
+            .paginated(limit, offset);
+
+        let page = ctx
+            .data_unchecked::<Arc<Services>>()
+            .search_votes(input, false)
+            .await?;
+
+        Ok(page
+            .items
+            .into_iter()
+            .map(|(vote, _)| Vote::from(vote))
+            .collect())
+    }
+
+    /// Voters are not stored separately, so any address is accepted
+    async fn voter(&self, address: String, dao_root: Option<String>) -> Voter {
+        Voter { address, dao_root }
+    }
+
+    #[graphql(complexity = "addresses.len() * child_complexity")]
+    async fn voters(&self, addresses: Vec<String>, dao_root: Option<String>) -> Result<Vec<Voter>> {
+        if addresses.len() > MAX_LIMIT as usize {
+            return Err(format!("At most {} voters can be requested", MAX_LIMIT).into());
+        }
+
+        Ok(addresses
+            .into_iter()
+            .map(|address| Voter {
+                address,
+                dao_root: dao_root.clone(),
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,273 @@
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{ComplexObject, Context, Enum, InputObject, Json, Result, SimpleObject};
+
+use super::loaders::*;
+use crate::api::responses::{ProposalActionsResponse, ProposalResponse, VoteResponse};
+use crate::models::*;
+use crate::utils::*;
+
+/// Nested lists are short unless asked otherwise
+const DEFAULT_NESTED_LIMIT: i32 = 10;
+
+/// A list costs as much as its items, the limit is clamped the same way as in the queries
+pub fn list_complexity(limit: i32, child_complexity: usize) -> usize {
+    limit.clamp(0, MAX_LIMIT) as usize * child_complexity
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Enum)]
+#[graphql(name = "ProposalState", remote = "crate::models::ProposalState")]
+pub enum GraphqlProposalState {
+    Pending,
+    Active,
+    Canceled,
+    Failed,
+    Succeeded,
+    Expired,
+    Queued,
+    Executed,
+}
+
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct ProposalFiltersInput {
+    pub dao_root: Option<String>,
+    pub start_time_ge: Option<i32>,
+    pub start_time_le: Option<i32>,
+    pub end_time_ge: Option<i32>,
+    pub end_time_le: Option<i32>,
+    pub proposal_id: Option<i32>,
+    pub proposer: Option<String>,
+    pub proposal_address: Option<String>,
+    pub state: Option<GraphqlProposalState>,
+    /// Full-text search query over the proposal description
+    pub query: Option<String>,
+}
+
+impl From<ProposalFiltersInput> for ProposalFilters {
+    fn from(w: ProposalFiltersInput) -> Self {
+        ProposalFilters {
+            dao_root: w.dao_root,
+            start_time_ge: w.start_time_ge,
+            start_time_le: w.start_time_le,
+            end_time_ge: w.end_time_ge,
+            end_time_le: w.end_time_le,
+            proposal_id: w.proposal_id,
+            proposer: w.proposer,
+            proposal_address: w.proposal_address,
+            state: w.state.map(From::from),
+            query: w.query,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct VoteFiltersInput {
+    pub dao_root: Option<String>,
+    pub voter: Option<String>,
+    pub proposal_id: Option<i32>,
+    pub support: Option<bool>,
+    pub locked: Option<bool>,
+    /// Full-text search query over the vote reason
+    pub query: Option<String>,
+}
+
+impl From<VoteFiltersInput> for VoteFilters {
+    fn from(w: VoteFiltersInput) -> Self {
+        VoteFilters {
+            dao_root: w.dao_root,
+            voter: w.voter,
+            proposal_id: w.proposal_id,
+            support: w.support,
+            locked: w.locked,
+            query: w.query,
+        }
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(complex)]
+pub struct Proposal {
+    pub dao_root: String,
+    pub proposal_id: i32,
+    pub proposal_address: String,
+    pub proposer: String,
+    pub description: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub execution_time: Option<i64>,
+    pub grace_period: i64,
+    pub time_lock: i64,
+    pub voting_delay: i64,
+    pub for_votes: String,
+    pub against_votes: String,
+    pub quorum_votes: String,
+    pub message_hash: String,
+    pub transaction_hash: String,
+    pub actions: Json<ProposalActionsResponse>,
+    pub executed: bool,
+    pub canceled: bool,
+    pub queued: bool,
+    pub executed_at: Option<i32>,
+    pub canceled_at: Option<i32>,
+    pub queued_at: Option<i32>,
+    pub created_at: i32,
+    pub state: GraphqlProposalState,
+}
+
+impl From<ProposalFromDb> for Proposal {
+    fn from(x: ProposalFromDb) -> Self {
+        let x = ProposalResponse::from(x);
+
+        Self {
+            dao_root: x.dao_root,
+            proposal_id: x.proposal_id,
+            proposal_address: x.proposal_address,
+            proposer: x.proposer,
+            description: x.description,
+            start_time: x.start_time,
+            end_time: x.end_time,
+            execution_time: x.execution_time,
+            grace_period: x.grace_period,
+            time_lock: x.time_lock,
+            voting_delay: x.voting_delay,
+            for_votes: x.for_votes.to_string(),
+            against_votes: x.against_votes.to_string(),
+            quorum_votes: x.quorum_votes.to_string(),
+            message_hash: x.message_hash,
+            transaction_hash: x.transaction_hash,
+            actions: Json(x.actions),
+            executed: x.executed,
+            canceled: x.canceled,
+            queued: x.queued,
+            executed_at: x.executed_at,
+            canceled_at: x.canceled_at,
+            queued_at: x.queued_at,
+            created_at: x.created_at,
+            state: x.state.into(),
+        }
+    }
+}
+
+#[ComplexObject]
+impl Proposal {
+    /// The proposal is fixed, other filters apply as is
+    #[graphql(complexity = "list_complexity(limit, child_complexity)")]
+    async fn votes(
+        &self,
+        ctx: &Context<'_>,
+        filters: Option<VoteFiltersInput>,
+        #[graphql(default_with = "DEFAULT_NESTED_LIMIT")] limit: i32,
+        #[graphql(default)] offset: i32,
+    ) -> Result<Vec<Vote>> {
+        let filters = VoteFilters {
+            dao_root: Some(self.dao_root.clone()),
+            proposal_id: Some(self.proposal_id),
+            ..VoteFilters::from(filters.unwrap_or_default())
+        };
+
+        load_votes(ctx, filters, limit, offset).await
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(complex)]
+pub struct Vote {
+    pub dao_root: String,
+    pub proposal_id: i32,
+    pub voter: String,
+    pub support: bool,
+    pub reason: String,
+    pub votes: String,
+    pub locked: bool,
+    pub message_hash: String,
+    pub transaction_hash: String,
+    pub created_at: i32,
+}
+
+impl From<VoteFromDb> for Vote {
+    fn from(x: VoteFromDb) -> Self {
+        let x = VoteResponse::from(x);
+
+        Self {
+            dao_root: x.dao_root,
+            proposal_id: x.proposal_id,
+            voter: x.voter,
+            support: x.support,
+            reason: x.reason,
+            votes: x.votes.to_string(),
+            locked: x.locked,
+            message_hash: x.message_hash,
+            transaction_hash: x.transaction_hash,
+            created_at: x.created_at,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Vote {
+    async fn proposal(&self, ctx: &Context<'_>) -> Result<Option<Proposal>> {
+        let proposal = ctx
+            .data_unchecked::<DataLoader<ProposalsLoader>>()
+            .load_one((self.dao_root.clone(), self.proposal_id))
+            .await?;
+
+        Ok(proposal.map(Proposal::from))
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(complex)]
+pub struct Voter {
+    pub address: String,
+    /// Limits the nested fields to a single DAO
+    #[graphql(skip)]
+    pub dao_root: Option<String>,
+}
+
+#[ComplexObject]
+impl Voter {
+    async fn proposals_count(&self, ctx: &Context<'_>) -> Result<i64> {
+        let count = ctx
+            .data_unchecked::<DataLoader<ProposalsCountLoader>>()
+            .load_one((self.dao_root.clone(), self.address.clone()))
+            .await?;
+
+        Ok(count.unwrap_or_default())
+    }
+
+    /// The voter is fixed, other filters apply as is
+    #[graphql(complexity = "list_complexity(limit, child_complexity)")]
+    async fn votes(
+        &self,
+        ctx: &Context<'_>,
+        filters: Option<VoteFiltersInput>,
+        #[graphql(default_with = "DEFAULT_NESTED_LIMIT")] limit: i32,
+        #[graphql(default)] offset: i32,
+    ) -> Result<Vec<Vote>> {
+        let filters = VoteFilters::from(filters.unwrap_or_default());
+        let filters = VoteFilters {
+            dao_root: self.dao_root.clone().or(filters.dao_root),
+            voter: Some(self.address.clone()),
+            ..filters
+        };
+
+        load_votes(ctx, filters, limit, offset).await
+    }
+}
+
+async fn load_votes(
+    ctx: &Context<'_>,
+    filters: VoteFilters,
+    limit: i32,
+    offset: i32,
+) -> Result<Vec<Vote>> {
+    let votes = ctx
+        .data_unchecked::<DataLoader<VotesLoader>>()
+        .load_one(filters.ordered(None).paginated(limit, offset))
+        .await?;
+
+    Ok(votes
+        .unwrap_or_default()
+        .into_iter()
+        .map(Vote::from)
+        .collect())
+}
@@ -1,5 +1,6 @@
 pub mod controllers;
 mod docs;
+mod graphql;
 pub mod requests;
 pub mod responses;
 mod utils;
@@ -20,22 +21,26 @@ pub async fn http_service(
     services: Arc<Services>,
     sqlx_client: SqlxClient,
     prod_url: String,
-    test_url: String
+    test_url: String,
+    admin_token: Option<String>,
 ) {
+    let graphql_schema = graphql::schema(services.clone());
     let ctx = Context {
         services,
         sqlx_client,
+        graphql_schema,
         prod_url,
-        test_url
+        test_url,
+        admin_token,
     };
 
     let api = filters::server(ctx);
     let cors = warp::cors()
         .allow_any_origin()
-        .allow_headers(vec!["content-type"])
+        .allow_headers(vec!["content-type", "authorization"])
         .allow_methods(vec!["GET", "POST", "DELETE", "OPTIONS", "PUT"]);
     let log = warp::log("warp");
-    let routes = api.with(log).with(cors);
+    let routes = api.recover(recover_unauthorized).with(log).with(cors);
     warp::serve(routes).run(server_http_address).await;
 }
 
@@ -45,6 +50,7 @@ mod filters {
 
     use super::controllers::{self, Context};
     use crate::api::docs;
+    use crate::api::UnauthorizedError;
 
     pub fn server(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
         warp::any().and(api_v1(ctx).or(healthcheck())).boxed()
@@ -70,6 +76,25 @@ mod filters {
                     .or(post_voters_search(ctx.clone()))
                     .or(post_voters_proposals_count(ctx.clone()))
                     .or(get_proposals_overview(ctx.clone()))
+                    .or(get_proposal(ctx.clone()))
+                    .or(get_proposal_timeline(ctx.clone()))
+                    .or(get_proposal_by_address(ctx.clone()))
+                    .or(get_dao_config(ctx.clone()))
+                    .or(post_dao_config_history(ctx.clone()))
+                    .or(get_dao_stats(ctx.clone()))
+                    .or(post_stats_timeseries(ctx.clone()))
+                    .or(get_events_stream(ctx.clone()))
+                    .or(post_graphql(ctx.clone()))
+                    .or(post_audit_search(ctx.clone()))
+                    .or(post_admin_pending_actions_search(ctx.clone()))
+                    .or(post_admin_failed_transactions_search(ctx.clone()))
+                    .or(get_admin_failed_transaction(ctx.clone()))
+                    .or(post_admin_failed_transaction_requeue(ctx.clone()))
+                    .or(get_admin_unknown_ton_functions(ctx.clone()))
+                    .or(post_admin_webhook(ctx.clone()))
+                    .or(get_admin_webhooks(ctx.clone()))
+                    .or(delete_admin_webhook(ctx.clone()))
+                    .or(post_admin_webhook_deliveries_search(ctx.clone()))
                     .or(post_voters_proposals_count_search(ctx)),
             )
             .boxed()
@@ -100,6 +125,181 @@ mod filters {
             .boxed()
     }
 
+    fn get_proposal(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("proposals" / i32)
+            .and(warp::get())
+            .and(with_ctx(ctx))
+            .and(warp::query())
+            .and_then(controllers::proposals::get_proposal)
+            .boxed()
+    }
+
+    fn get_proposal_timeline(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("proposals" / i32 / "timeline")
+            .and(warp::get())
+            .and(with_ctx(ctx))
+            .and(warp::query())
+            .and_then(controllers::proposals::get_proposal_timeline)
+            .boxed()
+    }
+
+    fn get_proposal_by_address(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("proposals" / "by-address" / String)
+            .and(warp::get())
+            .and(with_ctx(ctx))
+            .and_then(controllers::proposals::get_proposal_by_address)
+            .boxed()
+    }
+
+    fn get_dao_config(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("dao" / "config")
+            .and(warp::get())
+            .and(with_ctx(ctx))
+            .and(warp::query())
+            .and_then(controllers::dao::get_dao_config)
+            .boxed()
+    }
+
+    fn post_dao_config_history(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("dao" / "config" / "history")
+            .and(warp::post())
+            .and(with_ctx(ctx))
+            .and(json_body())
+            .and_then(controllers::dao::post_dao_config_history)
+            .boxed()
+    }
+
+    fn get_dao_stats(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("dao" / "stats")
+            .and(warp::get())
+            .and(with_ctx(ctx))
+            .and(warp::query())
+            .and_then(controllers::dao::get_dao_stats)
+            .boxed()
+    }
+
+    fn post_stats_timeseries(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("stats" / "timeseries")
+            .and(warp::post())
+            .and(with_ctx(ctx))
+            .and(json_body())
+            .and_then(controllers::stats::post_timeseries)
+            .boxed()
+    }
+
+    fn get_events_stream(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("events" / "stream")
+            .and(warp::get())
+            .and(with_ctx(ctx))
+            .and(warp::header::optional::<i64>("last-event-id"))
+            .and(warp::query())
+            .and_then(controllers::events::get_events_stream)
+            .boxed()
+    }
+
+    fn post_graphql(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("graphql")
+            .and(warp::post())
+            .and(with_ctx(ctx))
+            .and(json_body())
+            .and_then(controllers::graphql::post_graphql)
+            .boxed()
+    }
+
+    fn post_audit_search(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("audit" / "search")
+            .and(warp::post())
+            .and(with_ctx(ctx))
+            .and(json_body())
+            .and_then(controllers::audit::post_audit_search)
+            .boxed()
+    }
+
+    fn post_admin_pending_actions_search(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("admin" / "pending_actions" / "search")
+            .and(warp::post())
+            .and(admin_auth(&ctx))
+            .and(with_ctx(ctx))
+            .and(json_body())
+            .and_then(controllers::admin::post_pending_actions_search)
+            .boxed()
+    }
+
+    fn post_admin_failed_transactions_search(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("admin" / "failed_transactions" / "search")
+            .and(warp::post())
+            .and(admin_auth(&ctx))
+            .and(with_ctx(ctx))
+            .and(json_body())
+            .and_then(controllers::admin::post_failed_transactions_search)
+            .boxed()
+    }
+
+    fn get_admin_failed_transaction(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("admin" / "failed_transactions" / String)
+            .and(warp::get())
+            .and(admin_auth(&ctx))
+            .and(with_ctx(ctx))
+            .and_then(controllers::admin::get_failed_transaction)
+            .boxed()
+    }
+
+    fn post_admin_failed_transaction_requeue(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("admin" / "failed_transactions" / String / "requeue")
+            .and(warp::post())
+            .and(admin_auth(&ctx))
+            .and(with_ctx(ctx))
+            .and_then(controllers::admin::post_failed_transaction_requeue)
+            .boxed()
+    }
+
+    fn get_admin_unknown_ton_functions(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("admin" / "unknown_ton_functions")
+            .and(warp::get())
+            .and(admin_auth(&ctx))
+            .and(with_ctx(ctx))
+            .and_then(controllers::admin::get_unknown_ton_functions)
+            .boxed()
+    }
+
+    fn post_admin_webhook(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("admin" / "webhooks")
+            .and(warp::post())
+            .and(admin_auth(&ctx))
+            .and(with_ctx(ctx))
+            .and(json_body())
+            .and_then(controllers::admin::post_webhook)
+            .boxed()
+    }
+
+    fn get_admin_webhooks(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("admin" / "webhooks")
+            .and(warp::get())
+            .and(admin_auth(&ctx))
+            .and(with_ctx(ctx))
+            .and_then(controllers::admin::get_webhooks)
+            .boxed()
+    }
+
+    fn delete_admin_webhook(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("admin" / "webhooks" / i64)
+            .and(warp::delete())
+            .and(admin_auth(&ctx))
+            .and(with_ctx(ctx))
+            .and_then(controllers::admin::delete_webhook)
+            .boxed()
+    }
+
+    fn post_admin_webhook_deliveries_search(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
+        warp::path!("admin" / "webhooks" / "deliveries" / "search")
+            .and(warp::post())
+            .and(admin_auth(&ctx))
+            .and(with_ctx(ctx))
+            .and(json_body())
+            .and_then(controllers::admin::post_webhook_deliveries_search)
+            .boxed()
+    }
+
     fn post_votes_search(ctx: Context) -> BoxedFilter<(impl warp::Reply,)> {
         warp::path!("votes" / "search")
             .and(warp::post())
@@ -146,6 +346,29 @@ mod filters {
         warp::body::json()
     }
 
+    /// Admin routes require the configured token as a bearer token
+    fn admin_auth(ctx: &Context) -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+        let admin_token = ctx.admin_token.clone();
+
+        warp::header::optional::<String>("authorization")
+            .and_then(move |authorization: Option<String>| {
+                let authorized = match (&admin_token, &authorization) {
+                    (Some(token), Some(authorization)) => {
+                        authorization.strip_prefix("Bearer ") == Some(token.as_str())
+                    }
+                    _ => false,
+                };
+                async move {
+                    if authorized {
+                        Ok(())
+                    } else {
+                        Err(warp::reject::custom(UnauthorizedError))
+                    }
+                }
+            })
+            .untuple_one()
+    }
+
     fn with_ctx(
         ctx: Context,
     ) -> impl Filter<Extract = (Context,), Error = std::convert::Infallible> + Clone {
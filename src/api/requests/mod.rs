@@ -1,3 +1,4 @@
+use rust_decimal::Decimal;
 use serde::Deserialize;
 
 use crate::models::*;
@@ -8,7 +9,18 @@ use crate::utils::*;
 #[opg("Proposals request")]
 pub struct ProposalsRequest {
     pub limit: i32,
+    #[serde(default)]
+    #[opg(optional)]
     pub offset: i32,
+    /// `nextCursor` of the previous page, replaces `offset`
+    #[opg(optional)]
+    pub cursor: Option<String>,
+    /// Whether to count all matching rows, `true` by default
+    #[opg(optional)]
+    pub with_total_count: Option<bool>,
+
+    #[opg(optional)]
+    pub dao_root: Option<String>,
 
     #[opg(optional)]
     pub start_time_ge: Option<i32>,
@@ -32,6 +44,10 @@ pub struct ProposalsRequest {
     #[opg(optional)]
     pub state: Option<ProposalState>,
 
+    /// Full-text search query over the proposal description
+    #[opg(optional)]
+    pub query: Option<String>,
+
     #[opg(optional)]
     pub ordering: Option<ProposalsOrdering>,
 }
@@ -39,6 +55,7 @@ pub struct ProposalsRequest {
 impl From<ProposalsRequest> for ProposalsSearch {
     fn from(w: ProposalsRequest) -> Self {
         ProposalFilters {
+            dao_root: w.dao_root,
             start_time_ge: w.start_time_ge,
             start_time_le: w.start_time_le,
             end_time_ge: w.end_time_ge,
@@ -47,18 +64,50 @@ impl From<ProposalsRequest> for ProposalsSearch {
             proposer: w.proposer,
             proposal_address: w.proposal_address,
             state: w.state,
+            query: w.query,
         }
         .ordered(w.ordering)
         .paginated(w.limit, w.offset)
+        .with_cursor(w.cursor)
     }
 }
 
+#[derive(Debug, Deserialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[opg("Proposal request")]
+pub struct ProposalRequest {
+    #[opg(optional)]
+    pub dao_root: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[opg("Proposal timeline request")]
+pub struct ProposalTimelineRequest {
+    #[opg(optional)]
+    pub dao_root: Option<String>,
+    /// A point per vote block time if not set
+    #[opg(optional)]
+    pub interval: Option<TimeseriesInterval>,
+}
+
 #[derive(Debug, serde::Deserialize, Clone, opg::OpgModel)]
 #[serde(rename_all = "camelCase")]
 #[opg("Proposal votes request")]
 pub struct VotesRequest {
     pub limit: i32,
+    #[serde(default)]
+    #[opg(optional)]
     pub offset: i32,
+    /// `nextCursor` of the previous page, replaces `offset`
+    #[opg(optional)]
+    pub cursor: Option<String>,
+    /// Whether to count all matching rows, `true` by default
+    #[opg(optional)]
+    pub with_total_count: Option<bool>,
+
+    #[opg(optional)]
+    pub dao_root: Option<String>,
 
     #[opg(optional)]
     pub proposal_id: Option<i32>,
@@ -72,6 +121,10 @@ pub struct VotesRequest {
     #[opg(optional)]
     pub locked: Option<bool>,
 
+    /// Full-text search query over the vote reason
+    #[opg(optional)]
+    pub query: Option<String>,
+
     #[opg(optional)]
     pub ordering: Option<VotesOrdering>,
 }
@@ -79,13 +132,16 @@ pub struct VotesRequest {
 impl From<VotesRequest> for VotesSearch {
     fn from(w: VotesRequest) -> Self {
         VoteFilters {
+            dao_root: w.dao_root,
             proposal_id: w.proposal_id,
             voter: w.voter,
             support: w.support,
             locked: w.locked,
+            query: w.query,
         }
         .ordered(w.ordering)
         .paginated(w.limit, w.offset)
+        .with_cursor(w.cursor)
     }
 }
 
@@ -94,7 +150,18 @@ impl From<VotesRequest> for VotesSearch {
 #[opg("Voters request")]
 pub struct VotersRequest {
     pub limit: i32,
+    #[serde(default)]
+    #[opg(optional)]
     pub offset: i32,
+    /// `nextCursor` of the previous page, replaces `offset`
+    #[opg(optional)]
+    pub cursor: Option<String>,
+    /// Whether to count all matching rows, `true` by default
+    #[opg(optional)]
+    pub with_total_count: Option<bool>,
+
+    #[opg(optional)]
+    pub dao_root: Option<String>,
 
     #[opg(optional)]
     pub start_time_ge: Option<i32>,
@@ -134,6 +201,7 @@ pub struct VotersRequest {
 impl From<VotersRequest> for VotersSearch {
     fn from(w: VotersRequest) -> Self {
         VoterFilters {
+            dao_root: w.dao_root,
             start_time_ge: w.start_time_ge,
             start_time_le: w.start_time_le,
             end_time_ge: w.end_time_ge,
@@ -148,6 +216,7 @@ impl From<VotersRequest> for VotersSearch {
         }
         .ordered(w.ordering)
         .paginated(w.limit, w.offset)
+        .with_cursor(w.cursor)
     }
 }
 
@@ -156,6 +225,9 @@ impl From<VotersRequest> for VotersSearch {
 #[opg("Proposals count request")]
 pub struct ProposalsCountRequest {
     pub voters: Vec<String>,
+
+    #[opg(optional)]
+    pub dao_root: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, opg::OpgModel)]
@@ -165,6 +237,9 @@ pub struct ProposalsCountSearchRequest {
     pub limit: i32,
     pub offset: i32,
 
+    #[opg(optional)]
+    pub dao_root: Option<String>,
+
     #[opg(optional)]
     pub voters: Option<Vec<String>>,
 
@@ -174,8 +249,278 @@ pub struct ProposalsCountSearchRequest {
 
 impl From<ProposalsCountSearchRequest> for VotersProposalsCountSearch {
     fn from(w: ProposalsCountSearchRequest) -> Self {
-        VotersProposalsCountFilters { voters: w.voters }
-            .ordered(w.ordering)
-            .paginated(w.limit, w.offset)
+        VotersProposalsCountFilters {
+            dao_root: w.dao_root,
+            voters: w.voters,
+        }
+        .ordered(w.ordering)
+        .paginated(w.limit, w.offset)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[opg("Dao config history request")]
+pub struct DaoConfigHistoryRequest {
+    pub limit: i32,
+    pub offset: i32,
+
+    #[opg(optional)]
+    pub dao_root: Option<String>,
+
+    #[opg(optional)]
+    pub kind: Option<DaoConfigEventKind>,
+
+    #[opg(optional)]
+    pub timestamp_block_ge: Option<i32>,
+    #[opg(optional)]
+    pub timestamp_block_le: Option<i32>,
+
+    #[opg(optional)]
+    pub ordering: Option<DaoConfigOrdering>,
+}
+
+impl From<DaoConfigHistoryRequest> for DaoConfigHistorySearch {
+    fn from(w: DaoConfigHistoryRequest) -> Self {
+        DaoConfigFilters {
+            dao_root: w.dao_root,
+            kind: w.kind,
+            timestamp_block_ge: w.timestamp_block_ge,
+            timestamp_block_le: w.timestamp_block_le,
+        }
+        .ordered(w.ordering)
+        .paginated(w.limit, w.offset)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[opg("Audit events request")]
+pub struct AuditEventsRequest {
+    pub limit: i32,
+    pub offset: i32,
+
+    #[opg(optional)]
+    pub dao_root: Option<String>,
+
+    #[opg(optional)]
+    pub kind: Option<GovernanceAuditEventKind>,
+
+    #[opg(optional)]
+    pub contract_address: Option<String>,
+
+    #[opg(optional)]
+    pub timestamp_block_ge: Option<i32>,
+    #[opg(optional)]
+    pub timestamp_block_le: Option<i32>,
+
+    #[opg(optional)]
+    pub ordering: Option<AuditEventsOrdering>,
+}
+
+impl From<AuditEventsRequest> for AuditEventsSearch {
+    fn from(w: AuditEventsRequest) -> Self {
+        AuditEventFilters {
+            dao_root: w.dao_root,
+            kind: w.kind,
+            contract_address: w.contract_address,
+            timestamp_block_ge: w.timestamp_block_ge,
+            timestamp_block_le: w.timestamp_block_le,
+        }
+        .ordered(w.ordering)
+        .paginated(w.limit, w.offset)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[opg("Events stream request")]
+pub struct EventsStreamRequest {
+    #[opg(optional)]
+    pub dao_root: Option<String>,
+    #[opg(optional)]
+    pub proposal_id: Option<i32>,
+    #[opg(optional)]
+    pub voter: Option<String>,
+}
+
+impl From<EventsStreamRequest> for GovernanceEventFilters {
+    fn from(w: EventsStreamRequest) -> Self {
+        GovernanceEventFilters {
+            dao_root: w.dao_root,
+            proposal_id: w.proposal_id,
+            voter: w.voter,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[opg("GraphQL request")]
+pub struct GraphqlRequest {
+    pub query: String,
+    #[opg(optional)]
+    pub operation_name: Option<String>,
+    #[opg("variables", any, optional)]
+    pub variables: Option<serde_json::Value>,
+}
+
+impl From<GraphqlRequest> for async_graphql::Request {
+    fn from(w: GraphqlRequest) -> Self {
+        let mut request = async_graphql::Request::new(w.query).variables(
+            async_graphql::Variables::from_json(w.variables.unwrap_or_default()),
+        );
+        if let Some(operation_name) = w.operation_name {
+            request = request.operation_name(operation_name);
+        }
+        request
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[opg("Dao config request")]
+pub struct DaoConfigRequest {
+    #[opg(optional)]
+    pub dao_root: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[opg("Dao stats request")]
+pub struct DaoStatsRequest {
+    #[opg(optional)]
+    pub dao_root: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[opg("Timeseries request")]
+pub struct TimeseriesRequest {
+    pub interval: TimeseriesInterval,
+    /// Inclusive, aligned down to the bucket start
+    pub from: i32,
+    /// Exclusive
+    pub to: i32,
+    #[opg(optional)]
+    pub dao_root: Option<String>,
+    #[opg(optional)]
+    pub proposal_id: Option<i32>,
+    #[opg(optional)]
+    pub voter: Option<String>,
+}
+
+impl From<TimeseriesRequest> for TimeseriesFilters {
+    fn from(w: TimeseriesRequest) -> Self {
+        TimeseriesFilters {
+            interval: w.interval,
+            from: w.from,
+            to: w.to,
+            dao_root: w.dao_root,
+            proposal_id: w.proposal_id,
+            voter: w.voter,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[opg("Pending actions request")]
+pub struct PendingActionsRequest {
+    pub limit: i32,
+    pub offset: i32,
+
+    #[opg(optional)]
+    pub kind: Option<PendingActionKind>,
+
+    /// Only actions pending for at least this many seconds
+    #[opg(optional)]
+    pub older_than: Option<i64>,
+}
+
+impl From<PendingActionsRequest> for PendingActionsSearch {
+    fn from(w: PendingActionsRequest) -> Self {
+        PendingActionFilters {
+            kind: w.kind,
+            created_at_le: w
+                .older_than
+                .map(|secs| chrono::Utc::now().timestamp_millis() - secs * 1000),
+        }
+        .paginated(w.limit, w.offset)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[opg("Failed transactions request")]
+pub struct FailedTransactionsRequest {
+    pub limit: i32,
+    pub offset: i32,
+
+    /// Both `Fail` and `DeadLetter` transactions if not set
+    #[opg(optional)]
+    pub state: Option<RawTransactionState>,
+}
+
+impl From<FailedTransactionsRequest> for FailedRawTransactionsSearch {
+    fn from(w: FailedTransactionsRequest) -> Self {
+        FailedRawTransactionFilters { state: w.state }.paginated(w.limit, w.offset)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[opg("Webhook request")]
+pub struct WebhookRequest {
+    pub url: String,
+    /// Key of the HMAC-SHA256 payload signature
+    pub secret: String,
+
+    /// All DAO roots if not set
+    #[opg(optional)]
+    pub dao_root: Option<String>,
+
+    /// All event kinds if not set
+    #[opg(optional)]
+    pub kinds: Option<Vec<GovernanceEventKind>>,
+
+    /// Only votes with at least this weight if set
+    #[opg("minVoteWeight", string, optional)]
+    pub min_vote_weight: Option<Decimal>,
+}
+
+impl From<WebhookRequest> for CreateWebhook {
+    fn from(w: WebhookRequest) -> Self {
+        CreateWebhook {
+            url: w.url,
+            secret: w.secret,
+            dao_root: w.dao_root,
+            kinds: w.kinds.unwrap_or_default(),
+            min_vote_weight: w.min_vote_weight,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[opg("Webhook deliveries request")]
+pub struct WebhookDeliveriesRequest {
+    pub limit: i32,
+    pub offset: i32,
+
+    #[opg(optional)]
+    pub webhook_id: Option<i64>,
+
+    #[opg(optional)]
+    pub state: Option<WebhookDeliveryState>,
+}
+
+impl From<WebhookDeliveriesRequest> for WebhookDeliveriesSearch {
+    fn from(w: WebhookDeliveriesRequest) -> Self {
+        WebhookDeliveryFilters {
+            webhook_id: w.webhook_id,
+            state: w.state,
+        }
+        .paginated(w.limit, w.offset)
     }
 }
@@ -3,12 +3,19 @@ use nekoton_utils::TrustMe;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
-use crate::models::{ProposalActions, ProposalFromDb, ProposalState, VoteFromDb};
+use crate::models::{
+    decode_eth_call, decode_ton_payload, format_function_id, AuditEventFromDb, DaoConfigEventKind,
+    DaoConfigFromDb, DaoStatsFromDb, DecodedEthCall, DecodedTonCall, FailedRawTransactionFromDb,
+    GovernanceAuditEventKind, PendingActionFromDb, PendingActionKind, ProposalActions,
+    ProposalEthAction, ProposalFromDb, ProposalState, ProposalStateChange, ProposalTonAction,
+    RawTransactionState, SearchMatchFromDb, TonPayload, UnknownTonFunction, VoteFromDb,
+};
 
 #[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
 #[serde(rename_all = "camelCase")]
 #[opg("Proposal response")]
 pub struct ProposalResponse {
+    pub dao_root: String,
     pub proposal_id: i32,
     pub proposal_address: String,
     pub proposer: String,
@@ -27,7 +34,7 @@ pub struct ProposalResponse {
     pub quorum_votes: Decimal,
     pub message_hash: String,
     pub transaction_hash: String,
-    pub actions: ProposalActions,
+    pub actions: ProposalActionsResponse,
     pub executed: bool,
     pub canceled: bool,
     pub queued: bool,
@@ -36,6 +43,10 @@ pub struct ProposalResponse {
     pub queued_at: Option<i32>,
     pub created_at: i32,
     pub state: ProposalState,
+    /// Full-text search relevance, only set for search queries
+    pub rank: Option<f32>,
+    /// Description fragment with the matched words highlighted
+    pub snippet: Option<String>,
 }
 
 impl From<ProposalFromDb> for ProposalResponse {
@@ -65,6 +76,7 @@ impl From<ProposalFromDb> for ProposalResponse {
         };
 
         Self {
+            dao_root: x.dao_root,
             proposal_id: x.id,
             proposal_address: x.address,
             proposer: x.proposer,
@@ -80,7 +92,9 @@ impl From<ProposalFromDb> for ProposalResponse {
             quorum_votes: x.quorum_votes,
             message_hash: hex::encode(x.message_hash),
             transaction_hash: hex::encode(x.transaction_hash),
-            actions: serde_json::from_value(x.actions).trust_me(),
+            actions: serde_json::from_value::<ProposalActions>(x.actions)
+                .trust_me()
+                .into(),
             executed: x.executed,
             canceled: x.canceled,
             queued: x.queued,
@@ -89,6 +103,192 @@ impl From<ProposalFromDb> for ProposalResponse {
             queued_at: x.queued_at,
             created_at: x.timestamp_block,
             state,
+            rank: None,
+            snippet: None,
+        }
+    }
+}
+
+impl From<(ProposalFromDb, Option<SearchMatchFromDb>)> for ProposalResponse {
+    fn from((proposal, search_match): (ProposalFromDb, Option<SearchMatchFromDb>)) -> Self {
+        let mut response = Self::from(proposal);
+        if let Some(search_match) = search_match {
+            response.rank = Some(search_match.rank);
+            response.snippet = Some(search_match.snippet);
+        }
+        response
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Proposal details response")]
+pub struct ProposalDetailsResponse {
+    pub proposal: ProposalResponse,
+    pub votes_count: i64,
+    /// For votes relative to the quorum, in percent
+    #[opg("quorumProgress", string)]
+    pub quorum_progress: Decimal,
+    pub state_history: Vec<ProposalStateChange>,
+}
+
+impl From<(ProposalFromDb, i64)> for ProposalDetailsResponse {
+    fn from((proposal, votes_count): (ProposalFromDb, i64)) -> Self {
+        let quorum_progress = if proposal.quorum_votes.is_zero() {
+            Decimal::ONE_HUNDRED
+        } else {
+            (proposal.for_votes * Decimal::ONE_HUNDRED / proposal.quorum_votes).round_dp(2)
+        };
+        let state_history = proposal.state_history(Utc::now().timestamp());
+
+        Self {
+            proposal: proposal.into(),
+            votes_count,
+            quorum_progress,
+            state_history,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Proposal timeline response")]
+pub struct ProposalTimelineResponse {
+    pub points: Vec<ProposalTimelinePointResponse>,
+    #[opg("quorumVotes", string)]
+    pub quorum_votes: Decimal,
+    /// Block time of the vote which made for votes reach the quorum
+    pub quorum_reached_at: Option<i32>,
+}
+
+impl From<ProposalTimeline> for ProposalTimelineResponse {
+    fn from(x: ProposalTimeline) -> Self {
+        Self {
+            points: x
+                .points
+                .into_iter()
+                .map(ProposalTimelinePointResponse::from)
+                .collect(),
+            quorum_votes: x.quorum_votes,
+            quorum_reached_at: x.quorum_reached_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Proposal timeline point response")]
+pub struct ProposalTimelinePointResponse {
+    /// Vote block time or bucket start
+    pub timestamp: i32,
+    #[opg("forVotes", string)]
+    pub for_votes: Decimal,
+    #[opg("againstVotes", string)]
+    pub against_votes: Decimal,
+    pub voters_count: i64,
+}
+
+impl From<ProposalTallyFromDb> for ProposalTimelinePointResponse {
+    fn from(x: ProposalTallyFromDb) -> Self {
+        Self {
+            timestamp: x.timestamp_block,
+            for_votes: x.for_votes,
+            against_votes: x.against_votes,
+            voters_count: x.voters_count,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Proposal actions response")]
+pub struct ProposalActionsResponse {
+    pub ton_actions: Vec<ProposalTonActionResponse>,
+    pub eth_actions: Vec<ProposalEthActionResponse>,
+}
+
+impl From<ProposalActions> for ProposalActionsResponse {
+    fn from(x: ProposalActions) -> Self {
+        Self {
+            ton_actions: x
+                .ton_actions
+                .into_iter()
+                .map(ProposalTonActionResponse::from)
+                .collect(),
+            eth_actions: x
+                .eth_actions
+                .into_iter()
+                .map(ProposalEthActionResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Proposal ton action response")]
+pub struct ProposalTonActionResponse {
+    pub value: String,
+    pub target: String,
+    pub payload: String,
+    /// Missing if the payload is empty or its function is not in the ABI registry
+    #[opg(optional)]
+    pub decoded: Option<DecodedTonCall>,
+}
+
+impl From<ProposalTonAction> for ProposalTonActionResponse {
+    fn from(x: ProposalTonAction) -> Self {
+        let decoded = match decode_ton_payload(&x.payload) {
+            Ok(TonPayload::Known(call)) => Some(call),
+            Ok(_) => None,
+            Err(e) => {
+                log::debug!("Failed to decode ton action payload: {:?}", e);
+                None
+            }
+        };
+
+        Self {
+            value: x.value,
+            target: x.target,
+            payload: x.payload,
+            decoded,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Proposal eth action response")]
+pub struct ProposalEthActionResponse {
+    pub value: String,
+    pub chain_id: u32,
+    pub target: String,
+    pub signature: String,
+    pub call_data: String,
+    /// Missing if the call data doesn't match the signature
+    #[opg(optional)]
+    pub decoded: Option<DecodedEthCall>,
+}
+
+impl From<ProposalEthAction> for ProposalEthActionResponse {
+    fn from(x: ProposalEthAction) -> Self {
+        let decoded = decode_eth_call(&x.signature, &x.call_data)
+            .map_err(|e| {
+                log::debug!(
+                    "Failed to decode eth action call data with signature {}: {:?}",
+                    x.signature,
+                    e
+                )
+            })
+            .ok();
+
+        Self {
+            value: x.value,
+            chain_id: x.chain_id,
+            target: x.target,
+            signature: x.signature,
+            call_data: x.call_data,
+            decoded,
         }
     }
 }
@@ -98,13 +298,16 @@ impl From<ProposalFromDb> for ProposalResponse {
 #[opg("Proposal table response")]
 pub struct ProposalsResponse {
     pub proposals: Vec<ProposalResponse>,
-    pub total_count: i64,
+    pub total_count: Option<i64>,
+    /// Cursor of the next page, absent on the last page
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
 #[serde(rename_all = "camelCase")]
 #[opg("Vote response")]
 pub struct VoteResponse {
+    pub dao_root: String,
     pub proposal_id: i32,
     pub voter: String,
     pub support: bool,
@@ -115,11 +318,16 @@ pub struct VoteResponse {
     pub message_hash: String,
     pub transaction_hash: String,
     pub created_at: i32,
+    /// Full-text search relevance, only set for search queries
+    pub rank: Option<f32>,
+    /// Reason fragment with the matched words highlighted
+    pub snippet: Option<String>,
 }
 
 impl From<VoteFromDb> for VoteResponse {
     fn from(x: VoteFromDb) -> Self {
         Self {
+            dao_root: x.dao_root,
             transaction_hash: hex::encode(x.transaction_hash),
             message_hash: hex::encode(x.message_hash),
             proposal_id: x.proposal_id,
@@ -129,16 +337,31 @@ impl From<VoteFromDb> for VoteResponse {
             votes: x.votes,
             locked: x.locked,
             created_at: x.timestamp_block,
+            rank: None,
+            snippet: None,
         }
     }
 }
 
+impl From<(VoteFromDb, Option<SearchMatchFromDb>)> for VoteResponse {
+    fn from((vote, search_match): (VoteFromDb, Option<SearchMatchFromDb>)) -> Self {
+        let mut response = Self::from(vote);
+        if let Some(search_match) = search_match {
+            response.rank = Some(search_match.rank);
+            response.snippet = Some(search_match.snippet);
+        }
+        response
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
 #[serde(rename_all = "camelCase")]
 #[opg("Votes response")]
 pub struct VotesResponse {
     pub votes: Vec<VoteResponse>,
-    pub total_count: i64,
+    pub total_count: Option<i64>,
+    /// Cursor of the next page, absent on the last page
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
@@ -154,7 +377,9 @@ pub struct ProposalWithVoteResponse {
 #[opg("Proposals with Votes response")]
 pub struct ProposalsWithVotesResponse {
     pub proposal_with_votes: Vec<ProposalWithVoteResponse>,
-    pub total_count: i64,
+    pub total_count: Option<i64>,
+    /// Cursor of the next page, absent on the last page
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
@@ -164,3 +389,402 @@ pub struct ProposalCountResponse {
     pub voter: String,
     pub count: i64,
 }
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Dao config response")]
+pub struct DaoConfigResponse {
+    pub dao_root: String,
+    pub kind: DaoConfigEventKind,
+    pub voting_delay: Option<i64>,
+    pub voting_period: Option<i64>,
+    #[opg("quorumVotes", string, optional)]
+    pub quorum_votes: Option<Decimal>,
+    pub time_lock: Option<i64>,
+    #[opg("threshold", string, optional)]
+    pub threshold: Option<Decimal>,
+    pub grace_period: Option<i64>,
+    pub message_hash: String,
+    pub transaction_hash: String,
+    pub timestamp_block: i32,
+    pub created_at: i64,
+}
+
+impl From<DaoConfigFromDb> for DaoConfigResponse {
+    fn from(x: DaoConfigFromDb) -> Self {
+        Self {
+            dao_root: x.dao_root,
+            kind: x.kind,
+            voting_delay: x.voting_delay,
+            voting_period: x.voting_period,
+            quorum_votes: x.quorum_votes,
+            time_lock: x.time_lock,
+            threshold: x.threshold,
+            grace_period: x.grace_period,
+            message_hash: hex::encode(x.message_hash),
+            transaction_hash: hex::encode(x.transaction_hash),
+            timestamp_block: x.timestamp_block,
+            created_at: x.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Dao config history response")]
+pub struct DaoConfigHistoryResponse {
+    pub history: Vec<DaoConfigResponse>,
+    pub total_count: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Dao stats response")]
+pub struct DaoStatsResponse {
+    pub proposals_count: i64,
+    pub proposals_by_state: Vec<ProposalStateCountResponse>,
+    pub voters_count: i64,
+    pub votes_count: i64,
+    /// Votes cast per proposal
+    #[opg("averageParticipation", string)]
+    pub average_participation: Decimal,
+    /// Mean share of for votes among proposals with votes
+    #[opg("averageForRatio", string)]
+    pub average_for_ratio: Decimal,
+    #[opg("lockedVotes", string)]
+    pub locked_votes: Decimal,
+    pub refreshed_at: Option<i64>,
+}
+
+impl From<DaoStatsFromDb> for DaoStatsResponse {
+    fn from(x: DaoStatsFromDb) -> Self {
+        let proposals_by_state = [
+            (ProposalState::Pending, x.pending_count),
+            (ProposalState::Active, x.active_count),
+            (ProposalState::Canceled, x.canceled_count),
+            (ProposalState::Failed, x.failed_count),
+            (ProposalState::Succeeded, x.succeeded_count),
+            (ProposalState::Expired, x.expired_count),
+            (ProposalState::Queued, x.queued_count),
+            (ProposalState::Executed, x.executed_count),
+        ]
+        .into_iter()
+        .map(|(state, count)| ProposalStateCountResponse { state, count })
+        .collect();
+
+        Self {
+            proposals_count: x.proposals_count,
+            proposals_by_state,
+            voters_count: x.voters_count,
+            votes_count: x.votes_count,
+            average_participation: x.average_participation,
+            average_for_ratio: x.average_for_ratio,
+            locked_votes: x.locked_votes,
+            refreshed_at: x.refreshed_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Proposal state count response")]
+pub struct ProposalStateCountResponse {
+    pub state: ProposalState,
+    pub count: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Timeseries response")]
+pub struct TimeseriesResponse {
+    pub points: Vec<TimeseriesPointResponse>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Timeseries point response")]
+pub struct TimeseriesPointResponse {
+    /// Bucket start
+    pub timestamp: i32,
+    pub votes_count: i64,
+    pub proposals_count: i64,
+    pub voters_count: i64,
+    #[opg("voteWeight", string)]
+    pub vote_weight: Decimal,
+}
+
+impl From<TimeseriesPointFromDb> for TimeseriesPointResponse {
+    fn from(x: TimeseriesPointFromDb) -> Self {
+        Self {
+            timestamp: x.bucket,
+            votes_count: x.votes_count,
+            proposals_count: x.proposals_count,
+            voters_count: x.voters_count,
+            vote_weight: x.vote_weight,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Audit event response")]
+pub struct AuditEventResponse {
+    pub dao_root: String,
+    pub kind: GovernanceAuditEventKind,
+    pub contract_address: String,
+    #[opg("data", any)]
+    pub data: serde_json::Value,
+    pub message_hash: String,
+    pub transaction_hash: String,
+    pub created_at: i32,
+}
+
+impl From<AuditEventFromDb> for AuditEventResponse {
+    fn from(x: AuditEventFromDb) -> Self {
+        Self {
+            dao_root: x.dao_root,
+            kind: x.kind,
+            contract_address: x.contract_address,
+            data: x.data,
+            message_hash: hex::encode(x.message_hash),
+            transaction_hash: hex::encode(x.transaction_hash),
+            created_at: x.timestamp_block,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Governance event response")]
+pub struct GovernanceEventResponse {
+    pub id: i64,
+    pub kind: GovernanceEventKind,
+    pub dao_root: String,
+    pub proposal_id: i32,
+    pub voter: Option<String>,
+    #[opg("data", any)]
+    pub data: serde_json::Value,
+    pub created_at: i32,
+}
+
+impl From<GovernanceEventFromDb> for GovernanceEventResponse {
+    fn from(x: GovernanceEventFromDb) -> Self {
+        Self {
+            id: x.id,
+            kind: x.kind,
+            dao_root: x.dao_root,
+            proposal_id: x.proposal_id,
+            voter: x.voter,
+            data: x.data,
+            created_at: x.timestamp_block,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Audit events response")]
+pub struct AuditEventsResponse {
+    pub events: Vec<AuditEventResponse>,
+    pub total_count: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Pending action response")]
+pub struct PendingActionResponse {
+    pub kind: PendingActionKind,
+    #[opg(optional)]
+    pub proposal_address: Option<String>,
+    #[opg(optional)]
+    pub dao_root: Option<String>,
+    #[opg(optional)]
+    pub proposal_id: Option<i32>,
+    #[opg(optional)]
+    pub voter: Option<String>,
+    #[opg(optional)]
+    pub execution_time: Option<i64>,
+    pub timestamp_block: i32,
+    pub created_at: i64,
+}
+
+impl From<PendingActionFromDb> for PendingActionResponse {
+    fn from(x: PendingActionFromDb) -> Self {
+        Self {
+            kind: x.kind,
+            proposal_address: x.proposal_address,
+            dao_root: x.dao_root,
+            proposal_id: x.proposal_id,
+            voter: x.voter,
+            execution_time: x.execution_time,
+            timestamp_block: x.timestamp_block,
+            created_at: x.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Pending actions response")]
+pub struct PendingActionsResponse {
+    pub pending_actions: Vec<PendingActionResponse>,
+    pub total_count: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Failed transaction response")]
+pub struct FailedTransactionResponse {
+    pub transaction_hash: String,
+    /// Base64 encoded transaction BOC
+    pub boc: String,
+    pub timestamp_block: i32,
+    pub timestamp_lt: i64,
+    pub state: RawTransactionState,
+    pub attempts: i32,
+    #[opg(optional)]
+    pub last_error: Option<String>,
+    #[opg(optional)]
+    pub next_retry_at: Option<i64>,
+    pub created_at: i64,
+}
+
+impl From<FailedRawTransactionFromDb> for FailedTransactionResponse {
+    fn from(x: FailedRawTransactionFromDb) -> Self {
+        Self {
+            transaction_hash: hex::encode(x.transaction_hash),
+            boc: base64::encode(x.transaction),
+            timestamp_block: x.timestamp_block,
+            timestamp_lt: x.timestamp_lt,
+            state: x.state,
+            attempts: x.attempts,
+            last_error: x.last_error,
+            next_retry_at: x.next_retry_at,
+            created_at: x.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Webhook response")]
+pub struct WebhookResponse {
+    pub id: i64,
+    pub url: String,
+    #[opg(optional)]
+    pub dao_root: Option<String>,
+    pub kinds: Vec<GovernanceEventKind>,
+    #[opg("minVoteWeight", string, optional)]
+    pub min_vote_weight: Option<Decimal>,
+    pub created_at: i64,
+}
+
+impl From<WebhookFromDb> for WebhookResponse {
+    fn from(x: WebhookFromDb) -> Self {
+        Self {
+            id: x.id,
+            url: x.url,
+            dao_root: x.dao_root,
+            kinds: x.kinds,
+            min_vote_weight: x.min_vote_weight,
+            created_at: x.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Webhook delivery response")]
+pub struct WebhookDeliveryResponse {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event_id: i64,
+    #[opg("payload", any)]
+    pub payload: serde_json::Value,
+    pub state: WebhookDeliveryState,
+    pub attempts: i32,
+    #[opg(optional)]
+    pub last_error: Option<String>,
+    #[opg(optional)]
+    pub response_status: Option<i32>,
+    pub next_attempt_at: i64,
+    pub created_at: i64,
+    #[opg(optional)]
+    pub updated_at: Option<i64>,
+}
+
+impl From<WebhookDeliveryFromDb> for WebhookDeliveryResponse {
+    fn from(x: WebhookDeliveryFromDb) -> Self {
+        Self {
+            id: x.id,
+            webhook_id: x.webhook_id,
+            event_id: x.event_id,
+            payload: x.payload,
+            state: x.state,
+            attempts: x.attempts,
+            last_error: x.last_error,
+            response_status: x.response_status,
+            next_attempt_at: x.next_attempt_at,
+            created_at: x.created_at,
+            updated_at: x.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Webhook deliveries response")]
+pub struct WebhookDeliveriesResponse {
+    pub deliveries: Vec<WebhookDeliveryResponse>,
+    pub total_count: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Failed transactions response")]
+pub struct FailedTransactionsResponse {
+    pub transactions: Vec<FailedTransactionResponse>,
+    pub total_count: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Unknown ton function response")]
+pub struct UnknownTonFunctionResponse {
+    /// Hex encoded function ID, e.g. `0x1234abcd`
+    pub function_id: String,
+    pub proposal_addresses: Vec<String>,
+}
+
+impl From<UnknownTonFunction> for UnknownTonFunctionResponse {
+    fn from(x: UnknownTonFunction) -> Self {
+        Self {
+            function_id: format_function_id(x.function_id),
+            proposal_addresses: x.proposal_addresses,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("GraphQL response")]
+pub struct GraphqlResponse {
+    #[opg("data", any)]
+    pub data: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[opg("errors", any, optional)]
+    pub errors: Vec<serde_json::Value>,
+}
+
+impl From<async_graphql::Response> for GraphqlResponse {
+    fn from(x: async_graphql::Response) -> Self {
+        Self {
+            data: x.data.into_json().unwrap_or_default(),
+            errors: x
+                .errors
+                .into_iter()
+                .map(|error| serde_json::to_value(error).unwrap_or_default())
+                .collect(),
+        }
+    }
+}
@@ -9,6 +9,20 @@ pub struct BadRequestError(pub anyhow::Error);
 
 impl warp::reject::Reject for BadRequestError {}
 
+#[derive(Debug)]
+pub struct UnauthorizedError;
+
+impl warp::reject::Reject for UnauthorizedError {}
+
+pub async fn recover_unauthorized(
+    rejection: warp::Rejection,
+) -> Result<http::Response<hyper::Body>, warp::Rejection> {
+    match rejection.find::<UnauthorizedError>() {
+        Some(_) => Ok(unauthorized()),
+        None => Err(rejection),
+    }
+}
+
 #[allow(unused)]
 pub async fn parse_body<T>(body: serde_json::Value) -> Result<T, warp::Rejection>
 where
@@ -31,6 +45,17 @@ pub fn not_found() -> http::Response<hyper::Body> {
     response
 }
 
+pub fn unauthorized() -> http::Response<hyper::Body> {
+    let body = r#"{"description":"Unauthorized"}"#;
+    let mut response = Response::new(body.into());
+    *response.status_mut() = StatusCode::UNAUTHORIZED;
+    response.headers_mut().insert(
+        http::header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
 pub fn bad_request<T>(err: T) -> http::Response<hyper::Body>
 where
     T: AsRef<str>,
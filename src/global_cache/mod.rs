@@ -1,5 +0,0 @@
-pub use self::proposal::*;
-pub use self::vote::*;
-
-mod proposal;
-mod vote;
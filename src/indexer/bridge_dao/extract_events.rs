@@ -1,33 +1,192 @@
-use indexer_lib::{split, AnyExtractableOutput, ParsedOutput};
+use indexer_lib::{split, AnyExtractableOutput, ParsedOutput, TransactionExt};
 use nekoton_abi::{UnpackAbiPlain, UnpackFirst};
-use transaction_consumer::TransactionConsumer;
+use nekoton_utils::TrustMe;
+use sqlx::Postgres;
 
 use super::parse_dao_events::*;
 use super::parse_proposal_events::*;
 use super::parse_userdata_events::*;
 use crate::models::*;
 use crate::sqlx_client::*;
+use crate::ton_contracts::*;
 
 pub async fn extract_dao_root_parsed_events(
     sqlx_client: &SqlxClient,
-    transaction_consumer: &TransactionConsumer,
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    state_provider: &dyn ContractStateProvider,
+    events_parsing: &EventsParsing,
     events: ParsedOutput<AnyExtractableOutput>,
 ) -> Result<(), anyhow::Error> {
     let transaction = &events.transaction;
+    let transaction_hash = transaction.tx_hash().trust_me();
 
     let (_, events) = split(events.output);
     for event in events {
-        let message_hash = event.message_hash.to_vec();
-        if event.function_name.as_str() == "ProposalCreated" {
-            let data: ProposalCreated = event.input.unpack()?;
-            parse_proposal_created_event(
-                data,
-                message_hash,
-                transaction,
-                sqlx_client,
-                transaction_consumer,
+        // only events of this contract are recorded in the applied events ledger,
+        // so an event decoded by several contract ABIs is not taken by the wrong one
+        if !events_parsing
+            .events_check
+            .contains(&(event.function_name.clone(), event.event_id))
+        {
+            continue;
+        }
+        if !sqlx_client
+            .mark_event_applied(
+                tx,
+                transaction_hash.as_slice(),
+                event.message_hash.as_slice(),
             )
-            .await?;
+            .await?
+        {
+            log::debug!("Skip already applied event {}", event.function_name);
+            continue;
+        }
+
+        let message_hash = event.message_hash.to_vec();
+        match event.function_name.as_str() {
+            "ProposalCreated" => {
+                let data: ProposalCreated = event.input.unpack()?;
+                parse_proposal_created_event(
+                    data,
+                    message_hash,
+                    transaction,
+                    sqlx_client,
+                    tx,
+                    state_provider,
+                )
+                .await?;
+            }
+            "ProposalConfigurationUpdated" => {
+                let data: ProposalConfigurationUpdated = event.input.unpack()?;
+                parse_dao_config_updated_event(
+                    DaoConfigEventKind::ProposalConfigurationUpdated,
+                    data.new_config.into(),
+                    message_hash,
+                    transaction,
+                    sqlx_client,
+                    tx,
+                    state_provider,
+                )
+                .await?;
+            }
+            "ProposalVotingDelayUpdated" => {
+                let data: ProposalVotingDelayUpdated = event.input.unpack()?;
+                parse_dao_config_updated_event(
+                    DaoConfigEventKind::ProposalVotingDelayUpdated,
+                    data.into(),
+                    message_hash,
+                    transaction,
+                    sqlx_client,
+                    tx,
+                    state_provider,
+                )
+                .await?;
+            }
+            "ProposalVotingPeriodUpdated" => {
+                let data: ProposalVotingPeriodUpdated = event.input.unpack()?;
+                parse_dao_config_updated_event(
+                    DaoConfigEventKind::ProposalVotingPeriodUpdated,
+                    data.into(),
+                    message_hash,
+                    transaction,
+                    sqlx_client,
+                    tx,
+                    state_provider,
+                )
+                .await?;
+            }
+            "ProposalQuorumVotesUpdated" => {
+                let data: ProposalQuorumVotesUpdated = event.input.unpack()?;
+                parse_dao_config_updated_event(
+                    DaoConfigEventKind::ProposalQuorumVotesUpdated,
+                    data.into(),
+                    message_hash,
+                    transaction,
+                    sqlx_client,
+                    tx,
+                    state_provider,
+                )
+                .await?;
+            }
+            "ProposalTimeLockUpdated" => {
+                let data: ProposalTimeLockUpdated = event.input.unpack()?;
+                parse_dao_config_updated_event(
+                    DaoConfigEventKind::ProposalTimeLockUpdated,
+                    data.into(),
+                    message_hash,
+                    transaction,
+                    sqlx_client,
+                    tx,
+                    state_provider,
+                )
+                .await?;
+            }
+            "ProposalThresholdUpdated" => {
+                let data: ProposalThresholdUpdated = event.input.unpack()?;
+                parse_dao_config_updated_event(
+                    DaoConfigEventKind::ProposalThresholdUpdated,
+                    data.into(),
+                    message_hash,
+                    transaction,
+                    sqlx_client,
+                    tx,
+                    state_provider,
+                )
+                .await?;
+            }
+            "ProposalGracePeriodUpdated" => {
+                let data: ProposalGracePeriodUpdated = event.input.unpack()?;
+                parse_dao_config_updated_event(
+                    DaoConfigEventKind::ProposalGracePeriodUpdated,
+                    data.into(),
+                    message_hash,
+                    transaction,
+                    sqlx_client,
+                    tx,
+                    state_provider,
+                )
+                .await?;
+            }
+            "RequestedAdminTransfer" => {
+                let data: RequestedAdminTransfer = event.input.unpack()?;
+                parse_dao_audit_event(data.into(), message_hash, transaction, sqlx_client, tx)
+                    .await?;
+            }
+            "AdminTransferAccepted" => {
+                let data: AdminTransferAccepted = event.input.unpack()?;
+                parse_dao_audit_event(data.into(), message_hash, transaction, sqlx_client, tx)
+                    .await?;
+            }
+            "StakingRootUpdated" => {
+                let data: StakingRootUpdated = event.input.unpack()?;
+                parse_dao_audit_event(data.into(), message_hash, transaction, sqlx_client, tx)
+                    .await?;
+            }
+            "ProposalCodeUpgraded" => {
+                let data: ProposalCodeUpgraded = event.input.unpack()?;
+                parse_dao_audit_event(
+                    AuditEventData::proposal_code_upgraded(
+                        GovernanceAuditEventKind::ProposalCodeUpgraded,
+                        data,
+                    ),
+                    message_hash,
+                    transaction,
+                    sqlx_client,
+                    tx,
+                )
+                .await?;
+            }
+            "RootCodeUpgraded" => {
+                parse_dao_audit_event(
+                    AuditEventData::root_code_upgraded(),
+                    message_hash,
+                    transaction,
+                    sqlx_client,
+                    tx,
+                )
+                .await?;
+            }
+            _ => {}
         }
     }
     Ok(())
@@ -35,23 +194,80 @@ pub async fn extract_dao_root_parsed_events(
 
 pub async fn extract_proposal_parsed_events(
     sqlx_client: &SqlxClient,
-    node: &TransactionConsumer,
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    state_provider: &dyn ContractStateProvider,
+    events_parsing: &EventsParsing,
     events: ParsedOutput<AnyExtractableOutput>,
 ) -> Result<(), anyhow::Error> {
     let transaction = &events.transaction;
+    let transaction_hash = transaction.tx_hash().trust_me();
 
     let (_, events) = split(events.output);
     for event in events {
+        if !events_parsing
+            .events_check
+            .contains(&(event.function_name.clone(), event.event_id))
+        {
+            continue;
+        }
+        if !sqlx_client
+            .mark_event_applied(
+                tx,
+                transaction_hash.as_slice(),
+                event.message_hash.as_slice(),
+            )
+            .await?
+        {
+            log::debug!("Skip already applied event {}", event.function_name);
+            continue;
+        }
+
         match event.function_name.as_str() {
             "Executed" => {
-                parse_proposal_executed_event(transaction, sqlx_client, node).await?;
+                parse_proposal_executed_event(transaction, sqlx_client, tx, state_provider).await?;
             }
             "Canceled" => {
-                parse_proposal_canceled_event(transaction, sqlx_client, node).await?;
+                parse_proposal_canceled_event(transaction, sqlx_client, tx, state_provider).await?;
             }
             "Queued" => {
                 let execution_time: u32 = event.input.unpack_first()?;
-                parse_proposal_queued_event(execution_time, transaction, sqlx_client, node).await?;
+                parse_proposal_queued_event(
+                    execution_time,
+                    transaction,
+                    sqlx_client,
+                    tx,
+                    state_provider,
+                )
+                .await?;
+            }
+            "CodeUpgradeRequested" => {
+                let data: CodeUpgradeRequested = event.input.unpack()?;
+                let message_hash = event.message_hash.to_vec();
+                parse_proposal_audit_event(
+                    data.into(),
+                    message_hash,
+                    transaction,
+                    sqlx_client,
+                    tx,
+                    state_provider,
+                )
+                .await?;
+            }
+            "ProposalCodeUpgraded" => {
+                let data: ProposalCodeUpgraded = event.input.unpack()?;
+                let message_hash = event.message_hash.to_vec();
+                parse_proposal_audit_event(
+                    AuditEventData::proposal_code_upgraded(
+                        GovernanceAuditEventKind::ProposalUpgraded,
+                        data,
+                    ),
+                    message_hash,
+                    transaction,
+                    sqlx_client,
+                    tx,
+                    state_provider,
+                )
+                .await?;
             }
             _ => {}
         }
@@ -61,13 +277,34 @@ pub async fn extract_proposal_parsed_events(
 
 pub async fn extract_userdata_parsed_events(
     sqlx_client: &SqlxClient,
-    transaction_consumer: &TransactionConsumer,
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    state_provider: &dyn ContractStateProvider,
+    events_parsing: &EventsParsing,
     events: ParsedOutput<AnyExtractableOutput>,
 ) -> Result<(), anyhow::Error> {
     let transaction = &events.transaction;
+    let transaction_hash = transaction.tx_hash().trust_me();
 
     let (_, events) = split(events.output);
     for event in events {
+        if !events_parsing
+            .events_check
+            .contains(&(event.function_name.clone(), event.event_id))
+        {
+            continue;
+        }
+        if !sqlx_client
+            .mark_event_applied(
+                tx,
+                transaction_hash.as_slice(),
+                event.message_hash.as_slice(),
+            )
+            .await?
+        {
+            log::debug!("Skip already applied event {}", event.function_name);
+            continue;
+        }
+
         match event.function_name.as_str() {
             "VoteCast" => {
                 let vote: VoteCast = event.input.unpack()?;
@@ -77,7 +314,8 @@ pub async fn extract_userdata_parsed_events(
                     message_hash,
                     transaction,
                     sqlx_client,
-                    transaction_consumer,
+                    tx,
+                    state_provider,
                 )
                 .await?;
             }
@@ -87,7 +325,8 @@ pub async fn extract_userdata_parsed_events(
                     proposal_id,
                     transaction,
                     sqlx_client,
-                    transaction_consumer,
+                    tx,
+                    state_provider,
                 )
                 .await?
             }
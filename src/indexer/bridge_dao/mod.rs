@@ -1,17 +1,22 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 
+use chrono::Utc;
 use futures::channel::mpsc::{Receiver, Sender};
 use futures::{SinkExt, StreamExt};
 use indexer_lib::{split, AnyExtractableOutput, ExtractInput, ParsedOutput, TransactionExt};
 use nekoton_utils::{repack_address, TrustMe};
+use once_cell::sync::OnceCell;
 use tokio::time;
 use ton_block::{Deserializable, MsgAddressInt, Transaction};
 use ton_types::UInt256;
-use transaction_consumer::{TransactionConsumer};
 
+use crate::indexer::ParsedTransactions;
 use crate::models::*;
 use crate::sqlx_client::*;
+use crate::ton_contracts::*;
+use crate::utils::*;
 
 use self::extract_events::*;
 
@@ -20,162 +25,356 @@ mod parse_dao_events;
 mod parse_proposal_events;
 mod parse_userdata_events;
 
-lazy_static::lazy_static! {
-    static ref DAO_ROOT_ADDRESS: MsgAddressInt =
-        repack_address(&std::env::var("DAO_ROOT").trust_me()).trust_me();
+static DAO_ROOT_ADDRESSES: OnceCell<HashSet<MsgAddressInt>> = OnceCell::new();
+
+pub fn init_dao_roots(dao_roots: &[String]) -> Result<Vec<MsgAddressInt>, anyhow::Error> {
+    let dao_roots = dao_roots
+        .iter()
+        .map(|x| repack_address(x))
+        .collect::<Result<Vec<_>, _>>()?;
+    if dao_roots.is_empty() {
+        anyhow::bail!("No DAO roots configured");
+    }
+
+    DAO_ROOT_ADDRESSES
+        .set(dao_roots.iter().cloned().collect())
+        .map_err(|_| anyhow::Error::msg("DAO roots are already initialized"))?;
+
+    Ok(dao_roots)
+}
+
+fn is_dao_root(address: &MsgAddressInt) -> bool {
+    DAO_ROOT_ADDRESSES
+        .get()
+        .map(|dao_roots| dao_roots.contains(address))
+        .unwrap_or_default()
 }
 
 pub async fn bridge_dao_indexer(
     sqlx_client: SqlxClient,
-    transaction_consumer: Arc<TransactionConsumer>,
-    mut rx_raw_transactions: Receiver<
-        Vec<(
-            ParsedOutput<AnyExtractableOutput>,
-            transaction_buffer::models::RawTransaction,
-        )>,
-    >,
+    state_provider: Arc<dyn ContractStateProvider>,
+    retry_policy: RetryPolicy,
+    source: &'static str,
+    mut rx_raw_transactions: Receiver<ParsedTransactions>,
     mut tx_commit: Sender<()>,
 ) {
+    const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
     log::info!("Start Bridge-Dao indexer...");
 
+    verify_checkpoint(&sqlx_client, source).await;
+
     let all_events = AllEvents::new();
     while let Some(message) = rx_raw_transactions.next().await {
-        for (_, raw_transaction) in message {
-            let transaction = raw_transaction.data.clone();
-            let transaction_hash = transaction.tx_hash().trust_me();
+        let mut checkpoint: Option<(i64, i32)> = None;
 
-            let raw_transaction_from_db: RawTransactionFromDb =
-                raw_transaction.data.clone().try_into().trust_me();
+        for (_, raw_transaction) in message {
+            let transaction = raw_transaction.data;
 
-            if let Err(err) = sqlx_client
-                .create_raw_transaction(raw_transaction_from_db)
-                .await
+            // the batch is committed only when each transaction is either applied
+            // or stored in a retryable state
+            while let Err(err) = process_transaction(
+                &sqlx_client,
+                state_provider.as_ref(),
+                &all_events,
+                retry_policy,
+                transaction.clone(),
+            )
+            .await
             {
                 log::error!(
-                    "Failed to create a raw transaction in db: {}; Transaction hash: {}",
+                    "Failed to store transaction: {}; Transaction hash: {}",
                     err,
-                    transaction_hash.to_hex_string()
+                    raw_transaction.hash.to_hex_string()
                 );
+                time::sleep(RETRY_INTERVAL).await;
             }
 
-            match parse_new_event(
-                transaction,
-                transaction_hash,
-                &sqlx_client,
-                &all_events,
-                &transaction_consumer,
-            )
-            .await
+            // lt of different accounts are not comparable, only the block time
+            // orders the whole batch, lt is kept for diagnostics
+            let (lt, time) = checkpoint.unwrap_or_default();
+            checkpoint = Some((
+                lt.max(transaction.lt as i64),
+                time.max(transaction.now as i32),
+            ));
+        }
+
+        if let Some((timestamp_lt, timestamp_block)) = checkpoint {
+            while let Err(err) = sqlx_client
+                .update_indexer_checkpoint(source, timestamp_lt, timestamp_block)
+                .await
             {
-                Ok(_) => {
-                    if let Err(err) = sqlx_client
-                        .update_raw_transactions(
-                            transaction_hash.as_slice(),
-                            RawTransactionState::Success,
-                        )
-                        .await
-                    {
-                        log::error!(
-                            "Failed to set transaction state to 'Success': {}; Transaction hash: {}",
-                            err,
-                            transaction_hash.to_hex_string()
-                        );
-                    }
-                }
-                Err(err) => {
-                    log::error!(
-                        "Failed to parse event: {}; Transaction hash: {}",
-                        err,
-                        transaction_hash.to_hex_string()
-                    );
-                    if let Err(err) = sqlx_client
-                        .update_raw_transactions(
-                            transaction_hash.as_slice(),
-                            RawTransactionState::Fail,
-                        )
-                        .await
-                    {
-                        log::error!(
-                            "Failed to set transaction state to 'Fail': {}; Transaction hash: {}",
-                            err,
-                            transaction_hash.to_hex_string()
-                        );
-                    }
-                }
+                log::error!("Failed to update indexer checkpoint: {}", err);
+                time::sleep(RETRY_INTERVAL).await;
             }
         }
+
         tx_commit.send(()).await.expect("dead commit sender");
     }
 
     panic!("rip kafka consumer");
 }
 
+async fn process_transaction(
+    sqlx_client: &SqlxClient,
+    state_provider: &dyn ContractStateProvider,
+    all_events: &AllEvents,
+    retry_policy: RetryPolicy,
+    transaction: Transaction,
+) -> Result<(), anyhow::Error> {
+    let transaction_hash = transaction.tx_hash().trust_me();
+
+    let raw_transaction_from_db: RawTransactionFromDb = transaction.clone().try_into().trust_me();
+    sqlx_client
+        .create_raw_transaction(raw_transaction_from_db)
+        .await?;
+
+    if let Err(err) = parse_new_event(
+        transaction,
+        transaction_hash,
+        sqlx_client,
+        all_events,
+        state_provider,
+    )
+    .await
+    {
+        record_failure(sqlx_client, transaction_hash, err, retry_policy).await?;
+    }
+
+    Ok(())
+}
+
+/// Transactions stored before the checkpoint must have been processed,
+/// otherwise they are scheduled for retry
+async fn verify_checkpoint(sqlx_client: &SqlxClient, source: &str) {
+    let checkpoint = match sqlx_client.get_indexer_checkpoint(source).await {
+        Ok(Some(checkpoint)) => checkpoint,
+        Ok(None) => {
+            log::info!("No checkpoint for '{}' source", source);
+            return;
+        }
+        Err(err) => {
+            log::error!("Failed to get indexer checkpoint: {}", err);
+            return;
+        }
+    };
+
+    log::info!(
+        "Resume '{}' source after block time {} (lt {})",
+        source,
+        checkpoint.timestamp_block,
+        checkpoint.timestamp_lt
+    );
+
+    match sqlx_client
+        .requeue_unprocessed_raw_transactions(checkpoint.timestamp_block)
+        .await
+    {
+        Ok(0) => {}
+        Ok(count) => log::warn!(
+            "Found {} unprocessed transactions before the checkpoint, scheduled for retry",
+            count
+        ),
+        Err(err) => log::error!("Failed to check unprocessed transactions: {}", err),
+    }
+}
+
 pub async fn fail_transaction_monitor(
     sqlx_client: SqlxClient,
-    transaction_consumer: Arc<TransactionConsumer>,
+    state_provider: Arc<dyn ContractStateProvider>,
+    retry_policy: RetryPolicy,
 ) {
+    const BATCH_SIZE: i32 = 100;
+
     log::info!("Start Fail Transaction Monitor...");
 
-    let mut interval = time::interval(Duration::from_secs(300));
+    let mut interval = time::interval(Duration::from_secs(60));
 
     let all_events = AllEvents::new();
-    let prep_events = all_events.get_all_events();
 
     loop {
         interval.tick().await;
 
         let raw_transactions = match sqlx_client
-            .get_raw_transactions_by_state(RawTransactionState::Fail)
+            .get_raw_transactions_to_retry(Utc::now().timestamp_millis(), BATCH_SIZE)
             .await
         {
             Ok(raw_transactions) => raw_transactions,
             Err(err) => {
-                log::error!("Failed to get raw transactions by state from db: {}", err);
+                log::error!("Failed to get raw transactions to retry from db: {}", err);
                 continue;
             }
         };
 
-        let transactions = raw_transactions
-            .into_iter()
-            .map(|x| Transaction::construct_from_bytes(&x.transaction).trust_me())
-            .collect::<Vec<Transaction>>();
-
-        for transaction in transactions {
+        for raw_transaction in raw_transactions {
+            let transaction =
+                Transaction::construct_from_bytes(&raw_transaction.transaction).trust_me();
             let transaction_hash = transaction.tx_hash().trust_me();
 
-            if extract_events(&transaction, transaction_hash, &prep_events).is_some() {
-                if let Err(err) = parse_new_event(
-                    transaction,
-                    transaction_hash,
-                    &sqlx_client,
-                    &all_events,
-                    &transaction_consumer,
-                )
-                .await
-                {
-                    log::error!(
-                        "Failed to parse event: {}; Transaction hash: {}",
-                        err,
-                        transaction_hash.to_hex_string()
-                    );
-                }
+            // state is set to 'Success' together with the parsed events
+            if let Err(err) = parse_new_event(
+                transaction,
+                transaction_hash,
+                &sqlx_client,
+                &all_events,
+                state_provider.as_ref(),
+            )
+            .await
+            {
+                let _ = record_failure(&sqlx_client, transaction_hash, err, retry_policy).await;
             }
+        }
+    }
+}
 
-            if let Err(err) = sqlx_client
-                .update_raw_transactions(transaction_hash.as_slice(), RawTransactionState::Success)
-                .await
+/// Proposal states change with time, so the stats are refreshed periodically
+/// rather than after indexed transactions
+pub async fn dao_stats_refresher(sqlx_client: SqlxClient) {
+    log::info!("Start DAO stats refresher...");
+
+    let mut interval = time::interval(Duration::from_secs(60));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(err) = sqlx_client.refresh_dao_stats().await {
+            log::error!("Failed to refresh DAO stats: {}", err);
+        }
+    }
+}
+
+/// Records proposal states which are entered by the clock and announces them
+/// like the indexed events
+pub async fn proposal_state_scheduler(sqlx_client: SqlxClient) {
+    log::info!("Start proposal state scheduler...");
+
+    let mut interval = time::interval(Duration::from_secs(10));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(err) =
+            record_proposal_state_transitions(&sqlx_client, Utc::now().timestamp()).await
+        {
+            log::error!("Failed to record proposal state transitions: {}", err);
+        }
+    }
+}
+
+/// Returns the number of newly recorded transitions
+pub async fn record_proposal_state_transitions(
+    sqlx_client: &SqlxClient,
+    now: i64,
+) -> Result<usize, anyhow::Error> {
+    // votes of the last blocks must be indexed before the outcome is decided
+    const INDEXING_LAG_SEC: i64 = 60;
+    // the rest is picked up on the next tick
+    const BATCH_SIZE: i32 = 100;
+
+    let now = now - INDEXING_LAG_SEC;
+
+    let mut recorded = 0;
+    for proposal in sqlx_client
+        .get_proposals_awaiting_transitions(now, BATCH_SIZE)
+        .await?
+    {
+        let transitions = proposal.time_driven_transitions(now);
+        if transitions.is_empty() {
+            continue;
+        }
+
+        let mut tx = sqlx_client.begin().await?;
+        for change in transitions {
+            if sqlx_client
+                .create_proposal_state_transition(&mut tx, &proposal.dao_root, proposal.id, change)
+                .await?
             {
-                log::error!(
-                    "Failed to set transaction state to 'Success': {}; Transaction hash: {}",
-                    err,
-                    transaction_hash.to_hex_string()
+                let event = CreateGovernanceEvent::state_changed(
+                    proposal.dao_root.clone(),
+                    proposal.id,
+                    change,
                 );
+                sqlx_client.create_governance_event(&mut tx, event).await?;
+                recorded += 1;
+            }
+        }
+        sqlx_client.publish_governance_events(&mut tx).await?;
+        tx.commit().await?;
+    }
+
+    Ok(recorded)
+}
+
+/// Without filters the indexed data is rebuilt from scratch. A ranged reindex removes
+/// only what the transactions of the range derived and replays them
+pub async fn reindex(
+    sqlx_client: SqlxClient,
+    state_provider: Arc<dyn ContractStateProvider>,
+    retry_policy: RetryPolicy,
+    filters: RawTransactionFilters,
+) -> Result<(), anyhow::Error> {
+    const BATCH_SIZE: i32 = 1000;
+
+    log::info!("Start reindex {:?}...", filters);
+
+    // replayed events must not be sent to webhooks again
+    let sqlx_client = sqlx_client.replaying();
+    if filters.is_unbounded() {
+        sqlx_client.clear_indexed_data().await?;
+    } else {
+        sqlx_client.clear_indexed_range(filters.clone()).await?;
+    }
+
+    let all_events = AllEvents::new();
+
+    let mut offset = 0;
+    let mut failed = 0;
+    loop {
+        let raw_transactions = sqlx_client
+            .search_raw_transactions(filters.clone().paginated(BATCH_SIZE, offset))
+            .await?;
+        if raw_transactions.is_empty() {
+            break;
+        }
+        offset += raw_transactions.len() as i32;
+
+        for raw_transaction in raw_transactions {
+            let transaction =
+                Transaction::construct_from_bytes(&raw_transaction.transaction).trust_me();
+            let transaction_hash = transaction.tx_hash().trust_me();
+
+            if let Err(err) = parse_new_event(
+                transaction,
+                transaction_hash,
+                &sqlx_client,
+                &all_events,
+                state_provider.as_ref(),
+            )
+            .await
+            {
+                failed += 1;
+                let _ = record_failure(&sqlx_client, transaction_hash, err, retry_policy).await;
             }
         }
+
+        log::info!("Reindexed {} transactions", offset);
     }
+
+    // transitions of the rebuilt proposals are announced again like the replayed events
+    while record_proposal_state_transitions(&sqlx_client, Utc::now().timestamp()).await? > 0 {}
+
+    sqlx_client.refresh_dao_stats().await?;
+
+    log::info!(
+        "Reindex finished: {} transactions, {} failed",
+        offset,
+        failed
+    );
+
+    Ok(())
 }
 
-fn extract_events(
+pub(crate) fn extract_events(
     transaction: &Transaction,
     transaction_hash: UInt256,
     events_parsing: &EventsParsing,
@@ -211,23 +410,100 @@ fn extract_events(
     })
 }
 
-async fn parse_new_event(
+/// Applies all events of the transaction and marks it as `Success` atomically,
+/// so a failed transaction leaves no partial writes behind
+pub async fn parse_new_event(
     transaction: Transaction,
     transaction_hash: UInt256,
     sqlx_client: &SqlxClient,
     all_events: &AllEvents,
-    transaction_consumer: &TransactionConsumer,
+    state_provider: &dyn ContractStateProvider,
 ) -> Result<(), anyhow::Error> {
-    if let Some(events) = extract_events(&transaction, transaction_hash, &all_events.dao_root) {
-        extract_dao_root_parsed_events(sqlx_client, transaction_consumer, events).await?;
-    }
+    let mut tx = sqlx_client.begin().await?;
+
+    // DaoRoot and Proposal emit events with the same signature,
+    // so only the extractors of the emitting contract are run
+    if is_dao_root(&transaction.contract_address()?) {
+        if let Some(events) = extract_events(&transaction, transaction_hash, &all_events.dao_root) {
+            extract_dao_root_parsed_events(
+                sqlx_client,
+                &mut tx,
+                state_provider,
+                &all_events.dao_root,
+                events,
+            )
+            .await?;
+        }
+    } else {
+        if let Some(events) = extract_events(&transaction, transaction_hash, &all_events.proposal) {
+            extract_proposal_parsed_events(
+                sqlx_client,
+                &mut tx,
+                state_provider,
+                &all_events.proposal,
+                events,
+            )
+            .await?;
+        }
 
-    if let Some(events) = extract_events(&transaction, transaction_hash, &all_events.proposal) {
-        extract_proposal_parsed_events(sqlx_client, transaction_consumer, events).await?;
+        if let Some(events) = extract_events(&transaction, transaction_hash, &all_events.user_data)
+        {
+            extract_userdata_parsed_events(
+                sqlx_client,
+                &mut tx,
+                state_provider,
+                &all_events.user_data,
+                events,
+            )
+            .await?;
+        }
     }
 
-    if let Some(events) = extract_events(&transaction, transaction_hash, &all_events.user_data) {
-        extract_userdata_parsed_events(sqlx_client, transaction_consumer, events).await?;
+    sqlx_client
+        .update_raw_transactions(
+            &mut tx,
+            transaction_hash.as_slice(),
+            RawTransactionState::Success,
+        )
+        .await?;
+
+    sqlx_client.publish_governance_events(&mut tx).await?;
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Logs the parsing error, stores it and schedules a retry
+async fn record_failure(
+    sqlx_client: &SqlxClient,
+    transaction_hash: UInt256,
+    err: anyhow::Error,
+    retry_policy: RetryPolicy,
+) -> Result<(), anyhow::Error> {
+    log::error!(
+        "Failed to parse event: {}; Transaction hash: {}",
+        err,
+        transaction_hash.to_hex_string()
+    );
+
+    match sqlx_client
+        .record_raw_transaction_failure(transaction_hash.as_slice(), err.to_string(), retry_policy)
+        .await
+    {
+        Ok(Some(RawTransactionState::DeadLetter)) => log::warn!(
+            "Transaction moved to dead letter after {} attempts; Transaction hash: {}",
+            retry_policy.max_attempts,
+            transaction_hash.to_hex_string()
+        ),
+        Ok(_) => {}
+        Err(err) => {
+            log::error!(
+                "Failed to set transaction state to 'Fail': {}; Transaction hash: {}",
+                err,
+                transaction_hash.to_hex_string()
+            );
+            return Err(err);
+        }
     }
 
     Ok(())
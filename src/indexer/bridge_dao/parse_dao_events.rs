@@ -1,24 +1,21 @@
-use anyhow::Context;
 use indexer_lib::TransactionExt;
 use itertools::Itertools;
-use nekoton_abi::*;
 use nekoton_utils::TrustMe;
 use sqlx::types::Decimal;
-use ton_block::{MsgAddressInt, Transaction};
-use transaction_consumer::TransactionConsumer;
+use sqlx::Postgres;
+use ton_block::Transaction;
 
-use crate::global_cache::*;
 use crate::models::*;
 use crate::sqlx_client::*;
 use crate::ton_contracts::*;
-use crate::utils::*;
 
 pub async fn parse_proposal_created_event(
     data: ProposalCreated,
     message_hash: Vec<u8>,
     transaction: &Transaction,
     sqlx_client: &SqlxClient,
-    transaction_consumer: &TransactionConsumer,
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    state_provider: &dyn ContractStateProvider,
 ) -> Result<(), anyhow::Error> {
     log::debug!("Found new proposal : {:?}", data);
     log::debug!(
@@ -26,7 +23,8 @@ pub async fn parse_proposal_created_event(
         transaction.contract_address()?.to_string()
     );
 
-    if transaction.contract_address()? != *super::DAO_ROOT_ADDRESS {
+    let dao_root_address = transaction.contract_address()?;
+    if !super::is_dao_root(&dao_root_address) {
         // skip event
         return Ok(());
     }
@@ -35,46 +33,22 @@ pub async fn parse_proposal_created_event(
     let transaction_hash = transaction.tx_hash().trust_me().as_slice().to_vec();
 
     // get expected proposal address
-    let dao_root_address = transaction.contract_address()?;
-    let function_output = transaction_consumer
-        .run_local(
-            &dao_root_address,
-            expected_proposal_address(),
-            &[
-                answer_id(),
-                data.proposal_id.token_value().named("proposalId"),
-            ],
-        )
-        .await?
-        .context("none function output")?;
-    let proposal_address: MsgAddressInt =
-        function_output.tokens.unwrap_or_default().unpack_first()?;
+    let proposal_address = state_provider
+        .expected_proposal_address(&dao_root_address, data.proposal_id)
+        .await?;
 
     // get proposal overview
-    let function_output = poll_run_local(
-        transaction_consumer,
-        &proposal_address,
-        get_overview(),
-        &[answer_id()],
-        60,
-    )
-    .await?;
-    let proposal_overview: ProposalOverview =
-        function_output.tokens.unwrap_or_default().unpack()?;
+    let proposal_overview = state_provider
+        .get_proposal_overview(&proposal_address)
+        .await?;
 
     // get proposal config
-    let function_output = poll_run_local(
-        transaction_consumer,
-        &proposal_address,
-        get_config(),
-        &[answer_id()],
-        60,
-    )
-    .await?;
-    let proposal_config: ProposalConfig =
-        function_output.tokens.unwrap_or_default().unpack_first()?;
+    let proposal_config = state_provider
+        .get_proposal_config(&proposal_address)
+        .await?;
 
     let proposal = CreateProposal {
+        dao_root: dao_root_address.to_string(),
         id: data.proposal_id as i32,
         address: proposal_address.to_string(),
         proposer: proposal_overview.proposer.to_string(),
@@ -99,32 +73,84 @@ pub async fn parse_proposal_created_event(
         },
     };
 
-    sqlx_client.create_proposal(proposal).await?;
-
-    let proposal_actions = remove_proposal_actions_from_cache(&proposal_address);
-    for proposal_action in proposal_actions {
-        match proposal_action {
-            ProposalActionType::Executed(timestamp_block) => {
-                sqlx_client
-                    .update_proposal_executed(proposal_address.to_string(), timestamp_block)
-                    .await?;
-            }
-            ProposalActionType::Canceled(timestamp_block) => {
-                sqlx_client
-                    .update_proposal_canceled(proposal_address.to_string(), timestamp_block)
-                    .await?;
-            }
-            ProposalActionType::Queued(timestamp_block, execution_time) => {
-                sqlx_client
-                    .update_proposal_queued(
-                        proposal_address.to_string(),
-                        timestamp_block,
-                        execution_time,
-                    )
-                    .await?;
-            }
-        }
+    sqlx_client.create_proposal(tx, proposal).await?;
+
+    Ok(())
+}
+
+pub async fn parse_dao_config_updated_event(
+    kind: DaoConfigEventKind,
+    config: DaoConfigUpdate,
+    message_hash: Vec<u8>,
+    transaction: &Transaction,
+    sqlx_client: &SqlxClient,
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    state_provider: &dyn ContractStateProvider,
+) -> Result<(), anyhow::Error> {
+    log::debug!("Found dao config updated event {:?} : {:?}", kind, config);
+
+    let dao_root_address = transaction.contract_address()?;
+    if !super::is_dao_root(&dao_root_address) {
+        // skip event
+        return Ok(());
+    }
+
+    let dao_root = dao_root_address.to_string();
+    let timestamp_block = transaction.time() as i32;
+
+    let previous = match sqlx_client
+        .get_dao_config_at(tx, &dao_root, timestamp_block)
+        .await?
+    {
+        Some(previous) => previous,
+        // nothing is indexed before this change, so untouched parameters are taken from the
+        // contract. They are current ones, which may differ from the ones at the block time
+        None => state_provider
+            .get_dao_config(&dao_root_address)
+            .await?
+            .into(),
+    };
+
+    let payload = CreateDaoConfig {
+        dao_root,
+        kind,
+        config: config.merge(previous),
+        message_hash,
+        transaction_hash: transaction.tx_hash().trust_me().as_slice().to_vec(),
+        timestamp_block,
+    };
+
+    sqlx_client.create_dao_config(tx, payload).await?;
+
+    Ok(())
+}
+
+pub async fn parse_dao_audit_event(
+    event: AuditEventData,
+    message_hash: Vec<u8>,
+    transaction: &Transaction,
+    sqlx_client: &SqlxClient,
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+) -> Result<(), anyhow::Error> {
+    log::debug!("Found dao audit event : {:?}", event);
+
+    let dao_root_address = transaction.contract_address()?;
+    if !super::is_dao_root(&dao_root_address) {
+        // skip event
+        return Ok(());
     }
 
+    let payload = CreateAuditEvent {
+        dao_root: dao_root_address.to_string(),
+        kind: event.kind,
+        contract_address: dao_root_address.to_string(),
+        data: event.data,
+        message_hash,
+        transaction_hash: transaction.tx_hash().trust_me().as_slice().to_vec(),
+        timestamp_block: transaction.time() as i32,
+    };
+
+    sqlx_client.create_audit_event(tx, payload).await?;
+
     Ok(())
 }
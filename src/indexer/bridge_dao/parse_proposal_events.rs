@@ -1,44 +1,53 @@
-use anyhow::Context;
 use indexer_lib::TransactionExt;
-use nekoton_abi::*;
-use ton_block::{MsgAddressInt, Transaction};
-use transaction_consumer::TransactionConsumer;
+use nekoton_utils::TrustMe;
+use sqlx::Postgres;
+use ton_block::Transaction;
 
-use crate::global_cache::*;
+use crate::models::*;
 use crate::sqlx_client::*;
 use crate::ton_contracts::*;
 
 pub async fn parse_proposal_executed_event(
     transaction: &Transaction,
     sqlx_client: &SqlxClient,
-    transaction_consumer: &TransactionConsumer,
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    state_provider: &dyn ContractStateProvider,
 ) -> Result<(), anyhow::Error> {
     log::debug!("Found proposal executed event");
 
     let timestamp_block = transaction.time() as i32;
     let proposal_address = transaction.contract_address()?;
 
-    let function_output = transaction_consumer
-        .run_local(&proposal_address, get_dao_root(), &[])
-        .await?
-        .context("none function output")?;
-    let dao_root_address: MsgAddressInt =
-        function_output.tokens.unwrap_or_default().unpack_first()?;
+    let dao_root_address = state_provider.get_dao_root(&proposal_address).await?;
 
-    if dao_root_address != *super::DAO_ROOT_ADDRESS {
+    if !super::is_dao_root(&dao_root_address) {
         // skip event
         return Ok(());
     }
 
-    if sqlx_client
-        .update_proposal_executed(proposal_address.to_string(), timestamp_block)
-        .await
-        .is_err()
+    match sqlx_client
+        .update_proposal_executed(tx, proposal_address.to_string(), timestamp_block)
+        .await?
     {
-        save_proposal_action_in_cache(
-            proposal_address,
-            ProposalActionType::Executed(timestamp_block),
-        )
+        Some(proposal_id) => {
+            let event = CreateGovernanceEvent::executed(
+                dao_root_address.to_string(),
+                proposal_id,
+                timestamp_block,
+            );
+            sqlx_client.create_governance_event(tx, event).await?;
+        }
+        None => {
+            sqlx_client
+                .create_pending_action(
+                    tx,
+                    CreatePendingAction::Executed {
+                        proposal_address: proposal_address.to_string(),
+                        timestamp_block,
+                    },
+                )
+                .await?;
+        }
     }
 
     Ok(())
@@ -47,34 +56,44 @@ pub async fn parse_proposal_executed_event(
 pub async fn parse_proposal_canceled_event(
     transaction: &Transaction,
     sqlx_client: &SqlxClient,
-    transaction_consumer: &TransactionConsumer,
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    state_provider: &dyn ContractStateProvider,
 ) -> Result<(), anyhow::Error> {
     log::debug!("Found proposal canceled event");
 
     let timestamp_block = transaction.time() as i32;
     let proposal_address = transaction.contract_address()?;
 
-    let function_output = transaction_consumer
-        .run_local(&proposal_address, get_dao_root(), &[])
-        .await?
-        .context("none function output")?;
-    let dao_root_address: MsgAddressInt =
-        function_output.tokens.unwrap_or_default().unpack_first()?;
+    let dao_root_address = state_provider.get_dao_root(&proposal_address).await?;
 
-    if dao_root_address != *super::DAO_ROOT_ADDRESS {
+    if !super::is_dao_root(&dao_root_address) {
         // skip event
         return Ok(());
     }
 
-    if sqlx_client
-        .update_proposal_canceled(proposal_address.to_string(), timestamp_block)
-        .await
-        .is_err()
+    match sqlx_client
+        .update_proposal_canceled(tx, proposal_address.to_string(), timestamp_block)
+        .await?
     {
-        save_proposal_action_in_cache(
-            proposal_address,
-            ProposalActionType::Canceled(timestamp_block),
-        )
+        Some(proposal_id) => {
+            let event = CreateGovernanceEvent::canceled(
+                dao_root_address.to_string(),
+                proposal_id,
+                timestamp_block,
+            );
+            sqlx_client.create_governance_event(tx, event).await?;
+        }
+        None => {
+            sqlx_client
+                .create_pending_action(
+                    tx,
+                    CreatePendingAction::Canceled {
+                        proposal_address: proposal_address.to_string(),
+                        timestamp_block,
+                    },
+                )
+                .await?;
+        }
     }
 
     Ok(())
@@ -84,38 +103,85 @@ pub async fn parse_proposal_queued_event(
     execution_time: u32,
     transaction: &Transaction,
     sqlx_client: &SqlxClient,
-    transaction_consumer: &TransactionConsumer,
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    state_provider: &dyn ContractStateProvider,
 ) -> Result<(), anyhow::Error> {
     log::debug!("Found proposal queued event");
     let timestamp_block = transaction.time() as i32;
     let proposal_address = transaction.contract_address()?;
 
-    let function_output = transaction_consumer
-        .run_local(&proposal_address, get_dao_root(), &[])
-        .await?
-        .context("none function output")?;
-    let dao_root_address: MsgAddressInt =
-        function_output.tokens.unwrap_or_default().unpack_first()?;
+    let dao_root_address = state_provider.get_dao_root(&proposal_address).await?;
 
-    if dao_root_address != *super::DAO_ROOT_ADDRESS {
+    if !super::is_dao_root(&dao_root_address) {
         // skip event
         return Ok(());
     }
 
-    if sqlx_client
+    match sqlx_client
         .update_proposal_queued(
+            tx,
             proposal_address.to_string(),
             timestamp_block,
             execution_time as i64,
         )
-        .await
-        .is_err()
+        .await?
     {
-        save_proposal_action_in_cache(
-            proposal_address,
-            ProposalActionType::Queued(timestamp_block, execution_time as i64),
-        )
+        Some(proposal_id) => {
+            let event = CreateGovernanceEvent::queued(
+                dao_root_address.to_string(),
+                proposal_id,
+                execution_time as i64,
+                timestamp_block,
+            );
+            sqlx_client.create_governance_event(tx, event).await?;
+        }
+        None => {
+            sqlx_client
+                .create_pending_action(
+                    tx,
+                    CreatePendingAction::Queued {
+                        proposal_address: proposal_address.to_string(),
+                        timestamp_block,
+                        execution_time: execution_time as i64,
+                    },
+                )
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn parse_proposal_audit_event(
+    event: AuditEventData,
+    message_hash: Vec<u8>,
+    transaction: &Transaction,
+    sqlx_client: &SqlxClient,
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    state_provider: &dyn ContractStateProvider,
+) -> Result<(), anyhow::Error> {
+    log::debug!("Found proposal audit event : {:?}", event);
+
+    let proposal_address = transaction.contract_address()?;
+
+    let dao_root_address = state_provider.get_dao_root(&proposal_address).await?;
+
+    if !super::is_dao_root(&dao_root_address) {
+        // skip event
+        return Ok(());
     }
 
+    let payload = CreateAuditEvent {
+        dao_root: dao_root_address.to_string(),
+        kind: event.kind,
+        contract_address: proposal_address.to_string(),
+        data: event.data,
+        message_hash,
+        transaction_hash: transaction.tx_hash().trust_me().as_slice().to_vec(),
+        timestamp_block: transaction.time() as i32,
+    };
+
+    sqlx_client.create_audit_event(tx, payload).await?;
+
     Ok(())
 }
@@ -1,12 +1,9 @@
-use anyhow::Context;
 use indexer_lib::TransactionExt;
-use nekoton_abi::*;
 use nekoton_utils::TrustMe;
 use sqlx::types::Decimal;
+use sqlx::Postgres;
 use ton_block::Transaction;
-use transaction_consumer::TransactionConsumer;
 
-use crate::global_cache::*;
 use crate::models::*;
 use crate::sqlx_client::*;
 use crate::ton_contracts::*;
@@ -16,7 +13,8 @@ pub async fn parse_vote_cast_event(
     message_hash: Vec<u8>,
     transaction: &Transaction,
     sqlx_client: &SqlxClient,
-    transaction_consumer: &TransactionConsumer,
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    state_provider: &dyn ContractStateProvider,
 ) -> Result<(), anyhow::Error> {
     log::debug!("Found vote cast event - {:?}", vote);
 
@@ -25,20 +23,20 @@ pub async fn parse_vote_cast_event(
 
     // get userdata details
     let user_data_address = transaction.contract_address()?;
-    let function_output = transaction_consumer
-        .run_local(&user_data_address, get_user_data_details(), &[answer_id()])
-        .await?
-        .context("none function output")?;
-    let details: GetDetails = function_output.tokens.unwrap_or_default().unpack_first()?;
+    let details = state_provider
+        .get_user_data_owner(&user_data_address)
+        .await?;
 
     // check dao address
-    if details.dao_root != *super::DAO_ROOT_ADDRESS {
+    if !super::is_dao_root(&details.dao_root) {
         // skip event
         return Ok(());
     }
+    let dao_root = details.dao_root.to_string();
 
     // create vote
     let payload = CreateVote {
+        dao_root: dao_root.clone(),
         proposal_id: vote.proposal_id as i32,
         voter: details.user.to_string(),
         support: vote.support,
@@ -48,15 +46,17 @@ pub async fn parse_vote_cast_event(
         transaction_hash,
         timestamp_block,
     };
-    sqlx_client.create_vote(payload).await?;
-
-    // unlock vote if exist in cache
-    let unlock_vote = UnlockVote {
-        proposal_id: vote.proposal_id as i32,
-        voter: details.user.to_string(),
-    };
-    if remove_vote_actions_from_cache(unlock_vote.clone()) {
-        sqlx_client.unlock_vote(unlock_vote).await?;
+    if !sqlx_client.create_vote(tx, payload).await? {
+        // the vote was stored before, so its weight may already be in the tally
+        log::warn!(
+            "Duplicate vote of {} for proposal {}, recomputing tally",
+            details.user,
+            vote.proposal_id
+        );
+        sqlx_client
+            .recompute_proposal_votes(tx, dao_root, vote.proposal_id as i32)
+            .await?;
+        return Ok(());
     }
 
     // update proposal
@@ -72,9 +72,10 @@ pub async fn parse_vote_cast_event(
         }
     };
 
-    let _ = sqlx_client
-        .update_proposal_votes(vote.proposal_id as i32, payload.clone())
-        .await;
+    // votes of a proposal which is not indexed yet are counted on its creation
+    sqlx_client
+        .update_proposal_votes(tx, dao_root, vote.proposal_id as i32, payload)
+        .await?;
 
     Ok(())
 }
@@ -83,33 +84,51 @@ pub async fn parse_unlock_casted_votes_event(
     proposal_id: u32,
     transaction: &Transaction,
     sqlx_client: &SqlxClient,
-    transaction_consumer: &TransactionConsumer,
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    state_provider: &dyn ContractStateProvider,
 ) -> Result<(), anyhow::Error> {
     log::debug!("Found unlock casted votes event");
 
+    let timestamp_block = transaction.time() as i32;
+
     // get userdata details
     let user_data_address = transaction.contract_address()?;
-    let function_output = transaction_consumer
-        .run_local(&user_data_address, get_user_data_details(), &[answer_id()])
-        .await?
-        .context("none function output")?;
-    let details: GetDetails = function_output.tokens.unwrap_or_default().unpack_first()?;
+    let details = state_provider
+        .get_user_data_owner(&user_data_address)
+        .await?;
 
     // check dao address
-    if details.dao_root != *super::DAO_ROOT_ADDRESS {
+    if !super::is_dao_root(&details.dao_root) {
         // skip event
         return Ok(());
     }
 
     let vote = UnlockVote {
+        dao_root: details.dao_root.to_string(),
         proposal_id: proposal_id as i32,
         voter: details.user.to_string(),
     };
 
     log::debug!("Unlock event details {:?}", vote);
 
-    if sqlx_client.unlock_vote(vote.clone()).await.is_err() {
-        save_locked_vote_in_cache(vote)?;
+    match sqlx_client.unlock_vote(tx, vote.clone()).await? {
+        Some(_) => {
+            let event = CreateGovernanceEvent::vote_unlocked(&vote, timestamp_block);
+            sqlx_client.create_governance_event(tx, event).await?;
+        }
+        None => {
+            sqlx_client
+                .create_pending_action(
+                    tx,
+                    CreatePendingAction::UnlockVote {
+                        dao_root: vote.dao_root,
+                        proposal_id: vote.proposal_id,
+                        voter: vote.voter,
+                        timestamp_block,
+                    },
+                )
+                .await?;
+        }
     }
 
     Ok(())
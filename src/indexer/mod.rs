@@ -1,5 +1,9 @@
 pub use self::abi::*;
 pub use self::bridge_dao::*;
+pub use self::transaction_source::*;
+pub use self::webhooks::*;
 
 mod abi;
 mod bridge_dao;
+mod transaction_source;
+mod webhooks;
@@ -0,0 +1,224 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Context;
+use futures::channel::mpsc;
+use futures::{SinkExt, StreamExt};
+use indexer_lib::TransactionExt;
+use nekoton_utils::TrustMe;
+use tokio::sync::Notify;
+use ton_block::{Deserializable, Transaction};
+use transaction_buffer::models::RawTransaction;
+
+use super::*;
+use crate::indexer::extract_events;
+use crate::models::AllEvents;
+
+const BATCH_SIZE: usize = 100;
+
+/// Transactions read from a local directory. Every `*.boc` file contains
+/// a single serialized transaction, every `*.jsonl` file contains a base64
+/// encoded transaction per line (either as a plain string or as `{"boc": ...}`)
+pub struct FileTransactionSource {
+    pub dir: PathBuf,
+}
+
+impl TransactionSource for FileTransactionSource {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    fn start(self: Box<Self>) -> TransactionSourceChannels {
+        let (tx_parsed_events, rx_parsed_events) = mpsc::channel(1);
+        let (tx_commit, rx_commit) = mpsc::channel(1);
+        let notify_for_services = Arc::new(Notify::new());
+
+        tokio::spawn(replay_transactions(
+            self.dir,
+            tx_parsed_events,
+            rx_commit,
+            notify_for_services.clone(),
+        ));
+
+        TransactionSourceChannels {
+            rx_parsed_events,
+            tx_commit,
+            notify_for_services,
+        }
+    }
+}
+
+async fn replay_transactions(
+    dir: PathBuf,
+    mut tx_parsed_events: mpsc::Sender<ParsedTransactions>,
+    mut rx_commit: mpsc::Receiver<()>,
+    notify_for_services: Arc<Notify>,
+) {
+    log::info!("Start replaying transactions from {}...", dir.display());
+
+    let transactions = read_transactions(&dir).unwrap_or_else(|e| {
+        panic!(
+            "Failed to read transactions from {}: {:?}",
+            dir.display(),
+            e
+        )
+    });
+
+    let events_parsing = AllEvents::new().get_all_events();
+
+    let mut batch = Vec::with_capacity(BATCH_SIZE);
+    for transaction in transactions {
+        let hash = transaction.tx_hash().trust_me();
+        if let Some(parsed) = extract_events(&transaction, hash, &events_parsing) {
+            batch.push((
+                parsed,
+                RawTransaction {
+                    hash,
+                    data: transaction,
+                },
+            ));
+        }
+
+        if batch.len() == BATCH_SIZE {
+            send_batch(
+                &mut tx_parsed_events,
+                &mut rx_commit,
+                std::mem::take(&mut batch),
+            )
+            .await;
+        }
+    }
+    if !batch.is_empty() {
+        send_batch(&mut tx_parsed_events, &mut rx_commit, batch).await;
+    }
+
+    log::info!("All transactions from {} are replayed", dir.display());
+    notify_for_services.notify_one();
+
+    // keep the channel open, the indexer treats its end as a failure
+    futures::future::pending::<()>().await;
+}
+
+async fn send_batch(
+    tx_parsed_events: &mut mpsc::Sender<ParsedTransactions>,
+    rx_commit: &mut mpsc::Receiver<()>,
+    batch: ParsedTransactions,
+) {
+    tx_parsed_events
+        .send(batch)
+        .await
+        .expect("dead parsed events receiver");
+    rx_commit.next().await.expect("dead commit sender");
+}
+
+/// Transactions of all files in the directory, ordered by lt
+fn read_transactions(dir: &Path) -> Result<Vec<Transaction>, anyhow::Error> {
+    let mut paths = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|x| x.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.sort();
+
+    let mut transactions = Vec::new();
+    for path in paths {
+        match path.extension().and_then(|x| x.to_str()) {
+            Some("boc") => {
+                let bytes = std::fs::read(&path)?;
+                transactions.push(
+                    Transaction::construct_from_bytes(&bytes)
+                        .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?,
+                );
+            }
+            Some("jsonl") => {
+                let content = std::fs::read_to_string(&path)?;
+                for (i, line) in content.lines().enumerate() {
+                    let line = line.trim();
+                    if line.is_empty() {
+                        continue;
+                    }
+                    let transaction = parse_jsonl_line(line)
+                        .with_context(|| format!("{}:{}", path.display(), i + 1))?;
+                    transactions.push(transaction);
+                }
+            }
+            _ => log::warn!("Skipping unknown file {}", path.display()),
+        }
+    }
+    transactions.sort_by_key(|x| x.lt);
+
+    Ok(transactions)
+}
+
+fn parse_jsonl_line(line: &str) -> Result<Transaction, anyhow::Error> {
+    let value: serde_json::Value = serde_json::from_str(line)?;
+    let boc = match &value {
+        serde_json::Value::String(boc) => boc.as_str(),
+        serde_json::Value::Object(object) => object
+            .get("boc")
+            .and_then(|x| x.as_str())
+            .context("missing `boc` field")?,
+        _ => anyhow::bail!("expected a string or an object"),
+    };
+
+    Transaction::construct_from_base64(boc).map_err(|e| anyhow::anyhow!("{}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use ton_block::{AccountStatus, Serializable};
+    use ton_types::AccountId;
+
+    use super::*;
+
+    fn transaction(lt: u64) -> Transaction {
+        let mut transaction = Transaction::with_address_and_status(
+            AccountId::from([1; 32]),
+            AccountStatus::AccStateActive,
+        );
+        transaction.set_logical_time(lt);
+        transaction
+    }
+
+    fn boc(lt: u64) -> String {
+        base64::encode(transaction(lt).write_to_bytes().unwrap())
+    }
+
+    #[test]
+    fn jsonl_line_is_parsed() {
+        let plain = parse_jsonl_line(&format!("\"{}\"", boc(10))).unwrap();
+        assert_eq!(plain.lt, 10);
+
+        let object =
+            parse_jsonl_line(&format!("{{\"boc\": \"{}\", \"lt\": 20}}", boc(20))).unwrap();
+        assert_eq!(object.lt, 20);
+
+        assert!(parse_jsonl_line("{\"lt\": 20}").is_err());
+        assert!(parse_jsonl_line("20").is_err());
+        assert!(parse_jsonl_line("\"not a boc\"").is_err());
+        assert!(parse_jsonl_line("not json").is_err());
+    }
+
+    #[test]
+    fn transactions_are_ordered_by_lt() {
+        let dir = std::env::temp_dir().join(format!("file-source-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir(&dir).unwrap();
+
+        // file names don't follow the lt
+        std::fs::write(
+            dir.join("a.jsonl"),
+            format!("\"{}\"\n\n{{\"boc\": \"{}\"}}\n", boc(30), boc(10)),
+        )
+        .unwrap();
+        std::fs::write(dir.join("b.boc"), transaction(20).write_to_bytes().unwrap()).unwrap();
+        std::fs::write(dir.join("c.txt"), "skipped").unwrap();
+
+        let transactions = read_transactions(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let lts = transactions
+            .unwrap()
+            .into_iter()
+            .map(|x| x.lt)
+            .collect::<Vec<_>>();
+        assert_eq!(lts, [10, 20, 30]);
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use sqlx::PgPool;
+use transaction_buffer::models::{BufferedConsumerChannels, BufferedConsumerConfig};
+use transaction_buffer::start_parsing_and_get_channels;
+use transaction_consumer::TransactionConsumer;
+
+use super::*;
+use crate::models::AllEvents;
+
+/// Transactions consumed from kafka and buffered in postgres
+pub struct KafkaTransactionSource {
+    pub transaction_consumer: Arc<TransactionConsumer>,
+    pub pg_pool: PgPool,
+}
+
+impl TransactionSource for KafkaTransactionSource {
+    fn name(&self) -> &'static str {
+        "kafka"
+    }
+
+    fn start(self: Box<Self>) -> TransactionSourceChannels {
+        let BufferedConsumerChannels {
+            rx_parsed_events,
+            tx_commit,
+            notify_for_services,
+        } = start_parsing_and_get_channels(BufferedConsumerConfig {
+            transaction_consumer: self.transaction_consumer,
+            pg_pool: self.pg_pool,
+            events_to_parse: AllEvents::new().get_all_events().any_extractable,
+            buff_size: 100_000,
+            commit_time_secs: 100,
+        });
+
+        TransactionSourceChannels {
+            rx_parsed_events,
+            tx_commit,
+            notify_for_services,
+        }
+    }
+}
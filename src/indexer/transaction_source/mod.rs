@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use futures::channel::mpsc::{Receiver, Sender};
+use indexer_lib::{AnyExtractableOutput, ParsedOutput};
+use tokio::sync::Notify;
+use transaction_buffer::models::RawTransaction;
+
+pub use self::file::*;
+pub use self::kafka::*;
+
+mod file;
+mod kafka;
+
+pub type ParsedTransactions = Vec<(ParsedOutput<AnyExtractableOutput>, RawTransaction)>;
+
+pub struct TransactionSourceChannels {
+    /// Batches of transactions with at least one known event
+    pub rx_parsed_events: Receiver<ParsedTransactions>,
+    /// Signals that the last received batch was processed
+    pub tx_commit: Sender<()>,
+    /// Notified when the source has caught up
+    pub notify_for_services: Arc<Notify>,
+}
+
+/// Provider of transactions for the indexer
+pub trait TransactionSource {
+    /// Key of the indexer checkpoint
+    fn name(&self) -> &'static str;
+
+    fn start(self: Box<Self>) -> TransactionSourceChannels;
+}
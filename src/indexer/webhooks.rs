@@ -0,0 +1,186 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use chrono::Utc;
+use tokio::{net, time};
+
+use crate::models::*;
+use crate::sqlx_client::*;
+
+pub async fn webhook_dispatcher(sqlx_client: SqlxClient, retry_policy: RetryPolicy) {
+    log::info!("Start webhook dispatcher...");
+
+    let client = webhook_client();
+    let mut interval = time::interval(Duration::from_secs(5));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(err) = dispatch_webhook_deliveries(&sqlx_client, &client, retry_policy).await {
+            log::error!("Failed to dispatch webhook deliveries: {}", err);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct WebhookClient {
+    http: reqwest::Client,
+    allow_private_targets: bool,
+}
+
+impl WebhookClient {
+    /// Lets deliveries reach loopback and private hosts, e.g. a local test receiver
+    pub fn allow_private_targets(mut self) -> Self {
+        self.allow_private_targets = true;
+        self
+    }
+
+    /// Webhook hosts are resolved before each delivery,
+    /// so they can't be pointed to internal addresses via DNS after the registration.
+    /// The request is pinned to the checked address, otherwise the host could be
+    /// resolved to another one between the check and the request
+    async fn target_http(&self, url: &str) -> Result<reqwest::Client, anyhow::Error> {
+        if self.allow_private_targets {
+            return Ok(self.http.clone());
+        }
+
+        let url = url::Url::parse(url)?;
+        let port = url.port_or_known_default().unwrap_or_default();
+        let (domain, addrs): (_, Vec<SocketAddr>) = match url.host() {
+            Some(url::Host::Ipv4(ip)) => (None, vec![(ip, port).into()]),
+            Some(url::Host::Ipv6(ip)) => (None, vec![(ip, port).into()]),
+            Some(url::Host::Domain(domain)) => (
+                Some(domain),
+                net::lookup_host((domain, port)).await?.collect(),
+            ),
+            None => anyhow::bail!("Webhook url has no host"),
+        };
+
+        if let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+            anyhow::bail!(
+                "Webhook host resolves to a non-public address {}",
+                addr.ip()
+            );
+        }
+
+        match (domain, addrs.first()) {
+            (Some(domain), Some(addr)) => {
+                Ok(http_client_builder().resolve(domain, *addr).build()?)
+            }
+            (Some(domain), None) => anyhow::bail!("Webhook host {} doesn't resolve", domain),
+            (None, _) => Ok(self.http.clone()),
+        }
+    }
+}
+
+fn http_client_builder() -> reqwest::ClientBuilder {
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        // a redirect could lead to an internal host
+        .redirect(reqwest::redirect::Policy::none())
+}
+
+pub fn webhook_client() -> WebhookClient {
+    let http = http_client_builder()
+        .build()
+        .expect("Failed to build webhook HTTP client");
+
+    WebhookClient {
+        http,
+        allow_private_targets: false,
+    }
+}
+
+/// Sends a batch of due deliveries, returns the number of attempted ones
+pub async fn dispatch_webhook_deliveries(
+    sqlx_client: &SqlxClient,
+    client: &WebhookClient,
+    retry_policy: RetryPolicy,
+) -> Result<usize, anyhow::Error> {
+    const BATCH_SIZE: i32 = 100;
+
+    let deliveries = sqlx_client
+        .get_due_webhook_deliveries(Utc::now().timestamp_millis(), BATCH_SIZE)
+        .await?;
+    let count = deliveries.len();
+
+    for delivery in deliveries {
+        let result = match deliver(client, &delivery).await {
+            Ok(status) => {
+                sqlx_client
+                    .mark_webhook_delivered(delivery.id, status)
+                    .await
+            }
+            Err((status, err)) => {
+                log::warn!(
+                    "Failed to deliver event {} to {}: {}",
+                    delivery.event_id,
+                    delivery.url,
+                    err
+                );
+                sqlx_client
+                    .record_webhook_delivery_failure(
+                        delivery.id,
+                        status,
+                        err.to_string(),
+                        retry_policy,
+                    )
+                    .await
+                    .map(|state| {
+                        if state == Some(WebhookDeliveryState::DeadLetter) {
+                            log::warn!(
+                                "Webhook delivery {} moved to dead letter after {} attempts",
+                                delivery.id,
+                                retry_policy.max_attempts
+                            );
+                        }
+                    })
+            }
+        };
+
+        if let Err(err) = result {
+            log::error!("Failed to update webhook delivery {}: {}", delivery.id, err);
+        }
+    }
+
+    Ok(count)
+}
+
+/// Posts the signed payload, any non-2xx response is a failure
+async fn deliver(
+    client: &WebhookClient,
+    delivery: &DueWebhookDeliveryFromDb,
+) -> Result<i32, (Option<i32>, anyhow::Error)> {
+    let http = client
+        .target_http(&delivery.url)
+        .await
+        .map_err(|e| (None, e))?;
+
+    let body = serde_json::to_vec(&delivery.payload).map_err(|e| (None, e.into()))?;
+    let timestamp = Utc::now().timestamp();
+    let signature = sign_webhook_payload(&delivery.secret, timestamp, &body);
+
+    let response = http
+        .post(&delivery.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header("X-Dao-Event-Id", delivery.event_id)
+        .header("X-Dao-Delivery-Id", delivery.id)
+        .header("X-Dao-Timestamp", timestamp)
+        .header("X-Dao-Signature", format!("sha256={}", signature))
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| (None, e.into()))?;
+
+    let status = response.status();
+    if status.is_success() {
+        Ok(status.as_u16() as i32)
+    } else {
+        Err((
+            Some(status.as_u16() as i32),
+            anyhow::anyhow!("Unexpected response status {}", status),
+        ))
+    }
+}
@@ -1,8 +1,37 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 
+use dao_api::models::RawTransactionFilters;
 use dao_api::server::*;
 
+const USAGE: &str = "Usage: dao-api [reindex [--from-time <unixtime>] [--to-time <unixtime>] \
+    [--from-lt <lt>] [--to-lt <lt>]]";
+
 #[tokio::main(worker_threads = 8)]
 async fn main() -> Result<()> {
-    start_server().await
+    let mut args = std::env::args().skip(1);
+
+    match args.next().as_deref() {
+        None => start_server().await,
+        Some("reindex") => start_reindex(parse_reindex_args(args)?).await,
+        Some(command) => anyhow::bail!("Unknown command `{}`\n{}", command, USAGE),
+    }
+}
+
+fn parse_reindex_args(mut args: impl Iterator<Item = String>) -> Result<RawTransactionFilters> {
+    let mut filters = RawTransactionFilters::default();
+
+    while let Some(arg) = args.next() {
+        let value = args
+            .next()
+            .with_context(|| format!("Missing value for `{}`\n{}", arg, USAGE))?;
+        match arg.as_str() {
+            "--from-time" => filters.timestamp_block_ge = Some(value.parse()?),
+            "--to-time" => filters.timestamp_block_le = Some(value.parse()?),
+            "--from-lt" => filters.timestamp_lt_ge = Some(value.parse()?),
+            "--to-lt" => filters.timestamp_lt_le = Some(value.parse()?),
+            _ => anyhow::bail!("Unknown argument `{}`\n{}", arg, USAGE),
+        }
+    }
+
+    Ok(filters)
 }
@@ -2,6 +2,8 @@ use nekoton_abi::*;
 use ton_block::MsgAddressInt;
 use ton_types::UInt256;
 
+use super::ProposalConfig;
+
 #[derive(Debug, Clone, UnpackAbiPlain, KnownParamTypePlain)]
 pub struct ProposalCreated {
     #[abi(uint32, name = "proposalId")]
@@ -39,3 +41,90 @@ pub struct EthAction {
     #[abi(bytes, name = "callData")]
     pub call_data: Vec<u8>,
 }
+
+#[derive(Debug, Clone, UnpackAbiPlain, KnownParamTypePlain)]
+pub struct ProposalConfigurationUpdated {
+    #[abi(name = "oldConfig")]
+    pub old_config: ProposalConfig,
+    #[abi(name = "newConfig")]
+    pub new_config: ProposalConfig,
+}
+
+#[derive(Debug, Clone, UnpackAbiPlain, KnownParamTypePlain)]
+pub struct ProposalVotingDelayUpdated {
+    #[abi(uint32, name = "oldVotingDelay")]
+    pub old_voting_delay: u32,
+    #[abi(uint32, name = "newVotingDelay")]
+    pub new_voting_delay: u32,
+}
+
+#[derive(Debug, Clone, UnpackAbiPlain, KnownParamTypePlain)]
+pub struct ProposalVotingPeriodUpdated {
+    #[abi(uint32, name = "oldVotingPeriod")]
+    pub old_voting_period: u32,
+    #[abi(uint32, name = "newVotingPeriod")]
+    pub new_voting_period: u32,
+}
+
+#[derive(Debug, Clone, UnpackAbiPlain, KnownParamTypePlain)]
+pub struct ProposalQuorumVotesUpdated {
+    #[abi(uint128, name = "oldQuorumVotes")]
+    pub old_quorum_votes: u128,
+    #[abi(uint128, name = "newQuorumVotes")]
+    pub new_quorum_votes: u128,
+}
+
+#[derive(Debug, Clone, UnpackAbiPlain, KnownParamTypePlain)]
+pub struct ProposalTimeLockUpdated {
+    #[abi(uint32, name = "oldTimeLock")]
+    pub old_time_lock: u32,
+    #[abi(uint32, name = "newTimeLock")]
+    pub new_time_lock: u32,
+}
+
+#[derive(Debug, Clone, UnpackAbiPlain, KnownParamTypePlain)]
+pub struct ProposalThresholdUpdated {
+    #[abi(uint128, name = "oldThreshold")]
+    pub old_threshold: u128,
+    #[abi(uint128, name = "newThreshold")]
+    pub new_threshold: u128,
+}
+
+#[derive(Debug, Clone, UnpackAbiPlain, KnownParamTypePlain)]
+pub struct ProposalGracePeriodUpdated {
+    #[abi(uint32, name = "oldGracePeriod")]
+    pub old_grace_period: u32,
+    #[abi(uint32, name = "newGracePeriod")]
+    pub new_grace_period: u32,
+}
+
+#[derive(Debug, Clone, UnpackAbiPlain, KnownParamTypePlain)]
+pub struct RequestedAdminTransfer {
+    #[abi(address, name = "oldAdmin")]
+    pub old_admin: MsgAddressInt,
+    #[abi(address, name = "newAdmin")]
+    pub new_admin: MsgAddressInt,
+}
+
+#[derive(Debug, Clone, UnpackAbiPlain, KnownParamTypePlain)]
+pub struct AdminTransferAccepted {
+    #[abi(address, name = "oldAdmin")]
+    pub old_admin: MsgAddressInt,
+    #[abi(address, name = "newAdmin")]
+    pub new_admin: MsgAddressInt,
+}
+
+#[derive(Debug, Clone, UnpackAbiPlain, KnownParamTypePlain)]
+pub struct StakingRootUpdated {
+    #[abi(address, name = "oldRoot")]
+    pub old_root: MsgAddressInt,
+    #[abi(address, name = "newRoot")]
+    pub new_root: MsgAddressInt,
+}
+
+/// Emitted by both DaoRoot and Proposal contracts
+#[derive(Debug, Clone, UnpackAbiPlain, KnownParamTypePlain)]
+pub struct ProposalCodeUpgraded {
+    #[abi(uint16, name = "newVersion")]
+    pub new_version: u16,
+}
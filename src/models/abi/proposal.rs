@@ -38,3 +38,9 @@ pub struct ProposalOverview {
     #[abi(uint8)]
     pub state: u8,
 }
+
+#[derive(Debug, Clone, UnpackAbiPlain, KnownParamTypePlain)]
+pub struct CodeUpgradeRequested {
+    #[abi(uint16, name = "currentVersion")]
+    pub current_version: u16,
+}
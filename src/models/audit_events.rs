@@ -0,0 +1,156 @@
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::models::*;
+use crate::utils::*;
+
+pub type AuditEventsSearch = Paginated<Ordered<AuditEventFilters, AuditEventsOrdering>>;
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct AuditEventFilters {
+    pub dao_root: Option<String>,
+
+    pub kind: Option<GovernanceAuditEventKind>,
+
+    pub contract_address: Option<String>,
+
+    pub timestamp_block_ge: Option<i32>,
+    pub timestamp_block_le: Option<i32>,
+}
+
+/// Privileged DAO changes. `ProposalCodeUpgraded` is emitted by DaoRoot when
+/// the proposal code is replaced, `ProposalUpgraded` by a Proposal contract
+/// when it switches to the new code
+#[derive(
+    Debug,
+    serde::Deserialize,
+    serde::Serialize,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Hash,
+    sqlx::Type,
+    opg::OpgModel,
+)]
+#[sqlx(type_name = "governance_audit_event_type", rename_all = "PascalCase")]
+#[opg("Governance audit event kind")]
+pub enum GovernanceAuditEventKind {
+    RequestedAdminTransfer,
+    AdminTransferAccepted,
+    ProposalCodeUpgraded,
+    RootCodeUpgraded,
+    StakingRootUpdated,
+    CodeUpgradeRequested,
+    ProposalUpgraded,
+}
+
+impl Default for GovernanceAuditEventKind {
+    fn default() -> Self {
+        GovernanceAuditEventKind::RequestedAdminTransfer
+    }
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct AuditEventData {
+    pub kind: GovernanceAuditEventKind,
+    pub data: serde_json::Value,
+}
+
+impl From<RequestedAdminTransfer> for AuditEventData {
+    fn from(event: RequestedAdminTransfer) -> Self {
+        Self {
+            kind: GovernanceAuditEventKind::RequestedAdminTransfer,
+            data: json!({
+                "oldAdmin": event.old_admin.to_string(),
+                "newAdmin": event.new_admin.to_string(),
+            }),
+        }
+    }
+}
+
+impl From<AdminTransferAccepted> for AuditEventData {
+    fn from(event: AdminTransferAccepted) -> Self {
+        Self {
+            kind: GovernanceAuditEventKind::AdminTransferAccepted,
+            data: json!({
+                "oldAdmin": event.old_admin.to_string(),
+                "newAdmin": event.new_admin.to_string(),
+            }),
+        }
+    }
+}
+
+impl From<StakingRootUpdated> for AuditEventData {
+    fn from(event: StakingRootUpdated) -> Self {
+        Self {
+            kind: GovernanceAuditEventKind::StakingRootUpdated,
+            data: json!({
+                "oldRoot": event.old_root.to_string(),
+                "newRoot": event.new_root.to_string(),
+            }),
+        }
+    }
+}
+
+impl From<CodeUpgradeRequested> for AuditEventData {
+    fn from(event: CodeUpgradeRequested) -> Self {
+        Self {
+            kind: GovernanceAuditEventKind::CodeUpgradeRequested,
+            data: json!({ "currentVersion": event.current_version }),
+        }
+    }
+}
+
+impl AuditEventData {
+    pub fn root_code_upgraded() -> Self {
+        Self {
+            kind: GovernanceAuditEventKind::RootCodeUpgraded,
+            data: json!({}),
+        }
+    }
+
+    pub fn proposal_code_upgraded(
+        kind: GovernanceAuditEventKind,
+        event: ProposalCodeUpgraded,
+    ) -> Self {
+        Self {
+            kind,
+            data: json!({ "newVersion": event.new_version }),
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct CreateAuditEvent {
+    pub dao_root: String,
+    pub kind: GovernanceAuditEventKind,
+    pub contract_address: String,
+    pub data: serde_json::Value,
+    pub message_hash: Vec<u8>,
+    pub transaction_hash: Vec<u8>,
+    pub timestamp_block: i32,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize, Eq, PartialEq, Hash, opg::OpgModel)]
+#[opg("Audit events ordering")]
+pub struct AuditEventsOrdering {
+    pub column: AuditEventColumn,
+    pub direction: Direction,
+}
+
+impl Default for AuditEventsOrdering {
+    fn default() -> Self {
+        Self {
+            column: AuditEventColumn::CreatedAt,
+            direction: Direction::Descending,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Deserialize, Eq, PartialEq, Hash, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Audit event column")]
+pub enum AuditEventColumn {
+    CreatedAt,
+}
@@ -0,0 +1,80 @@
+use anyhow::Context;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::models::*;
+
+/// Last proposal of a page in the `(timestamp_block, id)` order
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ProposalCursor {
+    pub timestamp_block: i32,
+    pub id: i32,
+    pub dao_root: String,
+}
+
+impl From<&ProposalFromDb> for ProposalCursor {
+    fn from(x: &ProposalFromDb) -> Self {
+        Self {
+            timestamp_block: x.timestamp_block,
+            id: x.id,
+            dao_root: x.dao_root.clone(),
+        }
+    }
+}
+
+/// Last vote of a page in the `(timestamp_block, proposal_id, voter)` order
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct VoteCursor {
+    pub timestamp_block: i32,
+    pub proposal_id: i32,
+    pub voter: String,
+    pub dao_root: String,
+}
+
+impl From<&VoteFromDb> for VoteCursor {
+    fn from(x: &VoteFromDb) -> Self {
+        Self {
+            timestamp_block: x.timestamp_block,
+            proposal_id: x.proposal_id,
+            voter: x.voter.clone(),
+            dao_root: x.dao_root.clone(),
+        }
+    }
+}
+
+pub fn encode_cursor<T: Serialize>(cursor: &T) -> String {
+    let cursor = serde_json::to_vec(cursor).expect("cursor is always serializable");
+    base64::encode_config(cursor, base64::URL_SAFE_NO_PAD)
+}
+
+pub fn decode_cursor<T: DeserializeOwned>(cursor: &str) -> anyhow::Result<T> {
+    base64::decode_config(cursor, base64::URL_SAFE_NO_PAD)
+        .map_err(anyhow::Error::from)
+        .and_then(|cursor| serde_json::from_slice(&cursor).map_err(anyhow::Error::from))
+        .context("Invalid cursor")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_roundtrip() {
+        let cursor = VoteCursor {
+            timestamp_block: 1640000000,
+            proposal_id: 1,
+            voter: "0:1111111111111111111111111111111111111111111111111111111111111111".to_owned(),
+            dao_root: "0:2222222222222222222222222222222222222222222222222222222222222222"
+                .to_owned(),
+        };
+
+        let encoded = encode_cursor(&cursor);
+        assert_eq!(decode_cursor::<VoteCursor>(&encoded).unwrap(), cursor);
+    }
+
+    #[test]
+    fn invalid_cursor() {
+        assert!(decode_cursor::<ProposalCursor>("not a cursor").is_err());
+        assert!(decode_cursor::<ProposalCursor>(&encode_cursor(&1)).is_err());
+    }
+}
@@ -0,0 +1,173 @@
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::models::*;
+use crate::utils::*;
+
+pub type DaoConfigHistorySearch = Paginated<Ordered<DaoConfigFilters, DaoConfigOrdering>>;
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct DaoConfigFilters {
+    pub dao_root: Option<String>,
+
+    pub kind: Option<DaoConfigEventKind>,
+
+    pub timestamp_block_ge: Option<i32>,
+    pub timestamp_block_le: Option<i32>,
+}
+
+#[derive(
+    Debug,
+    serde::Deserialize,
+    serde::Serialize,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Hash,
+    sqlx::Type,
+    opg::OpgModel,
+)]
+#[sqlx(type_name = "dao_config_event_type", rename_all = "PascalCase")]
+#[opg("Dao config event kind")]
+pub enum DaoConfigEventKind {
+    ProposalConfigurationUpdated,
+    ProposalVotingDelayUpdated,
+    ProposalVotingPeriodUpdated,
+    ProposalQuorumVotesUpdated,
+    ProposalTimeLockUpdated,
+    ProposalThresholdUpdated,
+    ProposalGracePeriodUpdated,
+}
+
+impl Default for DaoConfigEventKind {
+    fn default() -> Self {
+        DaoConfigEventKind::ProposalConfigurationUpdated
+    }
+}
+
+/// Governance parameters changed by a single DaoRoot event.
+/// Parameters which were not touched by the event are `None`
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Default)]
+pub struct DaoConfigUpdate {
+    pub voting_delay: Option<i64>,
+    pub voting_period: Option<i64>,
+    pub quorum_votes: Option<Decimal>,
+    pub time_lock: Option<i64>,
+    pub threshold: Option<Decimal>,
+    pub grace_period: Option<i64>,
+}
+
+impl DaoConfigUpdate {
+    /// Fills untouched parameters from the previous config
+    pub fn merge(self, previous: DaoConfigUpdate) -> Self {
+        Self {
+            voting_delay: self.voting_delay.or(previous.voting_delay),
+            voting_period: self.voting_period.or(previous.voting_period),
+            quorum_votes: self.quorum_votes.or(previous.quorum_votes),
+            time_lock: self.time_lock.or(previous.time_lock),
+            threshold: self.threshold.or(previous.threshold),
+            grace_period: self.grace_period.or(previous.grace_period),
+        }
+    }
+}
+
+impl From<ProposalConfig> for DaoConfigUpdate {
+    fn from(config: ProposalConfig) -> Self {
+        Self {
+            voting_delay: Some(config.voting_delay as i64),
+            voting_period: Some(config.voting_period as i64),
+            quorum_votes: Some(Decimal::from(config.quorum_votes)),
+            time_lock: Some(config.time_lock as i64),
+            threshold: Some(Decimal::from(config.threshold)),
+            grace_period: Some(config.grace_period as i64),
+        }
+    }
+}
+
+impl From<ProposalVotingDelayUpdated> for DaoConfigUpdate {
+    fn from(event: ProposalVotingDelayUpdated) -> Self {
+        Self {
+            voting_delay: Some(event.new_voting_delay as i64),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<ProposalVotingPeriodUpdated> for DaoConfigUpdate {
+    fn from(event: ProposalVotingPeriodUpdated) -> Self {
+        Self {
+            voting_period: Some(event.new_voting_period as i64),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<ProposalQuorumVotesUpdated> for DaoConfigUpdate {
+    fn from(event: ProposalQuorumVotesUpdated) -> Self {
+        Self {
+            quorum_votes: Some(Decimal::from(event.new_quorum_votes)),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<ProposalTimeLockUpdated> for DaoConfigUpdate {
+    fn from(event: ProposalTimeLockUpdated) -> Self {
+        Self {
+            time_lock: Some(event.new_time_lock as i64),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<ProposalThresholdUpdated> for DaoConfigUpdate {
+    fn from(event: ProposalThresholdUpdated) -> Self {
+        Self {
+            threshold: Some(Decimal::from(event.new_threshold)),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<ProposalGracePeriodUpdated> for DaoConfigUpdate {
+    fn from(event: ProposalGracePeriodUpdated) -> Self {
+        Self {
+            grace_period: Some(event.new_grace_period as i64),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct CreateDaoConfig {
+    pub dao_root: String,
+    pub kind: DaoConfigEventKind,
+    pub config: DaoConfigUpdate,
+    pub message_hash: Vec<u8>,
+    pub transaction_hash: Vec<u8>,
+    pub timestamp_block: i32,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize, Eq, PartialEq, Hash, opg::OpgModel)]
+#[opg("Dao config ordering")]
+pub struct DaoConfigOrdering {
+    pub column: DaoConfigColumn,
+    pub direction: Direction,
+}
+
+impl Default for DaoConfigOrdering {
+    fn default() -> Self {
+        Self {
+            column: DaoConfigColumn::CreatedAt,
+            direction: Direction::Descending,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Deserialize, Eq, PartialEq, Hash, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Dao config column")]
+pub enum DaoConfigColumn {
+    CreatedAt,
+}
@@ -0,0 +1,126 @@
+use anyhow::Context;
+use ethabi::param_type::Reader;
+use ethabi::{ParamType, Token};
+
+/// Ethereum action call data decoded against its signature
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Decoded Ethereum call")]
+pub struct DecodedEthCall {
+    pub method: String,
+    pub params: Vec<DecodedEthParam>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Decoded Ethereum call param")]
+pub struct DecodedEthParam {
+    /// Canonical Solidity type, e.g. `uint256`
+    pub param_type: String,
+    pub value: String,
+}
+
+/// Decodes hex `call_data` (without the method selector) using a Solidity
+/// signature like `setConfiguration(uint256,address)`
+pub fn decode_eth_call(signature: &str, call_data: &str) -> Result<DecodedEthCall, anyhow::Error> {
+    let (method, params) = signature
+        .trim()
+        .strip_suffix(')')
+        .and_then(|x| x.split_once('('))
+        .context("Invalid signature")?;
+
+    let param_types = if params.trim().is_empty() {
+        Vec::new()
+    } else {
+        match Reader::read(&format!("({})", params))? {
+            ParamType::Tuple(types) => types,
+            _ => anyhow::bail!("Invalid signature params"),
+        }
+    };
+
+    let call_data = hex::decode(call_data.trim_start_matches("0x"))?;
+    let tokens = ethabi::decode(&param_types, &call_data)?;
+
+    Ok(DecodedEthCall {
+        method: method.trim().to_owned(),
+        params: param_types
+            .iter()
+            .zip(tokens.iter())
+            .map(|(param_type, token)| DecodedEthParam {
+                param_type: param_type.to_string(),
+                value: format_token(token),
+            })
+            .collect(),
+    })
+}
+
+fn format_token(token: &Token) -> String {
+    match token {
+        Token::Address(address) => format!("0x{}", hex::encode(address.as_bytes())),
+        Token::Bytes(bytes) | Token::FixedBytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        Token::Uint(value) => value.to_string(),
+        Token::Int(value) if value.bit(255) => {
+            // two's complement
+            format!("-{}", (!*value).overflowing_add(1.into()).0)
+        }
+        Token::Int(value) => value.to_string(),
+        Token::Bool(value) => value.to_string(),
+        Token::String(value) => value.clone(),
+        Token::FixedArray(tokens) | Token::Array(tokens) => {
+            format!(
+                "[{}]",
+                tokens
+                    .iter()
+                    .map(format_token)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+        Token::Tuple(tokens) => {
+            format!(
+                "({})",
+                tokens
+                    .iter()
+                    .map(format_token)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_set_configuration() {
+        let call_data = format!(
+            "{:064x}{}",
+            1000, "000000000000000000000000a5b1eb5cf0e8aa95d7e1b61a38b6c4b2a4b4f2c1"
+        );
+
+        let decoded = decode_eth_call("setConfiguration(uint256,address)", &call_data).unwrap();
+        assert_eq!(decoded.method, "setConfiguration");
+        assert_eq!(decoded.params.len(), 2);
+        assert_eq!(decoded.params[0].param_type, "uint256");
+        assert_eq!(decoded.params[0].value, "1000");
+        assert_eq!(decoded.params[1].param_type, "address");
+        assert_eq!(
+            decoded.params[1].value,
+            "0xa5b1eb5cf0e8aa95d7e1b61a38b6c4b2a4b4f2c1"
+        );
+    }
+
+    #[test]
+    fn decode_negative_int() {
+        let decoded = decode_eth_call("setOffset(int256)", &"f".repeat(64)).unwrap();
+        assert_eq!(decoded.params[0].value, "-1");
+    }
+
+    #[test]
+    fn invalid_call_data() {
+        assert!(decode_eth_call("setConfiguration(uint256,address)", "00").is_err());
+        assert!(decode_eth_call("setConfiguration", "").is_err());
+    }
+}
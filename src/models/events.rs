@@ -69,8 +69,34 @@ fn dao_root() -> Vec<AnyExtractable> {
     let contract = ton_abi::Contract::load(DAO_ROOT_ABI).trust_me();
     let events = contract.events;
     let proposal_created = events.get("ProposalCreated").trust_me();
+    let proposal_configuration_updated = events.get("ProposalConfigurationUpdated").trust_me();
+    let proposal_voting_delay_updated = events.get("ProposalVotingDelayUpdated").trust_me();
+    let proposal_voting_period_updated = events.get("ProposalVotingPeriodUpdated").trust_me();
+    let proposal_quorum_votes_updated = events.get("ProposalQuorumVotesUpdated").trust_me();
+    let proposal_time_lock_updated = events.get("ProposalTimeLockUpdated").trust_me();
+    let proposal_threshold_updated = events.get("ProposalThresholdUpdated").trust_me();
+    let proposal_grace_period_updated = events.get("ProposalGracePeriodUpdated").trust_me();
+    let requested_admin_transfer = events.get("RequestedAdminTransfer").trust_me();
+    let admin_transfer_accepted = events.get("AdminTransferAccepted").trust_me();
+    let proposal_code_upgraded = events.get("ProposalCodeUpgraded").trust_me();
+    let root_code_upgraded = events.get("RootCodeUpgraded").trust_me();
+    let staking_root_updated = events.get("StakingRootUpdated").trust_me();
 
-    vec![AnyExtractable::Event(proposal_created.clone())]
+    vec![
+        AnyExtractable::Event(proposal_created.clone()),
+        AnyExtractable::Event(proposal_configuration_updated.clone()),
+        AnyExtractable::Event(proposal_voting_delay_updated.clone()),
+        AnyExtractable::Event(proposal_voting_period_updated.clone()),
+        AnyExtractable::Event(proposal_quorum_votes_updated.clone()),
+        AnyExtractable::Event(proposal_time_lock_updated.clone()),
+        AnyExtractable::Event(proposal_threshold_updated.clone()),
+        AnyExtractable::Event(proposal_grace_period_updated.clone()),
+        AnyExtractable::Event(requested_admin_transfer.clone()),
+        AnyExtractable::Event(admin_transfer_accepted.clone()),
+        AnyExtractable::Event(proposal_code_upgraded.clone()),
+        AnyExtractable::Event(root_code_upgraded.clone()),
+        AnyExtractable::Event(staking_root_updated.clone()),
+    ]
 }
 
 fn user_data() -> Vec<AnyExtractable> {
@@ -91,10 +117,14 @@ fn proposal() -> Vec<AnyExtractable> {
     let executed = events.get("Executed").trust_me();
     let canceled = events.get("Canceled").trust_me();
     let queued = events.get("Queued").trust_me();
+    let code_upgrade_requested = events.get("CodeUpgradeRequested").trust_me();
+    let proposal_code_upgraded = events.get("ProposalCodeUpgraded").trust_me();
 
     vec![
         AnyExtractable::Event(executed.clone()),
         AnyExtractable::Event(canceled.clone()),
         AnyExtractable::Event(queued.clone()),
+        AnyExtractable::Event(code_upgrade_requested.clone()),
+        AnyExtractable::Event(proposal_code_upgraded.clone()),
     ]
 }
@@ -0,0 +1,168 @@
+use serde_json::json;
+
+use crate::models::*;
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct GovernanceEventFilters {
+    pub dao_root: Option<String>,
+    pub proposal_id: Option<i32>,
+    pub voter: Option<String>,
+}
+
+/// Proposal and vote changes applied by the indexer, and proposal state
+/// changes detected by the clock
+#[derive(
+    Debug,
+    serde::Deserialize,
+    serde::Serialize,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Hash,
+    sqlx::Type,
+    opg::OpgModel,
+)]
+#[sqlx(type_name = "governance_event_type", rename_all = "PascalCase")]
+#[opg("Governance event kind")]
+pub enum GovernanceEventKind {
+    ProposalCreated,
+    VoteCast,
+    VoteUnlocked,
+    Queued,
+    Executed,
+    Canceled,
+    StateChanged,
+}
+
+impl Default for GovernanceEventKind {
+    fn default() -> Self {
+        GovernanceEventKind::ProposalCreated
+    }
+}
+
+impl sqlx::postgres::PgHasArrayType for GovernanceEventKind {
+    fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+        sqlx::postgres::PgTypeInfo::with_name("_governance_event_type")
+    }
+}
+
+impl ToString for GovernanceEventKind {
+    fn to_string(&self) -> String {
+        match self {
+            GovernanceEventKind::ProposalCreated => "ProposalCreated".into(),
+            GovernanceEventKind::VoteCast => "VoteCast".into(),
+            GovernanceEventKind::VoteUnlocked => "VoteUnlocked".into(),
+            GovernanceEventKind::Queued => "Queued".into(),
+            GovernanceEventKind::Executed => "Executed".into(),
+            GovernanceEventKind::Canceled => "Canceled".into(),
+            GovernanceEventKind::StateChanged => "StateChanged".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateGovernanceEvent {
+    pub dao_root: String,
+    pub kind: GovernanceEventKind,
+    pub proposal_id: i32,
+    pub voter: Option<String>,
+    pub data: serde_json::Value,
+    pub timestamp_block: i32,
+}
+
+impl CreateGovernanceEvent {
+    pub fn proposal_created(proposal: &CreateProposal) -> Self {
+        Self {
+            dao_root: proposal.dao_root.clone(),
+            kind: GovernanceEventKind::ProposalCreated,
+            proposal_id: proposal.id,
+            voter: None,
+            data: json!({
+                "address": proposal.address,
+                "proposer": proposal.proposer,
+                "startTime": proposal.start_time,
+                "endTime": proposal.end_time,
+            }),
+            timestamp_block: proposal.timestamp_block,
+        }
+    }
+
+    pub fn vote_cast(vote: &CreateVote) -> Self {
+        Self {
+            dao_root: vote.dao_root.clone(),
+            kind: GovernanceEventKind::VoteCast,
+            proposal_id: vote.proposal_id,
+            voter: Some(vote.voter.clone()),
+            data: json!({
+                "support": vote.support,
+                "votes": vote.votes.to_string(),
+                "reason": vote.reason,
+            }),
+            timestamp_block: vote.timestamp_block,
+        }
+    }
+
+    pub fn vote_unlocked(vote: &UnlockVote, timestamp_block: i32) -> Self {
+        Self {
+            dao_root: vote.dao_root.clone(),
+            kind: GovernanceEventKind::VoteUnlocked,
+            proposal_id: vote.proposal_id,
+            voter: Some(vote.voter.clone()),
+            data: json!({}),
+            timestamp_block,
+        }
+    }
+
+    pub fn queued(
+        dao_root: String,
+        proposal_id: i32,
+        execution_time: i64,
+        timestamp_block: i32,
+    ) -> Self {
+        Self {
+            dao_root,
+            kind: GovernanceEventKind::Queued,
+            proposal_id,
+            voter: None,
+            data: json!({ "executionTime": execution_time }),
+            timestamp_block,
+        }
+    }
+
+    pub fn executed(dao_root: String, proposal_id: i32, timestamp_block: i32) -> Self {
+        Self {
+            dao_root,
+            kind: GovernanceEventKind::Executed,
+            proposal_id,
+            voter: None,
+            data: json!({}),
+            timestamp_block,
+        }
+    }
+
+    pub fn canceled(dao_root: String, proposal_id: i32, timestamp_block: i32) -> Self {
+        Self {
+            dao_root,
+            kind: GovernanceEventKind::Canceled,
+            proposal_id,
+            voter: None,
+            data: json!({}),
+            timestamp_block,
+        }
+    }
+
+    pub fn state_changed(dao_root: String, proposal_id: i32, change: ProposalStateChange) -> Self {
+        Self {
+            dao_root,
+            kind: GovernanceEventKind::StateChanged,
+            proposal_id,
+            voter: None,
+            data: json!({
+                "state": change.state,
+                "timestamp": change.timestamp,
+            }),
+            timestamp_block: change.timestamp as i32,
+        }
+    }
+}
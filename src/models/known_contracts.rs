@@ -0,0 +1,24 @@
+/// Contracts whose DAO root and owner were already resolved
+#[derive(
+    Debug, serde::Deserialize, serde::Serialize, Clone, Copy, Eq, PartialEq, Hash, sqlx::Type,
+)]
+#[sqlx(type_name = "known_contract_type", rename_all = "PascalCase")]
+pub enum KnownContractKind {
+    Proposal,
+    UserData,
+}
+
+impl Default for KnownContractKind {
+    fn default() -> Self {
+        KnownContractKind::Proposal
+    }
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct CreateKnownContract {
+    pub address: String,
+    pub kind: KnownContractKind,
+    pub dao_root: String,
+    /// UserData owner
+    pub owner: Option<String>,
+}
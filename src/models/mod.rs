@@ -1,17 +1,41 @@
 pub use self::abi::*;
+pub use self::audit_events::*;
+pub use self::cursor::*;
+pub use self::dao_config::*;
 pub use self::direction::*;
+pub use self::eth_call::*;
 pub use self::events::*;
+pub use self::governance_events::*;
+pub use self::known_contracts::*;
+pub use self::pending_actions::*;
 pub use self::proposal_state::*;
+pub use self::proposal_timeline::*;
 pub use self::proposals::*;
+pub use self::raw_transactions::*;
 pub use self::sqlx::*;
+pub use self::timeseries::*;
+pub use self::ton_call::*;
 pub use self::voters::*;
 pub use self::votes::*;
+pub use self::webhooks::*;
 
 mod abi;
+mod audit_events;
+mod cursor;
+mod dao_config;
 mod direction;
+mod eth_call;
 mod events;
+mod governance_events;
+mod known_contracts;
+mod pending_actions;
 mod proposal_state;
+mod proposal_timeline;
 mod proposals;
+mod raw_transactions;
 mod sqlx;
+mod timeseries;
+mod ton_call;
 mod voters;
 mod votes;
+mod webhooks;
@@ -0,0 +1,61 @@
+use crate::utils::*;
+
+pub type PendingActionsSearch = Paginated<PendingActionFilters>;
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct PendingActionFilters {
+    pub kind: Option<PendingActionKind>,
+
+    pub created_at_le: Option<i64>,
+}
+
+/// Proposal and vote actions which arrived before the proposal or vote itself
+#[derive(
+    Debug,
+    serde::Deserialize,
+    serde::Serialize,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Hash,
+    sqlx::Type,
+    opg::OpgModel,
+)]
+#[sqlx(type_name = "pending_action_type", rename_all = "PascalCase")]
+#[opg("Pending action kind")]
+pub enum PendingActionKind {
+    Executed,
+    Canceled,
+    Queued,
+    UnlockVote,
+}
+
+impl Default for PendingActionKind {
+    fn default() -> Self {
+        PendingActionKind::Executed
+    }
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub enum CreatePendingAction {
+    Executed {
+        proposal_address: String,
+        timestamp_block: i32,
+    },
+    Canceled {
+        proposal_address: String,
+        timestamp_block: i32,
+    },
+    Queued {
+        proposal_address: String,
+        timestamp_block: i32,
+        execution_time: i64,
+    },
+    UnlockVote {
+        dao_root: String,
+        proposal_id: i32,
+        voter: String,
+        timestamp_block: i32,
+    },
+}
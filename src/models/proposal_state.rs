@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use crate::models::ProposalFromDb;
+
 #[derive(
     Debug, serde::Serialize, serde::Deserialize, Clone, Copy, Eq, PartialEq, Hash, opg::OpgModel,
 )]
@@ -47,3 +49,252 @@ impl FromStr for ProposalState {
         }
     }
 }
+
+impl ProposalState {
+    /// States entered by the clock rather than by an on-chain event
+    pub fn is_time_driven(self) -> bool {
+        matches!(
+            self,
+            ProposalState::Active
+                | ProposalState::Failed
+                | ProposalState::Succeeded
+                | ProposalState::Expired
+        )
+    }
+}
+
+#[derive(
+    Debug, serde::Serialize, serde::Deserialize, Clone, Copy, Eq, PartialEq, opg::OpgModel,
+)]
+#[serde(rename_all = "camelCase")]
+#[opg("Proposal state change")]
+pub struct ProposalStateChange {
+    pub state: ProposalState,
+    pub timestamp: i64,
+}
+
+impl ProposalFromDb {
+    /// State changes up to `now` derived from the proposal timestamps, the last
+    /// one is the current state
+    pub fn state_history(&self, now: i64) -> Vec<ProposalStateChange> {
+        let mut history = Vec::new();
+        let mut push = |state, timestamp| history.push(ProposalStateChange { state, timestamp });
+
+        push(ProposalState::Pending, self.timestamp_block as i64);
+
+        // nothing happens to a canceled proposal
+        let canceled_at = self.canceled_at.filter(|_| self.canceled).map(i64::from);
+        let closed_at = canceled_at.unwrap_or(now);
+
+        if self.start_time < closed_at {
+            push(ProposalState::Active, self.start_time);
+        }
+
+        if self.end_time < closed_at {
+            if self.for_votes <= self.against_votes || self.for_votes < self.quorum_votes {
+                push(ProposalState::Failed, self.end_time);
+            } else {
+                push(ProposalState::Succeeded, self.end_time);
+
+                if self.execution_time != 0 {
+                    let queued_at = self.queued_at.map(i64::from).unwrap_or(self.end_time);
+                    push(ProposalState::Queued, queued_at);
+
+                    let expires_at = self.execution_time + self.grace_period;
+                    if let Some(executed_at) = self.executed_at.filter(|_| self.executed) {
+                        push(ProposalState::Executed, executed_at as i64);
+                    } else if expires_at < closed_at {
+                        push(ProposalState::Expired, expires_at);
+                    }
+                }
+            }
+        }
+
+        if let Some(canceled_at) = canceled_at {
+            push(ProposalState::Canceled, canceled_at);
+        }
+
+        history
+    }
+
+    /// Part of the state history which is not reported by the contracts
+    pub fn time_driven_transitions(&self, now: i64) -> Vec<ProposalStateChange> {
+        self.state_history(now)
+            .into_iter()
+            .filter(|x| x.state.is_time_driven())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use super::*;
+
+    fn proposal() -> ProposalFromDb {
+        ProposalFromDb {
+            id: 1,
+            address: String::new(),
+            proposer: String::new(),
+            description: String::new(),
+            start_time: 1000,
+            end_time: 2000,
+            execution_time: 0,
+            grace_period: 500,
+            time_lock: 100,
+            voting_delay: 0,
+            for_votes: Decimal::from(300),
+            against_votes: Decimal::from(100),
+            quorum_votes: Decimal::from(200),
+            message_hash: Vec::new(),
+            transaction_hash: Vec::new(),
+            timestamp_block: 900,
+            actions: serde_json::Value::Null,
+            executed: false,
+            canceled: false,
+            queued: false,
+            executed_at: None,
+            canceled_at: None,
+            queued_at: None,
+            updated_at: 0,
+            created_at: 0,
+            dao_root: String::new(),
+        }
+    }
+
+    fn states(history: Vec<ProposalStateChange>) -> Vec<ProposalState> {
+        history.into_iter().map(|x| x.state).collect()
+    }
+
+    #[test]
+    fn active_proposal_history() {
+        assert_eq!(
+            states(proposal().state_history(1500)),
+            [ProposalState::Pending, ProposalState::Active]
+        );
+    }
+
+    #[test]
+    fn executed_proposal_history() {
+        let proposal = ProposalFromDb {
+            execution_time: 2100,
+            queued: true,
+            queued_at: Some(2000),
+            executed: true,
+            executed_at: Some(2200),
+            ..proposal()
+        };
+
+        assert_eq!(
+            proposal.state_history(5000),
+            [
+                ProposalStateChange {
+                    state: ProposalState::Pending,
+                    timestamp: 900
+                },
+                ProposalStateChange {
+                    state: ProposalState::Active,
+                    timestamp: 1000
+                },
+                ProposalStateChange {
+                    state: ProposalState::Succeeded,
+                    timestamp: 2000
+                },
+                ProposalStateChange {
+                    state: ProposalState::Queued,
+                    timestamp: 2000
+                },
+                ProposalStateChange {
+                    state: ProposalState::Executed,
+                    timestamp: 2200
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn expired_proposal_history() {
+        let proposal = ProposalFromDb {
+            execution_time: 2100,
+            queued: true,
+            queued_at: Some(2000),
+            ..proposal()
+        };
+
+        assert_eq!(
+            states(proposal.state_history(5000)),
+            [
+                ProposalState::Pending,
+                ProposalState::Active,
+                ProposalState::Succeeded,
+                ProposalState::Queued,
+                ProposalState::Expired
+            ]
+        );
+    }
+
+    #[test]
+    fn canceled_proposal_history() {
+        let proposal = ProposalFromDb {
+            canceled: true,
+            canceled_at: Some(1200),
+            ..proposal()
+        };
+
+        assert_eq!(
+            states(proposal.state_history(5000)),
+            [
+                ProposalState::Pending,
+                ProposalState::Active,
+                ProposalState::Canceled
+            ]
+        );
+    }
+
+    #[test]
+    fn failed_proposal_history() {
+        let proposal = ProposalFromDb {
+            for_votes: Decimal::from(150),
+            ..proposal()
+        };
+
+        assert_eq!(
+            states(proposal.state_history(5000)),
+            [
+                ProposalState::Pending,
+                ProposalState::Active,
+                ProposalState::Failed
+            ]
+        );
+    }
+
+    #[test]
+    fn time_driven_transitions() {
+        let proposal = ProposalFromDb {
+            execution_time: 2100,
+            queued: true,
+            queued_at: Some(2000),
+            ..proposal()
+        };
+
+        assert_eq!(
+            proposal.time_driven_transitions(5000),
+            [
+                ProposalStateChange {
+                    state: ProposalState::Active,
+                    timestamp: 1000
+                },
+                ProposalStateChange {
+                    state: ProposalState::Succeeded,
+                    timestamp: 2000
+                },
+                ProposalStateChange {
+                    state: ProposalState::Expired,
+                    timestamp: 2600
+                },
+            ]
+        );
+        assert!(proposal.time_driven_transitions(900).is_empty());
+    }
+}
@@ -0,0 +1,88 @@
+use rust_decimal::Decimal;
+
+use crate::models::{ProposalTallyFromDb, TimeseriesInterval};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProposalTimeline {
+    pub points: Vec<ProposalTallyFromDb>,
+    pub quorum_votes: Decimal,
+    /// Block time of the vote which made for votes reach the quorum
+    pub quorum_reached_at: Option<i32>,
+}
+
+impl ProposalTimeline {
+    /// The quorum moment is taken from the exact tally even if the points are bucketed
+    pub fn new(
+        points: Vec<ProposalTallyFromDb>,
+        quorum_votes: Decimal,
+        interval: Option<TimeseriesInterval>,
+    ) -> Self {
+        let quorum_reached_at = points
+            .iter()
+            .find(|x| x.for_votes >= quorum_votes)
+            .map(|x| x.timestamp_block);
+
+        let points = match interval {
+            Some(interval) => bucket_tally(points, interval),
+            None => points,
+        };
+
+        Self {
+            points,
+            quorum_votes,
+            quorum_reached_at,
+        }
+    }
+}
+
+/// Keeps the last tally of each bucket, labelled with the bucket start
+fn bucket_tally(
+    points: Vec<ProposalTallyFromDb>,
+    interval: TimeseriesInterval,
+) -> Vec<ProposalTallyFromDb> {
+    let mut buckets: Vec<ProposalTallyFromDb> = Vec::with_capacity(points.len());
+    for mut point in points {
+        point.timestamp_block = interval.bucket_start(point.timestamp_block);
+        match buckets.last_mut() {
+            Some(last) if last.timestamp_block == point.timestamp_block => *last = point,
+            _ => buckets.push(point),
+        }
+    }
+    buckets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tally(timestamp_block: i32, for_votes: i64, voters_count: i64) -> ProposalTallyFromDb {
+        ProposalTallyFromDb {
+            timestamp_block,
+            for_votes: Decimal::from(for_votes),
+            against_votes: Decimal::ZERO,
+            voters_count,
+        }
+    }
+
+    #[test]
+    fn quorum_reached_at() {
+        let points = vec![tally(100, 100, 1), tally(200, 250, 2), tally(300, 400, 3)];
+
+        let timeline = ProposalTimeline::new(points.clone(), Decimal::from(250), None);
+        assert_eq!(timeline.points, points);
+        assert_eq!(timeline.quorum_reached_at, Some(200));
+
+        let timeline = ProposalTimeline::new(points, Decimal::from(500), None);
+        assert_eq!(timeline.quorum_reached_at, None);
+    }
+
+    #[test]
+    fn bucketed_points() {
+        let points = vec![tally(100, 100, 1), tally(200, 250, 2), tally(3700, 400, 3)];
+
+        let timeline =
+            ProposalTimeline::new(points, Decimal::from(250), Some(TimeseriesInterval::Hour));
+        assert_eq!(timeline.points, vec![tally(0, 250, 2), tally(3600, 400, 3)]);
+        assert_eq!(timeline.quorum_reached_at, Some(200));
+    }
+}
@@ -7,8 +7,23 @@ use crate::utils::*;
 
 pub type ProposalsSearch = Paginated<Ordered<ProposalFilters, ProposalsOrdering>>;
 
+impl ProposalsSearch {
+    /// Full-text matches are ordered by relevance unless an ordering or a cursor is given
+    pub fn is_ordered_by_rank(&self) -> bool {
+        self.data.filters.query.is_some() && self.data.ordering.is_none() && self.cursor.is_none()
+    }
+
+    /// Cursors only follow the creation order
+    pub fn supports_cursor(&self) -> bool {
+        !self.is_ordered_by_rank()
+            && self.data.ordering.unwrap_or_default().column == ProposalColumn::CreatedAt
+    }
+}
+
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
 pub struct ProposalFilters {
+    pub dao_root: Option<String>,
+
     pub start_time_ge: Option<i32>,
     pub start_time_le: Option<i32>,
 
@@ -22,10 +37,13 @@ pub struct ProposalFilters {
     pub proposal_address: Option<String>,
 
     pub state: Option<ProposalState>,
+
+    pub query: Option<String>,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
 pub struct CreateProposal {
+    pub dao_root: String,
     pub id: i32,
     pub address: String,
     pub proposer: String,
@@ -126,4 +144,9 @@ impl Default for ProposalsOrdering {
 #[opg("Proposal column")]
 pub enum ProposalColumn {
     CreatedAt,
+    EndTime,
+    TotalVotes,
+    ForVotes,
+    AgainstVotes,
+    QuorumProgress,
 }
@@ -0,0 +1,36 @@
+use crate::models::*;
+use crate::utils::*;
+
+pub type RawTransactionsSearch = Paginated<RawTransactionFilters>;
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct RawTransactionFilters {
+    pub timestamp_block_ge: Option<i32>,
+    pub timestamp_block_le: Option<i32>,
+
+    pub timestamp_lt_ge: Option<i64>,
+    pub timestamp_lt_le: Option<i64>,
+}
+
+impl RawTransactionFilters {
+    pub fn is_unbounded(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+pub type FailedRawTransactionsSearch = Paginated<FailedRawTransactionFilters>;
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct FailedRawTransactionFilters {
+    /// Both `Fail` and `DeadLetter` transactions if not set
+    pub state: Option<RawTransactionState>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct RetryPolicy {
+    /// Failed transaction becomes `DeadLetter` after this many attempts
+    pub max_attempts: i32,
+    /// Delay before the first retry, doubled on each next attempt
+    pub base_delay_ms: i64,
+    pub max_delay_ms: i64,
+}
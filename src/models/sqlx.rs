@@ -3,13 +3,32 @@ use std::convert::TryFrom;
 use rust_decimal::Decimal;
 use ton_block::{GetRepresentationHash, Serializable, Transaction};
 
-#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, sqlx::Type)]
+use crate::models::{
+    DaoConfigEventKind, GovernanceAuditEventKind, GovernanceEventKind, KnownContractKind,
+    PendingActionKind, WebhookDeliveryState,
+};
+
+#[derive(
+    Debug,
+    serde::Deserialize,
+    serde::Serialize,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Hash,
+    sqlx::Type,
+    opg::OpgModel,
+)]
 #[sqlx(type_name = "raw_transaction_state_type", rename_all = "PascalCase")]
+#[opg("Raw transaction state")]
 pub enum RawTransactionState {
     Idle,
     Fail,
     Success,
     InProgress,
+    /// Failed too many times and is not retried anymore
+    DeadLetter,
 }
 
 impl Default for RawTransactionState {
@@ -28,6 +47,19 @@ pub struct RawTransactionFromDb {
     pub state: RawTransactionState,
 }
 
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct FailedRawTransactionFromDb {
+    pub transaction: Vec<u8>,
+    pub transaction_hash: Vec<u8>,
+    pub timestamp_block: i32,
+    pub timestamp_lt: i64,
+    pub created_at: i64,
+    pub state: RawTransactionState,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_retry_at: Option<i64>,
+}
+
 impl TryFrom<Transaction> for RawTransactionFromDb {
     type Error = anyhow::Error;
 
@@ -72,6 +104,7 @@ pub struct ProposalFromDb {
     pub queued_at: Option<i32>,
     pub updated_at: i64,
     pub created_at: i64,
+    pub dao_root: String,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
@@ -86,4 +119,159 @@ pub struct VoteFromDb {
     pub transaction_hash: Vec<u8>,
     pub timestamp_block: i32,
     pub created_at: i64,
+    pub dao_root: String,
+}
+
+/// Relevance of a full-text search hit
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct SearchMatchFromDb {
+    pub rank: f32,
+    pub snippet: String,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct DaoConfigFromDb {
+    pub id: i64,
+    pub kind: DaoConfigEventKind,
+    pub voting_delay: Option<i64>,
+    pub voting_period: Option<i64>,
+    pub quorum_votes: Option<Decimal>,
+    pub time_lock: Option<i64>,
+    pub threshold: Option<Decimal>,
+    pub grace_period: Option<i64>,
+    pub message_hash: Vec<u8>,
+    pub transaction_hash: Vec<u8>,
+    pub timestamp_block: i32,
+    pub created_at: i64,
+    pub dao_root: String,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct AuditEventFromDb {
+    pub id: i64,
+    pub kind: GovernanceAuditEventKind,
+    pub contract_address: String,
+    pub data: serde_json::Value,
+    pub message_hash: Vec<u8>,
+    pub transaction_hash: Vec<u8>,
+    pub timestamp_block: i32,
+    pub created_at: i64,
+    pub dao_root: String,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Default)]
+pub struct GovernanceEventFromDb {
+    pub id: i64,
+    pub kind: GovernanceEventKind,
+    pub dao_root: String,
+    pub proposal_id: i32,
+    pub voter: Option<String>,
+    pub data: serde_json::Value,
+    pub timestamp_block: i32,
+    pub created_at: i64,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Default)]
+pub struct WebhookFromDb {
+    pub id: i64,
+    pub url: String,
+    pub secret: String,
+    pub dao_root: Option<String>,
+    pub kinds: Vec<GovernanceEventKind>,
+    pub min_vote_weight: Option<Decimal>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Default)]
+pub struct WebhookDeliveryFromDb {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event_id: i64,
+    pub payload: serde_json::Value,
+    pub state: WebhookDeliveryState,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub response_status: Option<i32>,
+    pub next_attempt_at: i64,
+    pub created_at: i64,
+    pub updated_at: Option<i64>,
+}
+
+/// Delivery due to be sent together with its webhook endpoint
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Default)]
+pub struct DueWebhookDeliveryFromDb {
+    pub id: i64,
+    pub event_id: i64,
+    pub url: String,
+    pub secret: String,
+    pub payload: serde_json::Value,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct PendingActionFromDb {
+    pub id: i64,
+    pub kind: PendingActionKind,
+    pub proposal_address: Option<String>,
+    pub dao_root: Option<String>,
+    pub proposal_id: Option<i32>,
+    pub voter: Option<String>,
+    pub execution_time: Option<i64>,
+    pub timestamp_block: i32,
+    pub created_at: i64,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct KnownContractFromDb {
+    pub address: String,
+    pub kind: KnownContractKind,
+    pub dao_root: String,
+    pub owner: Option<String>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct IndexerCheckpointFromDb {
+    pub source: String,
+    pub timestamp_lt: i64,
+    pub timestamp_block: i32,
+    pub updated_at: i64,
+}
+
+/// Governance statistics combined over the selected DAO roots
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Default)]
+pub struct DaoStatsFromDb {
+    pub proposals_count: i64,
+    pub pending_count: i64,
+    pub active_count: i64,
+    pub canceled_count: i64,
+    pub failed_count: i64,
+    pub succeeded_count: i64,
+    pub expired_count: i64,
+    pub queued_count: i64,
+    pub executed_count: i64,
+    pub voters_count: i64,
+    pub votes_count: i64,
+    pub average_participation: Decimal,
+    pub average_for_ratio: Decimal,
+    pub locked_votes: Decimal,
+    pub refreshed_at: Option<i64>,
+}
+
+/// Cumulative tally of a proposal after the votes of one block time
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Default, PartialEq)]
+pub struct ProposalTallyFromDb {
+    pub timestamp_block: i32,
+    pub for_votes: Decimal,
+    pub against_votes: Decimal,
+    pub voters_count: i64,
+}
+
+/// Voting activity within one timeseries bucket
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Default)]
+pub struct TimeseriesPointFromDb {
+    pub bucket: i32,
+    pub votes_count: i64,
+    pub proposals_count: i64,
+    pub voters_count: i64,
+    pub vote_weight: Decimal,
 }
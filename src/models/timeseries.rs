@@ -0,0 +1,125 @@
+use serde::Deserialize;
+
+/// Upper bound of points in one timeseries response
+pub const MAX_TIMESERIES_BUCKETS: i32 = 1000;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TimeseriesFilters {
+    pub interval: TimeseriesInterval,
+    pub from: i32,
+    pub to: i32,
+    pub dao_root: Option<String>,
+    pub proposal_id: Option<i32>,
+    pub voter: Option<String>,
+}
+
+impl TimeseriesFilters {
+    /// Starts of the first and the last buckets covering `[from, to)`
+    pub fn bucket_range(&self) -> Result<(i32, i32), anyhow::Error> {
+        if self.to <= self.from {
+            anyhow::bail!("`to` must be greater than `from`");
+        }
+
+        let first = self.interval.bucket_start(self.from);
+        let last = self.interval.bucket_start(self.to - 1);
+        let count = (last - first) / self.interval.size() + 1;
+        if count > MAX_TIMESERIES_BUCKETS {
+            anyhow::bail!(
+                "Too many buckets: {}, at most {} are allowed",
+                count,
+                MAX_TIMESERIES_BUCKETS
+            );
+        }
+
+        Ok((first, last))
+    }
+
+    /// Day buckets of a single DAO root can be read from the daily aggregates
+    pub fn is_daily_aggregate(&self) -> bool {
+        self.interval == TimeseriesInterval::Day
+            && self.dao_root.is_some()
+            && self.proposal_id.is_none()
+            && self.voter.is_none()
+    }
+}
+
+#[derive(Debug, Copy, Clone, Deserialize, Eq, PartialEq, Hash, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Timeseries interval")]
+pub enum TimeseriesInterval {
+    Hour,
+    Day,
+    Week,
+}
+
+impl TimeseriesInterval {
+    /// Bucket length in seconds
+    pub fn size(self) -> i32 {
+        match self {
+            Self::Hour => 3600,
+            Self::Day => 86400,
+            Self::Week => 7 * 86400,
+        }
+    }
+
+    /// Shift of the bucket start from the unix epoch, weeks start on Monday
+    pub fn offset(self) -> i32 {
+        match self {
+            Self::Hour | Self::Day => 0,
+            // 1970-01-05 is the first Monday
+            Self::Week => 4 * 86400,
+        }
+    }
+
+    /// Start of the bucket containing `timestamp`
+    pub fn bucket_start(self, timestamp: i32) -> i32 {
+        timestamp - (timestamp - self.offset()).rem_euclid(self.size())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_start() {
+        // 2022-01-12 15:30:00 UTC, Wednesday
+        let timestamp = 1642001400;
+
+        assert_eq!(TimeseriesInterval::Hour.bucket_start(timestamp), 1641999600);
+        assert_eq!(TimeseriesInterval::Day.bucket_start(timestamp), 1641945600);
+        // 2022-01-10 00:00:00 UTC, Monday
+        assert_eq!(TimeseriesInterval::Week.bucket_start(timestamp), 1641772800);
+    }
+
+    #[test]
+    fn bucket_range() {
+        let filters = |interval, from, to| TimeseriesFilters {
+            interval,
+            from,
+            to,
+            dao_root: None,
+            proposal_id: None,
+            voter: None,
+        };
+
+        assert_eq!(
+            filters(TimeseriesInterval::Day, 1641945600, 1642118400)
+                .bucket_range()
+                .unwrap(),
+            (1641945600, 1642032000)
+        );
+        assert_eq!(
+            filters(TimeseriesInterval::Hour, 1642001400, 1642001401)
+                .bucket_range()
+                .unwrap(),
+            (1641999600, 1641999600)
+        );
+        assert!(filters(TimeseriesInterval::Hour, 1642001400, 1642001400)
+            .bucket_range()
+            .is_err());
+        assert!(filters(TimeseriesInterval::Hour, 0, 1001 * 3600)
+            .bucket_range()
+            .is_err());
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use nekoton_utils::TrustMe;
+use once_cell::sync::Lazy;
+use ton_types::SliceData;
+
+use crate::indexer::*;
+
+/// Bundled ABIs used to decode TON action payloads
+static TON_ABI_REGISTRY: Lazy<TonAbiRegistry> =
+    Lazy::new(|| TonAbiRegistry::new(&[("DaoRoot", DAO_ROOT_ABI)]));
+
+/// TON action payload decoded against a known contract ABI
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Eq, PartialEq, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Decoded TON call")]
+pub struct DecodedTonCall {
+    pub contract: String,
+    pub method: String,
+    pub params: Vec<DecodedTonParam>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Eq, PartialEq, opg::OpgModel)]
+#[serde(rename_all = "camelCase")]
+#[opg("Decoded TON call param")]
+pub struct DecodedTonParam {
+    pub name: String,
+    pub param_type: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TonPayload {
+    Empty,
+    Known(DecodedTonCall),
+    UnknownFunction(u32),
+}
+
+/// Function ID of TON action payloads missing in the ABI registry
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UnknownTonFunction {
+    pub function_id: u32,
+    pub proposal_addresses: Vec<String>,
+}
+
+struct TonAbiRegistry {
+    functions: HashMap<u32, (&'static str, ton_abi::Function)>,
+}
+
+impl TonAbiRegistry {
+    fn new(contracts: &[(&'static str, &str)]) -> Self {
+        let mut functions = HashMap::new();
+        for (name, abi) in contracts {
+            let contract = ton_abi::Contract::load(*abi).trust_me();
+            for function in contract.functions.into_values() {
+                functions.insert(function.input_id, (*name, function));
+            }
+        }
+        Self { functions }
+    }
+}
+
+/// Decodes base64 encoded payload BOC by its function ID
+pub fn decode_ton_payload(payload: &str) -> Result<TonPayload, anyhow::Error> {
+    let bytes = base64::decode(payload)?;
+    let cell = ton_types::deserialize_tree_of_cells(&mut bytes.as_slice())
+        .map_err(|e| anyhow::anyhow!("Invalid payload BOC: {}", e))?;
+    let body = SliceData::from(cell);
+
+    if body.remaining_bits() < 32 {
+        return Ok(TonPayload::Empty);
+    }
+    let function_id = body
+        .clone()
+        .get_next_u32()
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let (contract, function) = match TON_ABI_REGISTRY.functions.get(&function_id) {
+        Some(function) => function,
+        None => return Ok(TonPayload::UnknownFunction(function_id)),
+    };
+
+    let tokens = function
+        .decode_input(body, true)
+        .map_err(|e| anyhow::anyhow!("{}", e))
+        .with_context(|| format!("Failed to decode {} input", function.name))?;
+
+    Ok(TonPayload::Known(DecodedTonCall {
+        contract: contract.to_string(),
+        method: function.name.clone(),
+        params: function
+            .inputs
+            .iter()
+            .zip(tokens)
+            .map(|(param, token)| DecodedTonParam {
+                name: token.name,
+                param_type: param.kind.to_string(),
+                value: token.value.to_string(),
+            })
+            .collect(),
+    }))
+}
+
+pub fn format_function_id(function_id: u32) -> String {
+    format!("0x{:08x}", function_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use nekoton_abi::*;
+    use ton_abi::TokenValue;
+    use ton_types::{BuilderData, IBitstring};
+
+    use super::*;
+
+    #[test]
+    fn decode_update_quorum_votes() {
+        let contract = ton_abi::Contract::load(DAO_ROOT_ABI).trust_me();
+        let function = contract.function("updateQuorumVotes").trust_me();
+
+        let mut body = BuilderData::new();
+        body.append_u32(function.input_id).trust_me();
+        let body = TokenValue::pack_values_into_chain(
+            &[1000u128.token_value().named("newQuorumVotes")],
+            vec![body],
+            &function.abi_version,
+        )
+        .trust_me()
+        .into_cell()
+        .trust_me();
+        let payload = base64::encode(ton_types::serialize_toc(&body).trust_me());
+
+        let call = match decode_ton_payload(&payload).unwrap() {
+            TonPayload::Known(call) => call,
+            payload => panic!("Unexpected payload {:?}", payload),
+        };
+        assert_eq!(call.contract, "DaoRoot");
+        assert_eq!(call.method, "updateQuorumVotes");
+        assert_eq!(call.params.len(), 1);
+        assert_eq!(call.params[0].name, "newQuorumVotes");
+        assert_eq!(call.params[0].value, "1000");
+    }
+
+    #[test]
+    fn unknown_function_is_reported() {
+        let payload =
+            decode_ton_payload("te6ccgEBAgEAGAABCAMFmxgBAB5SZW5hbWUgYWNjZXB0ZWQ=").unwrap();
+        assert_eq!(payload, TonPayload::UnknownFunction(0x03059b18));
+    }
+
+    #[test]
+    fn dao_root_functions_are_registered() {
+        let names = TON_ABI_REGISTRY
+            .functions
+            .values()
+            .map(|(_, function)| function.name.as_str())
+            .collect::<Vec<_>>();
+        assert!(names.contains(&"transferAdmin"));
+        assert!(names.contains(&"updateQuorumVotes"));
+    }
+}
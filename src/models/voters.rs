@@ -5,11 +5,20 @@ use crate::utils::*;
 
 pub type VotersSearch = Paginated<Ordered<VoterFilters, VotersOrdering>>;
 
+impl VotersSearch {
+    /// Cursors only follow the creation order
+    pub fn supports_cursor(&self) -> bool {
+        self.data.ordering.unwrap_or_default().column == VoterColumn::CreatedAt
+    }
+}
+
 pub type VotersProposalsCountSearch =
     Paginated<Ordered<VotersProposalsCountFilters, VotersProposalsOrdering>>;
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
 pub struct VoterFilters {
+    pub dao_root: Option<String>,
+
     pub start_time_ge: Option<i32>,
     pub start_time_le: Option<i32>,
 
@@ -52,10 +61,13 @@ impl Default for VotersOrdering {
 #[opg("Voter column")]
 pub enum VoterColumn {
     CreatedAt,
+    Votes,
+    EndTime,
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
 pub struct VotersProposalsCountFilters {
+    pub dao_root: Option<String>,
     pub voters: Option<Vec<String>>,
 }
 
@@ -6,16 +6,32 @@ use crate::utils::*;
 
 pub type VotesSearch = Paginated<Ordered<VoteFilters, VotesOrdering>>;
 
+impl VotesSearch {
+    /// Full-text matches are ordered by relevance unless an ordering or a cursor is given
+    pub fn is_ordered_by_rank(&self) -> bool {
+        self.data.filters.query.is_some() && self.data.ordering.is_none() && self.cursor.is_none()
+    }
+
+    /// Cursors only follow the creation order
+    pub fn supports_cursor(&self) -> bool {
+        !self.is_ordered_by_rank()
+            && self.data.ordering.unwrap_or_default().column == VoteColumn::CreatedAt
+    }
+}
+
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
 pub struct VoteFilters {
+    pub dao_root: Option<String>,
     pub voter: Option<String>,
     pub proposal_id: Option<i32>,
     pub support: Option<bool>,
     pub locked: Option<bool>,
+    pub query: Option<String>,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
 pub struct CreateVote {
+    pub dao_root: String,
     pub proposal_id: i32,
     pub voter: String,
     pub support: bool,
@@ -28,6 +44,7 @@ pub struct CreateVote {
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq, Eq, Hash)]
 pub struct UnlockVote {
+    pub dao_root: String,
     pub proposal_id: i32,
     pub voter: String,
 }
@@ -53,4 +70,5 @@ impl Default for VotesOrdering {
 #[opg("Vote column")]
 pub enum VoteColumn {
     CreatedAt,
+    Votes,
 }
@@ -0,0 +1,165 @@
+use std::net::IpAddr;
+
+use hmac::{Hmac, Mac};
+use rust_decimal::Decimal;
+use sha2::Sha256;
+
+use crate::models::*;
+use crate::utils::*;
+
+pub type WebhookDeliveriesSearch = Paginated<WebhookDeliveryFilters>;
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct WebhookDeliveryFilters {
+    pub webhook_id: Option<i64>,
+    pub state: Option<WebhookDeliveryState>,
+}
+
+#[derive(
+    Debug,
+    serde::Deserialize,
+    serde::Serialize,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Hash,
+    sqlx::Type,
+    opg::OpgModel,
+)]
+#[sqlx(type_name = "webhook_delivery_state_type", rename_all = "PascalCase")]
+#[opg("Webhook delivery state")]
+pub enum WebhookDeliveryState {
+    Pending,
+    Success,
+    Fail,
+    /// Failed too many times and is not retried anymore
+    DeadLetter,
+}
+
+impl Default for WebhookDeliveryState {
+    fn default() -> Self {
+        WebhookDeliveryState::Pending
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateWebhook {
+    pub url: String,
+    pub secret: String,
+    pub dao_root: Option<String>,
+    /// All event kinds if empty
+    pub kinds: Vec<GovernanceEventKind>,
+    /// Only votes with at least this weight if set
+    pub min_vote_weight: Option<Decimal>,
+}
+
+impl CreateWebhook {
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        let url = url::Url::parse(&self.url)?;
+        if !matches!(url.scheme(), "http" | "https") {
+            anyhow::bail!("Webhook url must be http or https");
+        }
+        let public_host = match url.host() {
+            Some(url::Host::Ipv4(ip)) => is_public_ip(ip.into()),
+            Some(url::Host::Ipv6(ip)) => is_public_ip(ip.into()),
+            Some(url::Host::Domain(domain)) => {
+                let domain = domain.trim_end_matches('.').to_lowercase();
+                domain != "localhost" && !domain.ends_with(".localhost")
+            }
+            None => false,
+        };
+        if !public_host {
+            anyhow::bail!("Webhook url must point to a public host");
+        }
+        if self.secret.is_empty() {
+            anyhow::bail!("Webhook secret must not be empty");
+        }
+        Ok(())
+    }
+}
+
+/// Webhooks must not reach loopback, private or link-local addresses of the server network
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                // shared address space
+                || (a == 100 && (64..128).contains(&b)))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4() {
+            Some(ipv4) if !ip.is_loopback() && !ip.is_unspecified() => is_public_ip(ipv4.into()),
+            _ => {
+                let first = ip.segments()[0];
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    // unique local
+                    || (first & 0xfe00) == 0xfc00
+                    // link-local
+                    || (first & 0xffc0) == 0xfe80)
+            }
+        },
+    }
+}
+
+/// Hex encoded HMAC-SHA256 of `{timestamp}.{body}`
+pub fn sign_webhook_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn webhook_signature() {
+        assert_eq!(
+            sign_webhook_payload("secret", 1640000000, br#"{"id":1}"#),
+            "f8662ea8deb847acf67d6542bb64673f5a1b74f200ecf55a1c86bcf35e4d9bc4"
+        );
+    }
+
+    #[test]
+    fn webhook_validation() {
+        let webhook = |url: &str, secret: &str| CreateWebhook {
+            url: url.to_owned(),
+            secret: secret.to_owned(),
+            dao_root: None,
+            kinds: Vec::new(),
+            min_vote_weight: None,
+        };
+
+        assert!(webhook("https://example.com/hook", "secret")
+            .validate()
+            .is_ok());
+        assert!(webhook("ftp://example.com/hook", "secret")
+            .validate()
+            .is_err());
+        assert!(webhook("not a url", "secret").validate().is_err());
+        assert!(webhook("https://example.com/hook", "").validate().is_err());
+
+        for url in [
+            "http://localhost/hook",
+            "http://127.0.0.1:8080/hook",
+            "http://10.0.0.1/hook",
+            "http://192.168.1.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[::ffff:10.0.0.1]/hook",
+        ] {
+            assert!(webhook(url, "secret").validate().is_err(), "{}", url);
+        }
+        assert!(webhook("http://8.8.8.8/hook", "secret").validate().is_ok());
+    }
+}
@@ -3,17 +3,17 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use futures::prelude::*;
+use nekoton_utils::TrustMe;
 use sqlx::postgres::PgPoolOptions;
-use transaction_buffer::models::{BufferedConsumerChannels, BufferedConsumerConfig};
-use transaction_buffer::start_parsing_and_get_channels;
 use transaction_consumer::{ConsumerOptions, TransactionConsumer};
 
 use crate::api::*;
 use crate::indexer::*;
-use crate::models::AllEvents;
+use crate::models::RawTransactionFilters;
 use crate::services::*;
 use crate::settings::*;
 use crate::sqlx_client::*;
+use crate::ton_contracts::*;
 
 pub async fn start_server() -> Result<()> {
     let config = Arc::new(get_config());
@@ -35,57 +35,77 @@ pub async fn start_server() -> Result<()> {
 
     let sqlx_client = SqlxClient::new(pool.clone());
 
-    // kafka connection
-    let (group_id, topic, states_rpc_endpoint, options) = get_kafka_settings(&config);
-    let transaction_consumer = TransactionConsumer::new(
-        &group_id,
-        &topic,
-        vec![states_rpc_endpoint],
-        None,
-        ConsumerOptions {
-            kafka_options: options
-                .iter()
-                .map(|(x, y)| (x.as_str(), y.as_str()))
-                .collect::<HashMap<_, _>>(),
-            skip_0_partition: true,
-        },
-    )
-    .await
-    .expect("Failed to get transaction producer");
-
-    let BufferedConsumerChannels {
+    let dao_roots = init_dao_roots(&config.dao_roots)?;
+    // rows indexed before multiple roots were supported belong to the first root
+    sqlx_client
+        .assign_legacy_dao_root(dao_roots[0].to_string())
+        .await?;
+
+    // kafka connection is not needed for a fully local run
+    let transaction_consumer = match (&config.replay_dir, &config.contract_fixtures) {
+        (Some(_), Some(_)) => None,
+        _ => Some(get_transaction_consumer(&config).await),
+    };
+
+    let state_provider = get_state_provider(&config, &sqlx_client, transaction_consumer.clone())?;
+
+    let transaction_source: Box<dyn TransactionSource> = match &config.replay_dir {
+        Some(dir) => Box::new(FileTransactionSource { dir: dir.clone() }),
+        None => Box::new(KafkaTransactionSource {
+            transaction_consumer: transaction_consumer.trust_me(),
+            pg_pool: pool,
+        }),
+    };
+
+    let source = transaction_source.name();
+    let TransactionSourceChannels {
         rx_parsed_events,
         tx_commit,
         notify_for_services,
-    } = start_parsing_and_get_channels(BufferedConsumerConfig {
-        transaction_consumer: transaction_consumer.clone(),
-        pg_pool: pool,
-        events_to_parse: AllEvents::new().get_all_events().any_extractable,
-        buff_size: 100_000,
-        commit_time_secs: 100,
-    });
+    } = transaction_source.start();
 
     {
         let sqlx_client = sqlx_client.clone();
-        let transaction_consumer = transaction_consumer.clone();
+        let state_provider = state_provider.clone();
         tokio::spawn(bridge_dao_indexer(
             sqlx_client,
-            transaction_consumer,
+            state_provider,
+            config.retry_policy(),
+            source,
             rx_parsed_events,
             tx_commit,
         ));
     }
 
+    tokio::spawn(dao_stats_refresher(sqlx_client.clone()));
+
+    tokio::spawn(proposal_state_scheduler(sqlx_client.clone()));
+
+    tokio::spawn(webhook_dispatcher(
+        sqlx_client.clone(),
+        config.webhook_retry_policy(),
+    ));
+
     {
         let sqlx_client = sqlx_client.clone();
-        tokio::spawn(fail_transaction_monitor(sqlx_client, transaction_consumer));
+        tokio::spawn(fail_transaction_monitor(
+            sqlx_client,
+            state_provider,
+            config.retry_policy(),
+        ));
     }
 
     log::debug!("start http server");
     let service = Arc::new(Services::new(sqlx_client.clone()));
 
+    {
+        let service = service.clone();
+        tokio::spawn(async move { service.governance_events_notifier().await });
+    }
+
     let prod_url = config.indexer_prod_url.clone();
     let test_url = config.indexer_test_url.clone();
+    let admin_token = config.admin_token.clone();
     notify_for_services.notified().await;
     log::info!("start http service");
     tokio::spawn(http_service(
@@ -94,11 +114,80 @@ pub async fn start_server() -> Result<()> {
         sqlx_client,
         prod_url,
         test_url,
+        admin_token,
     ));
 
     future::pending().await
 }
 
+/// Rebuilds indexed data from stored raw transactions without consuming kafka.
+/// The indexer must not be running meanwhile, a ranged reindex keeps data outside the range
+pub async fn start_reindex(filters: RawTransactionFilters) -> Result<()> {
+    let config = get_config();
+    stackdriver_logger::init_with_cargo!();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(config.db_pool_size)
+        .connect(&config.database_url)
+        .await
+        .expect("fail pg pool");
+
+    sqlx::migrate!().run(&pool).await?;
+
+    let sqlx_client = SqlxClient::new(pool);
+
+    let dao_roots = init_dao_roots(&config.dao_roots)?;
+    // replayed rows get the root, so legacy rows must have it too to be matched
+    sqlx_client
+        .assign_legacy_dao_root(dao_roots[0].to_string())
+        .await?;
+
+    // used only for contract state requests
+    let transaction_consumer = match &config.contract_fixtures {
+        Some(_) => None,
+        None => Some(get_transaction_consumer(&config).await),
+    };
+    let state_provider = get_state_provider(&config, &sqlx_client, transaction_consumer)?;
+
+    reindex(sqlx_client, state_provider, config.retry_policy(), filters).await
+}
+
+fn get_state_provider(
+    config: &Config,
+    sqlx_client: &SqlxClient,
+    transaction_consumer: Option<Arc<TransactionConsumer>>,
+) -> Result<Arc<dyn ContractStateProvider>> {
+    let state_provider: Arc<dyn ContractStateProvider> = match &config.contract_fixtures {
+        Some(path) => Arc::new(FixtureContractStateProvider::load(path)?),
+        None => transaction_consumer.trust_me(),
+    };
+
+    Ok(Arc::new(CachedContractStateProvider::new(
+        state_provider,
+        sqlx_client.clone(),
+    )))
+}
+
+async fn get_transaction_consumer(config: &Config) -> Arc<TransactionConsumer> {
+    // kafka connection
+    let (group_id, topic, states_rpc_endpoint, options) = get_kafka_settings(config);
+    TransactionConsumer::new(
+        &group_id,
+        &topic,
+        vec![states_rpc_endpoint],
+        None,
+        ConsumerOptions {
+            kafka_options: options
+                .iter()
+                .map(|(x, y)| (x.as_str(), y.as_str()))
+                .collect::<HashMap<_, _>>(),
+            skip_0_partition: true,
+        },
+    )
+    .await
+    .expect("Failed to get transaction producer")
+}
+
 fn get_config() -> Config {
     Config::new().unwrap_or_else(|e| panic!("Error parsing config: {}", e))
 }
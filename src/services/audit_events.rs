@@ -0,0 +1,17 @@
+use crate::models::*;
+use crate::services::*;
+
+impl Services {
+    pub async fn search_audit_events(
+        &self,
+        input: AuditEventsSearch,
+    ) -> Result<(impl Iterator<Item = AuditEventFromDb>, i64), anyhow::Error> {
+        let events = self.sqlx_client.search_audit_events(input.clone()).await?;
+        let total_count = self
+            .sqlx_client
+            .audit_events_total_count(input.data.filters)
+            .await?;
+
+        Ok((events, total_count))
+    }
+}
@@ -0,0 +1,27 @@
+use crate::models::*;
+use crate::services::*;
+
+impl Services {
+    pub async fn current_dao_config(
+        &self,
+        dao_root: Option<String>,
+    ) -> Result<Option<DaoConfigFromDb>, anyhow::Error> {
+        self.sqlx_client.get_current_dao_config(dao_root).await
+    }
+
+    pub async fn search_dao_config_history(
+        &self,
+        input: DaoConfigHistorySearch,
+    ) -> Result<(impl Iterator<Item = DaoConfigFromDb>, i64), anyhow::Error> {
+        let history = self
+            .sqlx_client
+            .search_dao_config_history(input.clone())
+            .await?;
+        let total_count = self
+            .sqlx_client
+            .dao_config_history_total_count(input.data.filters)
+            .await?;
+
+        Ok((history, total_count))
+    }
+}
@@ -0,0 +1,11 @@
+use crate::models::*;
+use crate::services::*;
+
+impl Services {
+    pub async fn dao_stats(
+        &self,
+        dao_root: Option<String>,
+    ) -> Result<DaoStatsFromDb, anyhow::Error> {
+        self.sqlx_client.get_dao_stats(dao_root).await
+    }
+}
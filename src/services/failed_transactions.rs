@@ -0,0 +1,38 @@
+use crate::models::*;
+use crate::services::*;
+
+impl Services {
+    pub async fn search_failed_transactions(
+        &self,
+        input: FailedRawTransactionsSearch,
+    ) -> Result<(impl Iterator<Item = FailedRawTransactionFromDb>, i64), anyhow::Error> {
+        let transactions = self
+            .sqlx_client
+            .search_failed_raw_transactions(input.clone())
+            .await?;
+        let total_count = self
+            .sqlx_client
+            .failed_raw_transactions_total_count(input.data)
+            .await?;
+
+        Ok((transactions, total_count))
+    }
+
+    pub async fn get_failed_transaction(
+        &self,
+        transaction_hash: &[u8],
+    ) -> Result<Option<FailedRawTransactionFromDb>, anyhow::Error> {
+        self.sqlx_client
+            .get_failed_raw_transaction(transaction_hash)
+            .await
+    }
+
+    pub async fn requeue_failed_transaction(
+        &self,
+        transaction_hash: &[u8],
+    ) -> Result<Option<FailedRawTransactionFromDb>, anyhow::Error> {
+        self.sqlx_client
+            .requeue_raw_transaction(transaction_hash)
+            .await
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use futures::{stream, Stream};
+use tokio::sync::broadcast;
+use tokio::time;
+
+use crate::models::*;
+use crate::services::*;
+use crate::sqlx_client::SqlxClient;
+
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+impl Services {
+    /// Wakes up event streams when the indexer commits new events
+    pub async fn governance_events_notifier(&self) {
+        log::info!("Start governance events notifier...");
+
+        loop {
+            let mut listener = match self.sqlx_client.listen_governance_events().await {
+                Ok(listener) => listener,
+                Err(err) => {
+                    log::error!("Failed to listen governance events: {}", err);
+                    time::sleep(RETRY_INTERVAL).await;
+                    continue;
+                }
+            };
+
+            // notifications are lost while disconnected
+            let _ = self.governance_events.send(());
+
+            while listener.recv().await.is_ok() {
+                let _ = self.governance_events.send(());
+            }
+
+            log::error!("Governance events listener disconnected");
+        }
+    }
+
+    /// Streams events after `last_event_id`, or only new events if it is not set
+    pub async fn governance_events_stream(
+        &self,
+        filters: GovernanceEventFilters,
+        last_event_id: Option<i64>,
+    ) -> Result<impl Stream<Item = GovernanceEventFromDb> + Send + 'static, anyhow::Error> {
+        // subscribed before reading the last id, so no event is missed in between
+        let notifications = self.governance_events.subscribe();
+
+        let last_id = match last_event_id {
+            Some(id) => id,
+            None => self.sqlx_client.last_governance_event_id().await?,
+        };
+
+        let state = GovernanceEventsStream {
+            sqlx_client: self.sqlx_client.clone(),
+            filters,
+            last_id,
+            pending: VecDeque::new(),
+            notifications,
+        };
+
+        Ok(stream::unfold(state, |mut state| async move {
+            state.next().await.map(|event| (event, state))
+        }))
+    }
+}
+
+struct GovernanceEventsStream {
+    sqlx_client: SqlxClient,
+    filters: GovernanceEventFilters,
+    last_id: i64,
+    pending: VecDeque<GovernanceEventFromDb>,
+    notifications: broadcast::Receiver<()>,
+}
+
+impl GovernanceEventsStream {
+    async fn next(&mut self) -> Option<GovernanceEventFromDb> {
+        const BATCH_SIZE: i32 = 100;
+
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                self.last_id = event.id;
+                return Some(event);
+            }
+
+            match self
+                .sqlx_client
+                .get_governance_events_after(self.filters.clone(), self.last_id, BATCH_SIZE)
+                .await
+            {
+                Ok(events) if !events.is_empty() => {
+                    self.pending.extend(events);
+                    continue;
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    log::error!("Failed to get governance events: {}", err);
+                    time::sleep(RETRY_INTERVAL).await;
+                    continue;
+                }
+            }
+
+            match self.notifications.recv().await {
+                Ok(()) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
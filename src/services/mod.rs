@@ -1,15 +1,32 @@
+use tokio::sync::broadcast;
+
 use crate::sqlx_client::SqlxClient;
 
+mod audit_events;
+mod dao_config;
+mod dao_stats;
+mod failed_transactions;
+mod governance_events;
+mod pending_actions;
 mod proposals;
+mod timeseries;
+mod ton_actions;
 mod voters;
 mod votes;
+mod webhooks;
 
 pub struct Services {
     sqlx_client: SqlxClient,
+    governance_events: broadcast::Sender<()>,
 }
 
 impl Services {
     pub fn new(sqlx_client: SqlxClient) -> Self {
-        Self { sqlx_client }
+        let (governance_events, _) = broadcast::channel(16);
+
+        Self {
+            sqlx_client,
+            governance_events,
+        }
     }
 }
@@ -0,0 +1,20 @@
+use crate::models::*;
+use crate::services::*;
+
+impl Services {
+    pub async fn search_pending_actions(
+        &self,
+        input: PendingActionsSearch,
+    ) -> Result<(impl Iterator<Item = PendingActionFromDb>, i64), anyhow::Error> {
+        let actions = self
+            .sqlx_client
+            .search_pending_actions(input.clone())
+            .await?;
+        let total_count = self
+            .sqlx_client
+            .pending_actions_total_count(input.data)
+            .await?;
+
+        Ok((actions, total_count))
+    }
+}
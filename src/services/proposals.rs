@@ -1,19 +1,106 @@
 use crate::models::*;
 use crate::services::*;
+use crate::utils::*;
 
 impl Services {
     pub async fn search_proposals(
         &self,
         input: ProposalsSearch,
-    ) -> Result<(impl Iterator<Item = ProposalFromDb>, i64), anyhow::Error> {
-        let proposals = self.sqlx_client.search_proposals(input.clone()).await?;
-        let total_count = self
+        with_total_count: bool,
+    ) -> Result<Page<(ProposalFromDb, Option<SearchMatchFromDb>)>, anyhow::Error> {
+        let items = self
             .sqlx_client
-            .proposals_total_count(input.data.filters)
+            .search_proposals(input.clone())
+            .await?
+            .collect::<Vec<_>>();
+
+        let next_cursor = match items.last() {
+            Some((proposal, _)) if input.supports_cursor() && input.is_full(items.len()) => {
+                Some(encode_cursor(&ProposalCursor::from(proposal)))
+            }
+            _ => None,
+        };
+
+        let total_count = if with_total_count {
+            Some(
+                self.sqlx_client
+                    .proposals_total_count(input.data.filters)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        Ok(Page {
+            items,
+            total_count,
+            next_cursor,
+        })
+    }
+
+    pub async fn get_proposal(
+        &self,
+        dao_root: Option<String>,
+        proposal_id: i32,
+    ) -> Result<Option<(ProposalFromDb, i64)>, anyhow::Error> {
+        match self.sqlx_client.get_proposal(dao_root, proposal_id).await? {
+            Some(proposal) => self.with_votes_count(proposal).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn get_proposal_by_address(
+        &self,
+        address: String,
+    ) -> Result<Option<(ProposalFromDb, i64)>, anyhow::Error> {
+        match self.sqlx_client.get_proposal_by_address(address).await? {
+            Some(proposal) => self.with_votes_count(proposal).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn get_proposal_timeline(
+        &self,
+        dao_root: Option<String>,
+        proposal_id: i32,
+        interval: Option<TimeseriesInterval>,
+    ) -> Result<Option<ProposalTimeline>, anyhow::Error> {
+        let proposal = match self.sqlx_client.get_proposal(dao_root, proposal_id).await? {
+            Some(proposal) => proposal,
+            None => return Ok(None),
+        };
+
+        let points = self
+            .sqlx_client
+            .proposal_tally_history(proposal.dao_root, proposal.id)
+            .await?;
+
+        Ok(Some(ProposalTimeline::new(
+            points,
+            proposal.quorum_votes,
+            interval,
+        )))
+    }
+
+    pub async fn get_proposals_by_ids(
+        &self,
+        ids: Vec<(String, i32)>,
+    ) -> Result<Vec<ProposalFromDb>, anyhow::Error> {
+        self.sqlx_client.get_proposals_by_ids(ids).await
+    }
+
+    async fn with_votes_count(
+        &self,
+        proposal: ProposalFromDb,
+    ) -> Result<(ProposalFromDb, i64), anyhow::Error> {
+        let votes_count = self
+            .sqlx_client
+            .proposal_votes_count(proposal.dao_root.clone(), proposal.id)
             .await?;
 
-        Ok((proposals, total_count))
+        Ok((proposal, votes_count))
     }
+
     pub async fn overview(&self) -> Result<ProposalsOverview, anyhow::Error> {
         let proposals_total_count = self
             .sqlx_client
@@ -0,0 +1,11 @@
+use crate::models::*;
+use crate::services::*;
+
+impl Services {
+    pub async fn timeseries(
+        &self,
+        filters: TimeseriesFilters,
+    ) -> Result<Vec<TimeseriesPointFromDb>, anyhow::Error> {
+        self.sqlx_client.get_timeseries(filters).await
+    }
+}
@@ -0,0 +1,34 @@
+use std::collections::BTreeMap;
+
+use crate::models::*;
+use crate::services::*;
+
+impl Services {
+    /// Function IDs of proposal TON actions which can't be decoded with bundled ABIs
+    pub async fn unknown_ton_functions(&self) -> Result<Vec<UnknownTonFunction>, anyhow::Error> {
+        let proposals = self.sqlx_client.get_proposals_with_ton_actions().await?;
+
+        let mut unknown: BTreeMap<u32, Vec<String>> = BTreeMap::new();
+        for (address, actions) in proposals {
+            let actions: ProposalActions = serde_json::from_value(actions)?;
+            for action in actions.ton_actions {
+                if let Ok(TonPayload::UnknownFunction(function_id)) =
+                    decode_ton_payload(&action.payload)
+                {
+                    let addresses = unknown.entry(function_id).or_default();
+                    if !addresses.contains(&address) {
+                        addresses.push(address.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(unknown
+            .into_iter()
+            .map(|(function_id, proposal_addresses)| UnknownTonFunction {
+                function_id,
+                proposal_addresses,
+            })
+            .collect())
+    }
+}
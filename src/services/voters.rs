@@ -7,24 +7,45 @@ impl Services {
         &self,
         address: String,
         input: VotersSearch,
-    ) -> Result<(impl Iterator<Item = (ProposalFromDb, VoteFromDb)>, i64), anyhow::Error> {
-        let proposals_with_votes = self
+        with_total_count: bool,
+    ) -> Result<Page<(ProposalFromDb, VoteFromDb)>, anyhow::Error> {
+        let items = self
             .sqlx_client
             .search_proposals_with_votes(address.clone(), input.clone())
-            .await?;
-        let total_count = self
-            .sqlx_client
-            .proposals_with_votes_total_count(address, input.data.filters)
-            .await?;
+            .await?
+            .collect::<Vec<_>>();
+
+        let next_cursor = match items.last() {
+            Some((_, vote)) if input.supports_cursor() && input.is_full(items.len()) => {
+                Some(encode_cursor(&VoteCursor::from(vote)))
+            }
+            _ => None,
+        };
+
+        let total_count = if with_total_count {
+            Some(
+                self.sqlx_client
+                    .proposals_with_votes_total_count(address, input.data.filters)
+                    .await?,
+            )
+        } else {
+            None
+        };
 
-        Ok((proposals_with_votes, total_count))
+        Ok(Page {
+            items,
+            total_count,
+            next_cursor,
+        })
     }
 
     pub async fn proposals_count(
         &self,
+        dao_root: Option<String>,
         voters: Vec<String>,
     ) -> Result<impl Iterator<Item = (String, i64)>, anyhow::Error> {
         let input: VotersProposalsCountSearch = VotersProposalsCountFilters {
+            dao_root,
             voters: Some(voters),
         }
         .ordered(None)
@@ -1,17 +1,47 @@
 use crate::models::*;
 use crate::services::*;
+use crate::utils::*;
 
 impl Services {
     pub async fn search_votes(
         &self,
         input: VotesSearch,
-    ) -> Result<(impl Iterator<Item = VoteFromDb>, i64), anyhow::Error> {
-        let votes = self.sqlx_client.search_votes(input.clone()).await?;
-        let total_count = self
+        with_total_count: bool,
+    ) -> Result<Page<(VoteFromDb, Option<SearchMatchFromDb>)>, anyhow::Error> {
+        let items = self
             .sqlx_client
-            .votes_total_count(input.data.filters)
-            .await?;
+            .search_votes(input.clone())
+            .await?
+            .collect::<Vec<_>>();
 
-        Ok((votes, total_count))
+        let next_cursor = match items.last() {
+            Some((vote, _)) if input.supports_cursor() && input.is_full(items.len()) => {
+                Some(encode_cursor(&VoteCursor::from(vote)))
+            }
+            _ => None,
+        };
+
+        let total_count = if with_total_count {
+            Some(
+                self.sqlx_client
+                    .votes_total_count(input.data.filters)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        Ok(Page {
+            items,
+            total_count,
+            next_cursor,
+        })
+    }
+
+    pub async fn search_votes_batch(
+        &self,
+        inputs: Vec<VotesSearch>,
+    ) -> Result<Vec<Vec<VoteFromDb>>, anyhow::Error> {
+        self.sqlx_client.search_votes_batch(inputs).await
     }
 }
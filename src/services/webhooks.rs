@@ -0,0 +1,36 @@
+use crate::models::*;
+use crate::services::*;
+
+impl Services {
+    pub async fn create_webhook(
+        &self,
+        webhook: CreateWebhook,
+    ) -> Result<WebhookFromDb, anyhow::Error> {
+        webhook.validate()?;
+        self.sqlx_client.create_webhook(webhook).await
+    }
+
+    pub async fn webhooks(&self) -> Result<Vec<WebhookFromDb>, anyhow::Error> {
+        self.sqlx_client.get_webhooks().await
+    }
+
+    pub async fn delete_webhook(&self, id: i64) -> Result<Option<WebhookFromDb>, anyhow::Error> {
+        self.sqlx_client.delete_webhook(id).await
+    }
+
+    pub async fn search_webhook_deliveries(
+        &self,
+        input: WebhookDeliveriesSearch,
+    ) -> Result<(impl Iterator<Item = WebhookDeliveryFromDb>, i64), anyhow::Error> {
+        let deliveries = self
+            .sqlx_client
+            .search_webhook_deliveries(input.clone())
+            .await?;
+        let total_count = self
+            .sqlx_client
+            .webhook_deliveries_total_count(input.data)
+            .await?;
+
+        Ok((deliveries, total_count))
+    }
+}
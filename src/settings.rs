@@ -1,7 +1,10 @@
 use std::net::SocketAddr;
+use std::path::PathBuf;
 
 use config::{Config as RawConfig, ConfigError, Environment};
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
+
+use crate::models::RetryPolicy;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Config {
@@ -17,7 +20,40 @@ pub struct Config {
     pub kafka_client_id: String,
 
     pub indexer_prod_url: String,
-    pub indexer_test_url: String
+    pub indexer_test_url: String,
+
+    /// Bearer token of the admin routes, they are disabled if not set
+    #[serde(default)]
+    pub admin_token: Option<String>,
+
+    /// Comma separated list of indexed DAO root addresses
+    #[serde(alias = "dao_root", deserialize_with = "deserialize_comma_separated")]
+    pub dao_roots: Vec<String>,
+
+    /// Directory with serialized transactions to index instead of kafka
+    #[serde(default)]
+    pub replay_dir: Option<PathBuf>,
+    /// JSON fixture with contract states to use instead of the states RPC
+    #[serde(default)]
+    pub contract_fixtures: Option<PathBuf>,
+
+    /// Failed transaction is moved to the dead letter state after this many attempts
+    #[serde(default = "default_max_retry_attempts")]
+    pub max_retry_attempts: i32,
+    /// Delay before the first retry of a failed transaction, doubled on each next attempt
+    #[serde(default = "default_retry_base_delay_sec")]
+    pub retry_base_delay_sec: i64,
+    #[serde(default = "default_retry_max_delay_sec")]
+    pub retry_max_delay_sec: i64,
+
+    /// Webhook delivery is moved to the dead letter state after this many attempts
+    #[serde(default = "default_webhook_max_attempts")]
+    pub webhook_max_attempts: i32,
+    /// Delay before the first retry of a failed delivery, doubled on each next attempt
+    #[serde(default = "default_webhook_retry_base_delay_sec")]
+    pub webhook_retry_base_delay_sec: i64,
+    #[serde(default = "default_webhook_retry_max_delay_sec")]
+    pub webhook_retry_max_delay_sec: i64,
 }
 
 impl Config {
@@ -27,4 +63,57 @@ impl Config {
 
         s.try_into()
     }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.max_retry_attempts,
+            base_delay_ms: self.retry_base_delay_sec * 1000,
+            max_delay_ms: self.retry_max_delay_sec * 1000,
+        }
+    }
+
+    pub fn webhook_retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.webhook_max_attempts,
+            base_delay_ms: self.webhook_retry_base_delay_sec * 1000,
+            max_delay_ms: self.webhook_retry_max_delay_sec * 1000,
+        }
+    }
+}
+
+fn default_max_retry_attempts() -> i32 {
+    10
+}
+
+fn default_retry_base_delay_sec() -> i64 {
+    60
+}
+
+fn default_retry_max_delay_sec() -> i64 {
+    86400
+}
+
+fn default_webhook_max_attempts() -> i32 {
+    8
+}
+
+fn default_webhook_retry_base_delay_sec() -> i64 {
+    10
+}
+
+fn default_webhook_retry_max_delay_sec() -> i64 {
+    3600
+}
+
+fn deserialize_comma_separated<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    Ok(value
+        .split(',')
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(ToOwned::to_owned)
+        .collect())
 }
@@ -0,0 +1,25 @@
+use anyhow::Result;
+use sqlx::{Postgres, Transaction};
+
+use crate::sqlx_client::*;
+
+impl SqlxClient {
+    /// Records the event in the ledger, returns `false` if it was already applied
+    pub async fn mark_event_applied(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        transaction_hash: &[u8],
+        message_hash: &[u8],
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            "INSERT INTO applied_events (transaction_hash, message_hash) VALUES ($1, $2) \
+            ON CONFLICT DO NOTHING",
+        )
+        .bind(transaction_hash)
+        .bind(message_hash)
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
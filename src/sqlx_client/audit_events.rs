@@ -0,0 +1,143 @@
+use anyhow::Result;
+use sqlx::{Postgres, Transaction};
+
+use crate::models::*;
+use crate::sqlx_client::*;
+use crate::utils::*;
+
+impl SqlxClient {
+    pub async fn create_audit_event(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        event: CreateAuditEvent,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"INSERT INTO governance_audit_events (
+            kind, contract_address, data, message_hash, transaction_hash, timestamp_block, dao_root)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT DO NOTHING"#,
+        )
+        .bind(event.kind)
+        .bind(event.contract_address)
+        .bind(event.data)
+        .bind(event.message_hash)
+        .bind(event.transaction_hash)
+        .bind(event.timestamp_block)
+        .bind(event.dao_root)
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn search_audit_events(
+        &self,
+        input: AuditEventsSearch,
+    ) -> Result<impl Iterator<Item = AuditEventFromDb> + Send + Sync> {
+        let mut query = OwnedPartBuilder::new().starts_with(
+            "SELECT \
+                id, kind, contract_address, data, message_hash, transaction_hash, timestamp_block, created_at, \
+                dao_root \
+            FROM governance_audit_events",
+        );
+
+        let mut args_len = 0;
+
+        query
+            .push_part(audit_event_filters(input.data.filters, &mut args_len))
+            .push(audit_events_ordering(input.data.ordering))
+            .push_with_arg(
+                {
+                    format!("LIMIT ${}", {
+                        args_len += 1;
+                        args_len
+                    })
+                },
+                max_limit(input.limit),
+            )
+            .push_with_arg(
+                {
+                    format!("OFFSET ${}", {
+                        args_len += 1;
+                        args_len
+                    })
+                },
+                input.offset,
+            );
+
+        let (query, args) = query.split();
+
+        let events = sqlx::query_with(&query, args).fetch_all(&self.pool).await?;
+
+        Ok(events
+            .into_iter()
+            .map(RowReader::from_row)
+            .map(|mut x| AuditEventFromDb {
+                id: x.read_next(),
+                kind: x.read_next(),
+                contract_address: x.read_next(),
+                data: x.read_next(),
+                message_hash: x.read_next(),
+                transaction_hash: x.read_next(),
+                timestamp_block: x.read_next(),
+                created_at: x.read_next(),
+                dao_root: x.read_next(),
+            }))
+    }
+
+    pub async fn audit_events_total_count(&self, input: AuditEventFilters) -> Result<i64> {
+        let mut args_len = 0;
+
+        let mut query =
+            OwnedPartBuilder::new().starts_with("SELECT COUNT(*) FROM governance_audit_events");
+
+        query.push_part(audit_event_filters(input, &mut args_len));
+
+        let (query, args) = query.split();
+
+        let total_count: i64 = sqlx::query_with(&query, args)
+            .fetch_one(&self.pool)
+            .await
+            .map(RowReader::from_row)
+            .map(|mut x| x.read_next())
+            .unwrap_or_default();
+
+        Ok(total_count)
+    }
+}
+
+fn audit_event_filters(filters: AuditEventFilters, args_len: &mut u32) -> impl QueryPart {
+    WhereAndConditions((
+        filters.dao_root.map(|dao_root| {
+            *args_len += 1;
+            (format!("dao_root = ${}", *args_len), dao_root)
+        }),
+        filters.kind.map(|kind| {
+            *args_len += 1;
+            (format!("kind = ${}", *args_len), kind)
+        }),
+        filters.contract_address.map(|address| {
+            *args_len += 1;
+            (format!("contract_address = ${}", *args_len), address)
+        }),
+        filters.timestamp_block_ge.map(|time| {
+            *args_len += 1;
+            (format!("timestamp_block >= ${}", *args_len), time)
+        }),
+        filters.timestamp_block_le.map(|time| {
+            *args_len += 1;
+            (format!("timestamp_block <= ${}", *args_len), time)
+        }),
+    ))
+}
+
+fn audit_events_ordering(ordering: Option<AuditEventsOrdering>) -> &'static str {
+    let AuditEventsOrdering { column, direction } = ordering.unwrap_or_default();
+
+    match (column, direction) {
+        (AuditEventColumn::CreatedAt, Direction::Ascending) => "ORDER BY timestamp_block, id",
+        (AuditEventColumn::CreatedAt, Direction::Descending) => {
+            "ORDER BY timestamp_block DESC, id DESC"
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use anyhow::Result;
+use rust_decimal::Decimal;
+use sqlx::{Postgres, Transaction};
+
+use crate::models::*;
+use crate::sqlx_client::*;
+use crate::utils::*;
+
+const DAO_CONFIG_COLUMNS: &str = "id, kind, voting_delay, voting_period, quorum_votes, time_lock, \
+    threshold, grace_period, message_hash, transaction_hash, timestamp_block, created_at, dao_root";
+
+impl SqlxClient {
+    /// The config in effect at the block time, `None` before the first indexed change
+    pub async fn get_dao_config_at(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        dao_root: &str,
+        timestamp_block: i32,
+    ) -> Result<Option<DaoConfigUpdate>> {
+        let config: Option<(
+            Option<i64>,
+            Option<i64>,
+            Option<Decimal>,
+            Option<i64>,
+            Option<Decimal>,
+            Option<i64>,
+        )> = sqlx::query_as(
+            "SELECT voting_delay, voting_period, quorum_votes, time_lock, threshold, grace_period \
+            FROM dao_config_history \
+            WHERE dao_root = $1 AND timestamp_block <= $2 \
+            ORDER BY timestamp_block DESC, id DESC \
+            LIMIT 1",
+        )
+        .bind(dao_root)
+        .bind(timestamp_block)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        Ok(config.map(
+            |(voting_delay, voting_period, quorum_votes, time_lock, threshold, grace_period)| {
+                DaoConfigUpdate {
+                    voting_delay,
+                    voting_period,
+                    quorum_votes,
+                    time_lock,
+                    threshold,
+                    grace_period,
+                }
+            },
+        ))
+    }
+
+    /// Stores the config as is, untouched parameters must be merged by the caller
+    pub async fn create_dao_config(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        config: CreateDaoConfig,
+    ) -> Result<()> {
+        let values = config.config;
+
+        sqlx::query(
+            r#"INSERT INTO dao_config_history (
+            kind, voting_delay, voting_period, quorum_votes, time_lock, threshold, grace_period,
+            message_hash, transaction_hash, timestamp_block, dao_root)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            ON CONFLICT DO NOTHING"#,
+        )
+        .bind(config.kind)
+        .bind(values.voting_delay)
+        .bind(values.voting_period)
+        .bind(values.quorum_votes)
+        .bind(values.time_lock)
+        .bind(values.threshold)
+        .bind(values.grace_period)
+        .bind(config.message_hash)
+        .bind(config.transaction_hash)
+        .bind(config.timestamp_block)
+        .bind(config.dao_root)
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Without `dao_root` only a single indexed DAO is allowed, configs of different DAOs
+    /// are not comparable
+    pub async fn get_current_dao_config(
+        &self,
+        dao_root: Option<String>,
+    ) -> Result<Option<DaoConfigFromDb>> {
+        let mut query = OwnedPartBuilder::new().starts_with(format!(
+            "SELECT {} FROM dao_config_history",
+            DAO_CONFIG_COLUMNS
+        ));
+
+        if let Some(dao_root) = &dao_root {
+            query.push_with_arg("WHERE dao_root = $1", dao_root.clone());
+        }
+        query.push("ORDER BY timestamp_block DESC, id DESC LIMIT 1");
+
+        let (query, args) = query.split();
+
+        let config = sqlx::query_with(&query, args)
+            .fetch_optional(&self.pool)
+            .await?
+            .map(RowReader::from_row)
+            .map(read_dao_config);
+
+        if let (None, Some(config)) = (&dao_root, &config) {
+            let other_root: Option<(String,)> = sqlx::query_as(
+                "SELECT dao_root FROM dao_config_history WHERE dao_root <> $1 LIMIT 1",
+            )
+            .bind(&config.dao_root)
+            .fetch_optional(&self.pool)
+            .await?;
+
+            if other_root.is_some() {
+                anyhow::bail!("daoRoot is required when several DAOs are indexed");
+            }
+        }
+
+        Ok(config)
+    }
+
+    pub async fn search_dao_config_history(
+        &self,
+        input: DaoConfigHistorySearch,
+    ) -> Result<impl Iterator<Item = DaoConfigFromDb> + Send + Sync> {
+        let mut query = OwnedPartBuilder::new().starts_with(format!(
+            "SELECT {} FROM dao_config_history",
+            DAO_CONFIG_COLUMNS
+        ));
+
+        let mut args_len = 0;
+
+        query
+            .push_part(dao_config_filters(input.data.filters, &mut args_len))
+            .push(dao_config_ordering(input.data.ordering))
+            .push_with_arg(
+                {
+                    format!("LIMIT ${}", {
+                        args_len += 1;
+                        args_len
+                    })
+                },
+                max_limit(input.limit),
+            )
+            .push_with_arg(
+                {
+                    format!("OFFSET ${}", {
+                        args_len += 1;
+                        args_len
+                    })
+                },
+                input.offset,
+            );
+
+        let (query, args) = query.split();
+
+        let history = sqlx::query_with(&query, args).fetch_all(&self.pool).await?;
+
+        Ok(history
+            .into_iter()
+            .map(RowReader::from_row)
+            .map(read_dao_config))
+    }
+
+    pub async fn dao_config_history_total_count(&self, input: DaoConfigFilters) -> Result<i64> {
+        let mut args_len = 0;
+
+        let mut query =
+            OwnedPartBuilder::new().starts_with("SELECT COUNT(*) FROM dao_config_history");
+
+        query.push_part(dao_config_filters(input, &mut args_len));
+
+        let (query, args) = query.split();
+
+        let total_count: i64 = sqlx::query_with(&query, args)
+            .fetch_one(&self.pool)
+            .await
+            .map(RowReader::from_row)
+            .map(|mut x| x.read_next())
+            .unwrap_or_default();
+
+        Ok(total_count)
+    }
+}
+
+fn read_dao_config(mut x: RowReader<sqlx::Postgres>) -> DaoConfigFromDb {
+    DaoConfigFromDb {
+        id: x.read_next(),
+        kind: x.read_next(),
+        voting_delay: x.read_next(),
+        voting_period: x.read_next(),
+        quorum_votes: x.read_next(),
+        time_lock: x.read_next(),
+        threshold: x.read_next(),
+        grace_period: x.read_next(),
+        message_hash: x.read_next(),
+        transaction_hash: x.read_next(),
+        timestamp_block: x.read_next(),
+        created_at: x.read_next(),
+        dao_root: x.read_next(),
+    }
+}
+
+fn dao_config_filters(filters: DaoConfigFilters, args_len: &mut u32) -> impl QueryPart {
+    WhereAndConditions((
+        filters.dao_root.map(|dao_root| {
+            *args_len += 1;
+            (format!("dao_root = ${}", *args_len), dao_root)
+        }),
+        filters.kind.map(|kind| {
+            *args_len += 1;
+            (format!("kind = ${}", *args_len), kind)
+        }),
+        filters.timestamp_block_ge.map(|time| {
+            *args_len += 1;
+            (format!("timestamp_block >= ${}", *args_len), time)
+        }),
+        filters.timestamp_block_le.map(|time| {
+            *args_len += 1;
+            (format!("timestamp_block <= ${}", *args_len), time)
+        }),
+    ))
+}
+
+fn dao_config_ordering(ordering: Option<DaoConfigOrdering>) -> &'static str {
+    let DaoConfigOrdering { column, direction } = ordering.unwrap_or_default();
+
+    match (column, direction) {
+        (DaoConfigColumn::CreatedAt, Direction::Ascending) => "ORDER BY timestamp_block, id",
+        (DaoConfigColumn::CreatedAt, Direction::Descending) => {
+            "ORDER BY timestamp_block DESC, id DESC"
+        }
+    }
+}
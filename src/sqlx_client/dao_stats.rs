@@ -0,0 +1,78 @@
+use anyhow::Result;
+
+use crate::models::*;
+use crate::sqlx_client::*;
+use crate::utils::*;
+
+impl SqlxClient {
+    pub async fn refresh_dao_stats(&self) -> Result<()> {
+        sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY dao_stats")
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY dao_voters_stats")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Without `dao_root` the stats of all DAO roots are combined, voters
+    /// are then counted once across all roots
+    pub async fn get_dao_stats(&self, dao_root: Option<String>) -> Result<DaoStatsFromDb> {
+        let voters_count = match dao_root {
+            Some(_) => "COALESCE(SUM(voters_count), 0)::BIGINT",
+            None => "(SELECT voters_count FROM dao_voters_stats)",
+        };
+
+        let mut query = OwnedPartBuilder::new().starts_with(format!(
+            "SELECT \
+                COALESCE(SUM(proposals_count), 0)::BIGINT, \
+                COALESCE(SUM(pending_count), 0)::BIGINT, \
+                COALESCE(SUM(active_count), 0)::BIGINT, \
+                COALESCE(SUM(canceled_count), 0)::BIGINT, \
+                COALESCE(SUM(failed_count), 0)::BIGINT, \
+                COALESCE(SUM(succeeded_count), 0)::BIGINT, \
+                COALESCE(SUM(expired_count), 0)::BIGINT, \
+                COALESCE(SUM(queued_count), 0)::BIGINT, \
+                COALESCE(SUM(executed_count), 0)::BIGINT, \
+                {}, \
+                COALESCE(SUM(votes_count), 0)::BIGINT, \
+                ROUND(COALESCE(SUM(votes_count)::NUMERIC / NULLIF(SUM(proposals_count), 0), 0), 4), \
+                ROUND(COALESCE(SUM(for_ratio_sum) / NULLIF(SUM(voted_proposals_count), 0), 0), 4), \
+                COALESCE(SUM(locked_votes), 0), \
+                MIN(refreshed_at) \
+            FROM dao_stats",
+            voters_count
+        ));
+
+        if let Some(dao_root) = dao_root {
+            query.push_with_arg("WHERE dao_root = $1", dao_root);
+        }
+
+        let (query, args) = query.split();
+
+        let stats = sqlx::query_with(&query, args)
+            .fetch_one(&self.pool)
+            .await
+            .map(RowReader::from_row)
+            .map(|mut x| DaoStatsFromDb {
+                proposals_count: x.read_next(),
+                pending_count: x.read_next(),
+                active_count: x.read_next(),
+                canceled_count: x.read_next(),
+                failed_count: x.read_next(),
+                succeeded_count: x.read_next(),
+                expired_count: x.read_next(),
+                queued_count: x.read_next(),
+                executed_count: x.read_next(),
+                voters_count: x.read_next(),
+                votes_count: x.read_next(),
+                average_participation: x.read_next(),
+                average_for_ratio: x.read_next(),
+                locked_votes: x.read_next(),
+                refreshed_at: x.read_next(),
+            })?;
+
+        Ok(stats)
+    }
+}
@@ -0,0 +1,172 @@
+use anyhow::Result;
+use sqlx::postgres::PgListener;
+use sqlx::{Postgres, Transaction};
+
+use crate::models::*;
+use crate::sqlx_client::*;
+use crate::utils::*;
+
+/// Notified with the event id when a governance event is committed
+const GOVERNANCE_EVENTS_CHANNEL: &str = "governance_events";
+
+/// Advisory lock held by publishers of governance events until commit
+const GOVERNANCE_EVENTS_LOCK: i64 = 0x676f765f6576;
+
+impl SqlxClient {
+    /// The event is staged until `publish_governance_events`
+    pub async fn create_governance_event(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        event: CreateGovernanceEvent,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"INSERT INTO staged_governance_events (kind, dao_root, proposal_id, voter, data, timestamp_block)
+            VALUES ($1, $2, $3, $4, $5, $6)"#,
+        )
+        .bind(event.kind)
+        .bind(event.dao_root)
+        .bind(event.proposal_id)
+        .bind(event.voter)
+        .bind(event.data)
+        .bind(event.timestamp_block)
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Moves the staged events to the log, must be called right before the commit
+    pub async fn publish_governance_events(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+    ) -> Result<()> {
+        let (staged,): (bool,) =
+            sqlx::query_as("SELECT EXISTS (SELECT 1 FROM staged_governance_events)")
+                .fetch_one(&mut *tx)
+                .await?;
+        if !staged {
+            return Ok(());
+        }
+
+        // ids are committed in order, otherwise a stream could read a later id
+        // before an earlier one is committed and skip it when resumed
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(GOVERNANCE_EVENTS_LOCK)
+            .execute(&mut *tx)
+            .await?;
+
+        let mut ids: Vec<(i64,)> = sqlx::query_as(
+            r#"WITH staged AS (DELETE FROM staged_governance_events RETURNING *)
+            INSERT INTO governance_events (kind, dao_root, proposal_id, voter, data, timestamp_block)
+            SELECT kind, dao_root, proposal_id, voter, data, timestamp_block FROM staged ORDER BY id
+            RETURNING id"#,
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        ids.sort_unstable();
+
+        for (id,) in ids {
+            if !self.replaying {
+                self.enqueue_webhook_deliveries(tx, id).await?;
+            }
+
+            // delivered on commit
+            sqlx::query("SELECT pg_notify($1, $2)")
+                .bind(GOVERNANCE_EVENTS_CHANNEL)
+                .bind(id.to_string())
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Events after `id` in the order they were applied
+    pub async fn get_governance_events_after(
+        &self,
+        filters: GovernanceEventFilters,
+        id: i64,
+        limit: i32,
+    ) -> Result<Vec<GovernanceEventFromDb>> {
+        let mut args_len = 0;
+
+        let mut query = OwnedPartBuilder::new().starts_with(
+            "SELECT id, kind, dao_root, proposal_id, voter, data, timestamp_block, created_at \
+            FROM governance_events",
+        );
+
+        query
+            .push_part(governance_event_filters(filters, id, &mut args_len))
+            .push("ORDER BY id")
+            .push_with_arg(
+                {
+                    format!("LIMIT ${}", {
+                        args_len += 1;
+                        args_len
+                    })
+                },
+                max_limit(limit),
+            );
+
+        let (query, args) = query.split();
+
+        let events = sqlx::query_with(&query, args)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(RowReader::from_row)
+            .map(|mut x| GovernanceEventFromDb {
+                id: x.read_next(),
+                kind: x.read_next(),
+                dao_root: x.read_next(),
+                proposal_id: x.read_next(),
+                voter: x.read_next(),
+                data: x.read_next(),
+                timestamp_block: x.read_next(),
+                created_at: x.read_next(),
+            })
+            .collect();
+
+        Ok(events)
+    }
+
+    pub async fn last_governance_event_id(&self) -> Result<i64> {
+        sqlx::query_as::<_, (i64,)>("SELECT COALESCE(MAX(id), 0) FROM governance_events")
+            .fetch_one(&self.pool)
+            .await
+            .map(|x| x.0)
+            .map_err(From::from)
+    }
+
+    /// Dedicated connection receiving ids of committed governance events
+    pub async fn listen_governance_events(&self) -> Result<PgListener> {
+        let mut listener = PgListener::connect_with(&self.pool).await?;
+        listener.listen(GOVERNANCE_EVENTS_CHANNEL).await?;
+        Ok(listener)
+    }
+}
+
+fn governance_event_filters(
+    filters: GovernanceEventFilters,
+    id: i64,
+    args_len: &mut u32,
+) -> impl QueryPart {
+    WhereAndConditions((
+        Some({
+            *args_len += 1;
+            (format!("id > ${}", *args_len), id)
+        }),
+        filters.dao_root.map(|dao_root| {
+            *args_len += 1;
+            (format!("dao_root = ${}", *args_len), dao_root)
+        }),
+        filters.proposal_id.map(|proposal_id| {
+            *args_len += 1;
+            (format!("proposal_id = ${}", *args_len), proposal_id)
+        }),
+        filters.voter.map(|voter| {
+            *args_len += 1;
+            (format!("voter = ${}", *args_len), voter)
+        }),
+    ))
+}
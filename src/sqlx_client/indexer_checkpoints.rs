@@ -0,0 +1,59 @@
+use anyhow::Result;
+use chrono::Utc;
+
+use crate::models::*;
+use crate::sqlx_client::*;
+
+impl SqlxClient {
+    pub async fn get_indexer_checkpoint(
+        &self,
+        source: &str,
+    ) -> Result<Option<IndexerCheckpointFromDb>> {
+        let checkpoint: Option<(String, i64, i32, i64)> = sqlx::query_as(
+            "SELECT source, timestamp_lt, timestamp_block, updated_at \
+            FROM indexer_checkpoints \
+            WHERE source = $1",
+        )
+        .bind(source)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(
+            checkpoint.map(|(source, timestamp_lt, timestamp_block, updated_at)| {
+                IndexerCheckpointFromDb {
+                    source,
+                    timestamp_lt,
+                    timestamp_block,
+                    updated_at,
+                }
+            }),
+        )
+    }
+
+    /// Moves the checkpoint forward, redelivered batches never move it back
+    pub async fn update_indexer_checkpoint(
+        &self,
+        source: &str,
+        timestamp_lt: i64,
+        timestamp_block: i32,
+    ) -> Result<()> {
+        let updated_at = Utc::now().timestamp_millis();
+
+        sqlx::query(
+            r#"INSERT INTO indexer_checkpoints (source, timestamp_lt, timestamp_block, updated_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (source) DO UPDATE SET
+                timestamp_lt = GREATEST(indexer_checkpoints.timestamp_lt, excluded.timestamp_lt),
+                timestamp_block = GREATEST(indexer_checkpoints.timestamp_block, excluded.timestamp_block),
+                updated_at = excluded.updated_at"#,
+        )
+        .bind(source)
+        .bind(timestamp_lt)
+        .bind(timestamp_block)
+        .bind(updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
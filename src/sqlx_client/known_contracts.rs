@@ -0,0 +1,49 @@
+use anyhow::Result;
+
+use crate::models::*;
+use crate::sqlx_client::*;
+
+impl SqlxClient {
+    pub async fn create_known_contract(&self, contract: CreateKnownContract) -> Result<()> {
+        sqlx::query(
+            r#"INSERT INTO known_contracts (address, kind, dao_root, owner)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT DO NOTHING"#,
+        )
+        .bind(contract.address)
+        .bind(contract.kind)
+        .bind(contract.dao_root)
+        .bind(contract.owner)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_known_contract(
+        &self,
+        address: String,
+        kind: KnownContractKind,
+    ) -> Result<Option<KnownContractFromDb>> {
+        let contract: Option<(String, KnownContractKind, String, Option<String>, i64)> =
+            sqlx::query_as(
+                "SELECT address, kind, dao_root, owner, created_at \
+                FROM known_contracts \
+                WHERE address = $1 AND kind = $2",
+            )
+            .bind(address)
+            .bind(kind)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(contract.map(
+            |(address, kind, dao_root, owner, created_at)| KnownContractFromDb {
+                address,
+                kind,
+                dao_root,
+                owner,
+                created_at,
+            },
+        ))
+    }
+}
@@ -1,17 +1,157 @@
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 
+use itertools::Itertools;
+
+use crate::models::{Direction, SearchMatchFromDb};
+use crate::utils::{CustomBuild, CustomBuildType, OwnedPartBuilder, RowReader, MAX_LIMIT};
+
+mod applied_events;
+mod audit_events;
+mod dao_config;
+mod dao_stats;
+mod governance_events;
+mod indexer_checkpoints;
+mod known_contracts;
+mod pending_actions;
 mod proposals;
 mod raw_transactions;
+mod timeseries;
 mod voters;
 mod votes;
+mod webhooks;
 
 #[derive(Clone)]
 pub struct SqlxClient {
     pool: PgPool,
+    /// Governance events are replayed, so they are not sent to webhooks again
+    replaying: bool,
 }
 
 impl SqlxClient {
     pub fn new(pool: PgPool) -> SqlxClient {
-        SqlxClient { pool }
+        SqlxClient {
+            pool,
+            replaying: false,
+        }
+    }
+
+    /// Client for a reindex, which doesn't enqueue webhook deliveries
+    pub fn replaying(self) -> SqlxClient {
+        SqlxClient {
+            replaying: true,
+            ..self
+        }
     }
+
+    /// Starts a transaction shared by all writes of one chain transaction
+    pub async fn begin(&self) -> anyhow::Result<Transaction<'static, Postgres>> {
+        self.pool.begin().await.map_err(From::from)
+    }
+}
+
+/// Selects the rank and the highlighted snippet of a full-text search hit,
+/// or `NULL`s when there is no search query
+fn push_search_match_columns(
+    builder: &mut OwnedPartBuilder,
+    document: &str,
+    query: Option<String>,
+    args_len: &mut u32,
+) {
+    match query {
+        Some(query) => {
+            *args_len += 1;
+            builder.push_with_arg(
+                format!(
+                    ", ts_rank(search_vector, websearch_to_tsquery('english', ${0})) AS search_rank, \
+                    ts_headline('english', {1}, websearch_to_tsquery('english', ${0}), 'MaxFragments=2') AS search_snippet",
+                    *args_len, document
+                ),
+                query,
+            );
+        }
+        None => {
+            builder.push(", NULL::REAL AS search_rank, NULL::TEXT AS search_snippet");
+        }
+    }
+}
+
+fn read_search_match(x: &mut RowReader<Postgres>) -> Option<SearchMatchFromDb> {
+    let rank: Option<f32> = x.read_next();
+    let snippet: Option<String> = x.read_next();
+    rank.zip(snippet)
+        .map(|(rank, snippet)| SearchMatchFromDb { rank, snippet })
+}
+
+fn search_query_filter(query: String, args_len: &mut u32) -> (String, String) {
+    *args_len += 1;
+    (
+        format!(
+            "search_vector @@ websearch_to_tsquery('english', ${})",
+            *args_len
+        ),
+        query,
+    )
+}
+
+/// Selects rows after the cursor `values` of the `columns` tuple
+fn keyset_condition(
+    columns: &str,
+    direction: Direction,
+    values: Vec<CustomBuildType>,
+    args_len: &mut u32,
+) -> CustomBuild<Vec<CustomBuildType>> {
+    let operator = match direction {
+        Direction::Ascending => ">",
+        Direction::Descending => "<",
+    };
+    let params = values
+        .iter()
+        .map(|_| {
+            *args_len += 1;
+            format!("${}", *args_len)
+        })
+        .join(", ");
+
+    CustomBuild(format!("({}) {} ({})", columns, operator, params), values)
+}
+
+/// Pages never exceed `MAX_LIMIT` rows
+fn max_limit(limit: i32) -> i32 {
+    std::cmp::min(limit, MAX_LIMIT)
+}
+
+/// `ORDER BY` clause followed by the tie-breaker, all in the same direction
+fn ordering_clause(column: Option<&str>, tie_breaker: &[&str], direction: Direction) -> String {
+    let direction = match direction {
+        Direction::Ascending => "ASC",
+        Direction::Descending => "DESC",
+    };
+
+    format!(
+        "ORDER BY {}",
+        column
+            .into_iter()
+            .chain(tie_breaker.iter().copied())
+            .map(|column| format!("{} {}", column, direction))
+            .join(", ")
+    )
+}
+
+/// Whether the query reads rows in the order of an index, without sorting them
+#[cfg(test)]
+async fn is_ordered_by_index(pool: &PgPool, query: &str) -> bool {
+    let mut tx = pool.begin().await.unwrap();
+    // tables are empty in tests, so a sequential scan would be cheaper
+    sqlx::query("SET LOCAL enable_seqscan = off")
+        .execute(&mut tx)
+        .await
+        .unwrap();
+
+    let plan: Vec<(String,)> = sqlx::query_as(&format!("EXPLAIN {}", query))
+        .fetch_all(&mut tx)
+        .await
+        .unwrap();
+    let plan = plan.into_iter().map(|(x,)| x).join("\n");
+
+    plan.contains("Index Scan") && !plan.contains("Sort")
 }
@@ -0,0 +1,162 @@
+use anyhow::Result;
+use sqlx::{Postgres, Transaction};
+
+use crate::models::*;
+use crate::sqlx_client::*;
+use crate::utils::*;
+
+impl SqlxClient {
+    pub async fn create_pending_action(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        action: CreatePendingAction,
+    ) -> Result<()> {
+        let query = sqlx::query(
+            r#"INSERT INTO pending_actions (
+            kind, proposal_address, dao_root, proposal_id, voter, execution_time, timestamp_block)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT DO NOTHING"#,
+        );
+
+        let query = match action {
+            CreatePendingAction::Executed {
+                proposal_address,
+                timestamp_block,
+            } => query
+                .bind(PendingActionKind::Executed)
+                .bind(Some(proposal_address))
+                .bind(None::<String>)
+                .bind(None::<i32>)
+                .bind(None::<String>)
+                .bind(None::<i64>)
+                .bind(timestamp_block),
+            CreatePendingAction::Canceled {
+                proposal_address,
+                timestamp_block,
+            } => query
+                .bind(PendingActionKind::Canceled)
+                .bind(Some(proposal_address))
+                .bind(None::<String>)
+                .bind(None::<i32>)
+                .bind(None::<String>)
+                .bind(None::<i64>)
+                .bind(timestamp_block),
+            CreatePendingAction::Queued {
+                proposal_address,
+                timestamp_block,
+                execution_time,
+            } => query
+                .bind(PendingActionKind::Queued)
+                .bind(Some(proposal_address))
+                .bind(None::<String>)
+                .bind(None::<i32>)
+                .bind(None::<String>)
+                .bind(Some(execution_time))
+                .bind(timestamp_block),
+            CreatePendingAction::UnlockVote {
+                dao_root,
+                proposal_id,
+                voter,
+                timestamp_block,
+            } => query
+                .bind(PendingActionKind::UnlockVote)
+                .bind(None::<String>)
+                .bind(Some(dao_root))
+                .bind(Some(proposal_id))
+                .bind(Some(voter))
+                .bind(None::<i64>)
+                .bind(timestamp_block),
+        };
+
+        query.execute(&mut *tx).await?;
+
+        Ok(())
+    }
+
+    pub async fn search_pending_actions(
+        &self,
+        input: PendingActionsSearch,
+    ) -> Result<impl Iterator<Item = PendingActionFromDb> + Send + Sync> {
+        let mut query = OwnedPartBuilder::new().starts_with(
+            "SELECT \
+                id, kind, proposal_address, dao_root, proposal_id, voter, execution_time, \
+                timestamp_block, created_at \
+            FROM pending_actions",
+        );
+
+        let mut args_len = 0;
+
+        query
+            .push_part(pending_action_filters(input.data, &mut args_len))
+            .push("ORDER BY created_at, id")
+            .push_with_arg(
+                {
+                    format!("LIMIT ${}", {
+                        args_len += 1;
+                        args_len
+                    })
+                },
+                max_limit(input.limit),
+            )
+            .push_with_arg(
+                {
+                    format!("OFFSET ${}", {
+                        args_len += 1;
+                        args_len
+                    })
+                },
+                input.offset,
+            );
+
+        let (query, args) = query.split();
+
+        let actions = sqlx::query_with(&query, args).fetch_all(&self.pool).await?;
+
+        Ok(actions
+            .into_iter()
+            .map(RowReader::from_row)
+            .map(|mut x| PendingActionFromDb {
+                id: x.read_next(),
+                kind: x.read_next(),
+                proposal_address: x.read_next(),
+                dao_root: x.read_next(),
+                proposal_id: x.read_next(),
+                voter: x.read_next(),
+                execution_time: x.read_next(),
+                timestamp_block: x.read_next(),
+                created_at: x.read_next(),
+            }))
+    }
+
+    pub async fn pending_actions_total_count(&self, input: PendingActionFilters) -> Result<i64> {
+        let mut args_len = 0;
+
+        let mut query = OwnedPartBuilder::new().starts_with("SELECT COUNT(*) FROM pending_actions");
+
+        query.push_part(pending_action_filters(input, &mut args_len));
+
+        let (query, args) = query.split();
+
+        let total_count: i64 = sqlx::query_with(&query, args)
+            .fetch_one(&self.pool)
+            .await
+            .map(RowReader::from_row)
+            .map(|mut x| x.read_next())
+            .unwrap_or_default();
+
+        Ok(total_count)
+    }
+}
+
+fn pending_action_filters(filters: PendingActionFilters, args_len: &mut u32) -> impl QueryPart {
+    WhereAndConditions((
+        filters.kind.map(|kind| {
+            *args_len += 1;
+            (format!("kind = ${}", *args_len), kind)
+        }),
+        filters.created_at_le.map(|time| {
+            *args_len += 1;
+            (format!("created_at <= ${}", *args_len), time)
+        }),
+    ))
+}
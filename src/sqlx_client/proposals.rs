@@ -1,67 +1,144 @@
 use anyhow::Result;
 use chrono::Utc;
 use rust_decimal::Decimal;
+use sqlx::{Postgres, Transaction};
 
 use crate::models::*;
 use crate::sqlx_client::*;
 use crate::utils::*;
 
+/// Makes the proposals order deterministic
+const PROPOSALS_TIE_BREAKER: [&str; 3] = ["timestamp_block", "id", "dao_root"];
+
+const PROPOSAL_COLUMNS: &str = "id, address, proposer, description, start_time, end_time, execution_time, \
+    grace_period, time_lock, voting_delay, for_votes, against_votes, quorum_votes, message_hash, transaction_hash, \
+    timestamp_block, actions, executed, canceled, queued, executed_at, canceled_at, queued_at, \
+    updated_at, created_at, dao_root";
+
 impl SqlxClient {
-    pub async fn create_proposal(&self, proposal: CreateProposal) -> Result<()> {
-        let mut tx = self.pool.begin().await?;
+    pub async fn create_proposal(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        proposal: CreateProposal,
+    ) -> Result<()> {
+        let created_event = CreateGovernanceEvent::proposal_created(&proposal);
 
-        let sql = "SELECT SUM(votes) FROM votes WHERE proposal_id = $1 AND support = $2";
+        let sql = "SELECT SUM(votes) FROM votes WHERE dao_root = $1 AND proposal_id = $2 AND support = $3";
         let for_votes: (Decimal,) = sqlx::query_as(sql)
+            .bind(&proposal.dao_root)
             .bind(proposal.id)
             .bind(true)
-            .fetch_one(&mut tx)
+            .fetch_one(&mut *tx)
             .await
             .unwrap_or_default();
         let against_votes: (Decimal,) = sqlx::query_as(sql)
+            .bind(&proposal.dao_root)
             .bind(proposal.id)
             .bind(false)
-            .fetch_one(&mut tx)
+            .fetch_one(&mut *tx)
             .await
             .unwrap_or_default();
 
-        sqlx::query!(
+        sqlx::query(
             r#"INSERT INTO proposals (
             id, address, proposer, description, start_time, end_time, execution_time, grace_period, time_lock, voting_delay, for_votes,
-            against_votes, quorum_votes, message_hash, transaction_hash, timestamp_block, actions)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+            against_votes, quorum_votes, message_hash, transaction_hash, timestamp_block, actions, dao_root)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
             "#,
-            proposal.id,
-            proposal.address,
-            proposal.proposer,
-            proposal.description,
-            proposal.start_time,
-            proposal.end_time,
-            proposal.execution_time,
-            proposal.grace_period,
-            proposal.time_lock,
-            proposal.voting_delay,
-            for_votes.0,
-            against_votes.0,
-            proposal.quorum_votes,
-            proposal.message_hash,
-            proposal.transaction_hash,
-            proposal.timestamp_block,
-            serde_json::to_value(proposal.actions).unwrap(),
         )
-            .execute(&mut tx)
+        .bind(proposal.id)
+        .bind(&proposal.address)
+        .bind(proposal.proposer)
+        .bind(proposal.description)
+        .bind(proposal.start_time)
+        .bind(proposal.end_time)
+        .bind(proposal.execution_time)
+        .bind(proposal.grace_period)
+        .bind(proposal.time_lock)
+        .bind(proposal.voting_delay)
+        .bind(for_votes.0)
+        .bind(against_votes.0)
+        .bind(proposal.quorum_votes)
+        .bind(proposal.message_hash)
+        .bind(proposal.transaction_hash)
+        .bind(proposal.timestamp_block)
+        .bind(serde_json::to_value(proposal.actions).unwrap())
+        .bind(&proposal.dao_root)
+        .execute(&mut *tx)
+        .await?;
+
+        self.update_daily_voting_stats(tx, &proposal.dao_root, proposal.timestamp_block)
             .await?;
+        self.create_governance_event(tx, created_event).await?;
 
-        tx.commit().await?;
+        // apply actions which arrived before the proposal
+        let mut pending_actions: Vec<(i64, PendingActionKind, i32, Option<i64>)> = sqlx::query_as(
+            "DELETE FROM pending_actions WHERE proposal_address = $1 \
+            RETURNING id, kind, timestamp_block, execution_time",
+        )
+        .bind(&proposal.address)
+        .fetch_all(&mut *tx)
+        .await?;
+        pending_actions.sort_by_key(|(id, ..)| *id);
+
+        let updated_at = Utc::now().timestamp_millis();
+        for (_, kind, timestamp_block, execution_time) in pending_actions {
+            let query = match kind {
+                PendingActionKind::Executed => sqlx::query(
+                    "UPDATE proposals SET executed = true, executed_at = $1, updated_at = $2 \
+                    WHERE address = $3",
+                )
+                .bind(timestamp_block),
+                PendingActionKind::Canceled => sqlx::query(
+                    "UPDATE proposals SET canceled = true, canceled_at = $1, updated_at = $2 \
+                    WHERE address = $3",
+                )
+                .bind(timestamp_block),
+                PendingActionKind::Queued => sqlx::query(
+                    "UPDATE proposals SET queued = true, execution_time = $4, queued_at = $1, updated_at = $2 \
+                    WHERE address = $3",
+                )
+                .bind(timestamp_block),
+                PendingActionKind::UnlockVote => continue,
+            };
+
+            let query = query.bind(updated_at).bind(&proposal.address);
+            let query = match kind {
+                PendingActionKind::Queued => query.bind(execution_time.unwrap_or_default()),
+                _ => query,
+            };
+
+            query.execute(&mut *tx).await?;
+
+            let (dao_root, id) = (proposal.dao_root.clone(), proposal.id);
+            let event = match kind {
+                PendingActionKind::Executed => {
+                    CreateGovernanceEvent::executed(dao_root, id, timestamp_block)
+                }
+                PendingActionKind::Canceled => {
+                    CreateGovernanceEvent::canceled(dao_root, id, timestamp_block)
+                }
+                PendingActionKind::Queued => CreateGovernanceEvent::queued(
+                    dao_root,
+                    id,
+                    execution_time.unwrap_or_default(),
+                    timestamp_block,
+                ),
+                PendingActionKind::UnlockVote => continue,
+            };
+            self.create_governance_event(tx, event).await?;
+        }
 
         Ok(())
     }
 
     pub async fn update_proposal_executed(
         &self,
+        tx: &mut Transaction<'_, Postgres>,
         address: String,
         timestamp_block: i32,
-    ) -> Result<i32> {
-        let updated_at = chrono::Utc::now().timestamp_millis();
+    ) -> Result<Option<i32>> {
+        let updated_at = Utc::now().timestamp_millis();
 
         sqlx::query!(
             r#"
@@ -72,18 +149,19 @@ impl SqlxClient {
             updated_at,
             address,
         )
-        .fetch_one(&self.pool)
+        .fetch_optional(&mut *tx)
         .await
-        .map(|x| x.id)
+        .map(|x| x.map(|x| x.id))
         .map_err(From::from)
     }
 
     pub async fn update_proposal_canceled(
         &self,
+        tx: &mut Transaction<'_, Postgres>,
         address: String,
         timestamp_block: i32,
-    ) -> Result<i32> {
-        let updated_at = chrono::Utc::now().timestamp_millis();
+    ) -> Result<Option<i32>> {
+        let updated_at = Utc::now().timestamp_millis();
 
         sqlx::query!(
             r#"
@@ -94,19 +172,20 @@ impl SqlxClient {
             updated_at,
             address,
         )
-        .fetch_one(&self.pool)
+        .fetch_optional(&mut *tx)
         .await
-        .map(|x| x.id)
+        .map(|x| x.map(|x| x.id))
         .map_err(From::from)
     }
 
     pub async fn update_proposal_queued(
         &self,
+        tx: &mut Transaction<'_, Postgres>,
         address: String,
         timestamp_block: i32,
         execution_time: i64,
-    ) -> Result<i32> {
-        let updated_at = chrono::Utc::now().timestamp_millis();
+    ) -> Result<Option<i32>> {
+        let updated_at = Utc::now().timestamp_millis();
 
         sqlx::query!(
             r#"
@@ -118,52 +197,137 @@ impl SqlxClient {
             updated_at,
             address,
         )
-        .fetch_one(&self.pool)
+        .fetch_optional(&mut *tx)
         .await
-        .map(|x| x.id)
+        .map(|x| x.map(|x| x.id))
         .map_err(From::from)
     }
 
     pub async fn update_proposal_votes(
         &self,
+        tx: &mut Transaction<'_, Postgres>,
+        dao_root: String,
         proposal_id: i32,
         proposal_votes: UpdateProposalVotes,
-    ) -> Result<i32> {
-        let updated_at = chrono::Utc::now().timestamp_millis();
+    ) -> Result<Option<i32>> {
+        let updated_at = Utc::now().timestamp_millis();
 
-        sqlx::query!(
+        sqlx::query_as::<_, (i32,)>(
             r#"
-            UPDATE proposals SET for_votes = for_votes + $2, against_votes = against_votes + $3, updated_at = $4
-            WHERE id = $1
+            UPDATE proposals SET for_votes = for_votes + $3, against_votes = against_votes + $4, updated_at = $5
+            WHERE dao_root = $1 AND id = $2
             RETURNING id"#,
-            proposal_id,
-            proposal_votes.for_votes,
-            proposal_votes.against_votes,
-            updated_at,
         )
-            .fetch_one(&self.pool)
-            .await
-            .map(|x| x.id)
-            .map_err(From::from)
+        .bind(dao_root)
+        .bind(proposal_id)
+        .bind(proposal_votes.for_votes)
+        .bind(proposal_votes.against_votes)
+        .bind(updated_at)
+        .fetch_optional(&mut *tx)
+        .await
+        .map(|x| x.map(|x| x.0))
+        .map_err(From::from)
+    }
+
+    /// Sets the tally to the sum of the stored votes
+    pub async fn recompute_proposal_votes(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        dao_root: String,
+        proposal_id: i32,
+    ) -> Result<Option<i32>> {
+        let updated_at = Utc::now().timestamp_millis();
+
+        sqlx::query_as::<_, (i32,)>(
+            r#"
+            UPDATE proposals SET
+                for_votes = (SELECT COALESCE(SUM(votes), 0) FROM votes WHERE dao_root = $1 AND proposal_id = $2 AND support),
+                against_votes = (SELECT COALESCE(SUM(votes), 0) FROM votes WHERE dao_root = $1 AND proposal_id = $2 AND NOT support),
+                updated_at = $3
+            WHERE dao_root = $1 AND id = $2
+            RETURNING id"#,
+        )
+        .bind(dao_root)
+        .bind(proposal_id)
+        .bind(updated_at)
+        .fetch_optional(&mut *tx)
+        .await
+        .map(|x| x.map(|x| x.0))
+        .map_err(From::from)
+    }
+
+    /// Addresses and actions of proposals with at least one TON action
+    pub async fn get_proposals_with_ton_actions(&self) -> Result<Vec<(String, serde_json::Value)>> {
+        sqlx::query_as(
+            "SELECT address, actions FROM proposals \
+            WHERE jsonb_array_length(actions->'tonActions') > 0 \
+            ORDER BY created_at",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(From::from)
+    }
+
+    pub async fn assign_legacy_dao_root(&self, dao_root: String) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for table in [
+            "proposals",
+            "votes",
+            "dao_config_history",
+            "governance_audit_events",
+        ] {
+            sqlx::query(&format!(
+                "UPDATE {} SET dao_root = $1 WHERE dao_root = ''",
+                table
+            ))
+            .bind(&dao_root)
+            .execute(&mut tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
     }
 
     pub async fn search_proposals(
         &self,
         input: ProposalsSearch,
-    ) -> Result<impl Iterator<Item = ProposalFromDb> + Send + Sync> {
-        let mut query = OwnedPartBuilder::new().starts_with(
-                "SELECT \
-                id, address, proposer, description, start_time, end_time, execution_time, \
-                grace_period, time_lock, voting_delay, for_votes, against_votes, quorum_votes, message_hash, transaction_hash, \
-                timestamp_block, actions, executed, canceled, queued, executed_at, canceled_at, queued_at, \
-                updated_at, created_at \
-            FROM proposals");
+    ) -> Result<impl Iterator<Item = (ProposalFromDb, Option<SearchMatchFromDb>)> + Send + Sync>
+    {
+        let mut query = OwnedPartBuilder::new().starts_with(format!("SELECT {}", PROPOSAL_COLUMNS));
 
         let mut args_len = 0;
 
+        let ordering = if input.is_ordered_by_rank() {
+            ordering_clause(
+                Some("search_rank"),
+                &PROPOSALS_TIE_BREAKER,
+                Direction::Descending,
+            )
+        } else {
+            proposals_ordering(input.data.ordering)
+        };
+
+        if input.cursor.is_some() && !input.supports_cursor() {
+            anyhow::bail!("Cursor is only supported for the createdAt ordering");
+        }
+        let cursor = input
+            .cursor
+            .as_deref()
+            .map(decode_cursor::<ProposalCursor>)
+            .transpose()?
+            .map(|cursor| (cursor, input.data.ordering.unwrap_or_default().direction));
+        let offset = if cursor.is_some() { 0 } else { input.offset };
+
+        let search = input.data.filters.query.clone();
+        push_search_match_columns(&mut query, "description", search, &mut args_len);
+
         query
-            .push_part(proposal_filters(input.data.filters, &mut args_len))
-            .push(proposals_ordering(input.data.ordering))
+            .push("FROM proposals")
+            .push_part(proposal_filters(input.data.filters, cursor, &mut args_len))
+            .push(ordering)
             .push_with_arg(
                 {
                     format!("LIMIT ${}", {
@@ -180,43 +344,55 @@ impl SqlxClient {
                         args_len
                     })
                 },
-                input.offset,
+                offset,
             );
 
         let (query, args) = query.split();
 
         let proposals = sqlx::query_with(&query, args).fetch_all(&self.pool).await?;
 
-        Ok(proposals
-            .into_iter()
-            .map(RowReader::from_row)
-            .map(|mut x| ProposalFromDb {
-                id: x.read_next(),
-                address: x.read_next(),
-                proposer: x.read_next(),
-                description: x.read_next(),
-                start_time: x.read_next(),
-                end_time: x.read_next(),
-                execution_time: x.read_next(),
-                grace_period: x.read_next(),
-                time_lock: x.read_next(),
-                voting_delay: x.read_next(),
-                for_votes: x.read_next(),
-                against_votes: x.read_next(),
-                quorum_votes: x.read_next(),
-                message_hash: x.read_next(),
-                transaction_hash: x.read_next(),
-                timestamp_block: x.read_next(),
-                actions: x.read_next(),
-                executed: x.read_next(),
-                canceled: x.read_next(),
-                queued: x.read_next(),
-                executed_at: x.read_next(),
-                canceled_at: x.read_next(),
-                queued_at: x.read_next(),
-                updated_at: x.read_next(),
-                created_at: x.read_next(),
-            }))
+        Ok(proposals.into_iter().map(RowReader::from_row).map(|mut x| {
+            let proposal = read_proposal(&mut x);
+            (proposal, read_search_match(&mut x))
+        }))
+    }
+
+    /// Without `dao_root` the latest proposal with the id is returned
+    pub async fn get_proposal(
+        &self,
+        dao_root: Option<String>,
+        proposal_id: i32,
+    ) -> Result<Option<ProposalFromDb>> {
+        let mut query = OwnedPartBuilder::new().starts_with(format!(
+            "SELECT {} FROM proposals WHERE id = $1",
+            PROPOSAL_COLUMNS
+        ));
+        query.push_arg(proposal_id);
+
+        if let Some(dao_root) = dao_root {
+            query.push_with_arg("AND dao_root = $2", dao_root);
+        }
+        query.push("ORDER BY timestamp_block DESC, dao_root LIMIT 1");
+
+        let (query, args) = query.split();
+
+        let proposal = sqlx::query_with(&query, args)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(proposal.map(|x| read_proposal(&mut RowReader::from_row(x))))
+    }
+
+    pub async fn get_proposal_by_address(&self, address: String) -> Result<Option<ProposalFromDb>> {
+        let proposal = sqlx::query(&format!(
+            "SELECT {} FROM proposals WHERE address = $1",
+            PROPOSAL_COLUMNS
+        ))
+        .bind(address)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(proposal.map(|x| read_proposal(&mut RowReader::from_row(x))))
     }
 
     pub async fn proposals_total_count(&self, input: ProposalFilters) -> Result<i64> {
@@ -224,7 +400,7 @@ impl SqlxClient {
 
         let mut query = OwnedPartBuilder::new().starts_with("SELECT COUNT(*) FROM proposals");
 
-        query.push_part(proposal_filters(input, &mut args_len));
+        query.push_part(proposal_filters(input, None, &mut args_len));
 
         let (query, args) = query.split();
 
@@ -237,10 +413,143 @@ impl SqlxClient {
 
         Ok(total_count)
     }
+
+    /// Proposals by `(dao_root, id)` pairs, missing ones are skipped
+    pub async fn get_proposals_by_ids(
+        &self,
+        ids: Vec<(String, i32)>,
+    ) -> Result<Vec<ProposalFromDb>> {
+        let (dao_roots, proposal_ids): (Vec<_>, Vec<_>) = ids.into_iter().unzip();
+
+        let proposals = sqlx::query(&format!(
+            "SELECT {} FROM proposals \
+            WHERE (dao_root, id) IN (SELECT * FROM UNNEST($1::VARCHAR[], $2::INTEGER[]))",
+            PROPOSAL_COLUMNS
+        ))
+        .bind(dao_roots)
+        .bind(proposal_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(proposals
+            .into_iter()
+            .map(|x| read_proposal(&mut RowReader::from_row(x)))
+            .collect())
+    }
+
+    /// Proposals whose next state change by the clock is due before `now`.
+    /// The next change follows the last recorded one, like in `ProposalFromDb::state_history`
+    pub async fn get_proposals_awaiting_transitions(
+        &self,
+        now: i64,
+        limit: i32,
+    ) -> Result<Vec<ProposalFromDb>> {
+        let proposals = sqlx::query(&format!(
+            "SELECT {} FROM proposals p \
+            LEFT JOIN LATERAL ( \
+                SELECT t.state AS last_state FROM proposal_state_transitions t \
+                WHERE t.dao_root = p.dao_root AND t.proposal_id = p.id \
+                ORDER BY t.transition_time DESC \
+                LIMIT 1 \
+            ) last_transition ON TRUE \
+            WHERE NOT canceled AND NOT executed AND CASE \
+                WHEN last_state IS NULL THEN start_time < $1 \
+                WHEN last_state = 'Active' THEN end_time < $1 \
+                WHEN last_state = 'Succeeded' THEN execution_time <> 0 AND execution_time + grace_period < $1 \
+                ELSE FALSE \
+            END \
+            ORDER BY timestamp_block, id \
+            LIMIT $2",
+            PROPOSAL_COLUMNS
+        ))
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(proposals
+            .into_iter()
+            .map(|x| read_proposal(&mut RowReader::from_row(x)))
+            .collect())
+    }
+
+    /// Returns `false` if the transition has already been recorded
+    pub async fn create_proposal_state_transition(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        dao_root: &str,
+        proposal_id: i32,
+        change: ProposalStateChange,
+    ) -> Result<bool> {
+        let res = sqlx::query(
+            "INSERT INTO proposal_state_transitions (dao_root, proposal_id, state, transition_time) \
+            VALUES ($1, $2, $3, $4) \
+            ON CONFLICT DO NOTHING",
+        )
+        .bind(dao_root)
+        .bind(proposal_id)
+        .bind(change.state.to_string())
+        .bind(change.timestamp)
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+}
+
+fn read_proposal(x: &mut RowReader<Postgres>) -> ProposalFromDb {
+    ProposalFromDb {
+        id: x.read_next(),
+        address: x.read_next(),
+        proposer: x.read_next(),
+        description: x.read_next(),
+        start_time: x.read_next(),
+        end_time: x.read_next(),
+        execution_time: x.read_next(),
+        grace_period: x.read_next(),
+        time_lock: x.read_next(),
+        voting_delay: x.read_next(),
+        for_votes: x.read_next(),
+        against_votes: x.read_next(),
+        quorum_votes: x.read_next(),
+        message_hash: x.read_next(),
+        transaction_hash: x.read_next(),
+        timestamp_block: x.read_next(),
+        actions: x.read_next(),
+        executed: x.read_next(),
+        canceled: x.read_next(),
+        queued: x.read_next(),
+        executed_at: x.read_next(),
+        canceled_at: x.read_next(),
+        queued_at: x.read_next(),
+        updated_at: x.read_next(),
+        created_at: x.read_next(),
+        dao_root: x.read_next(),
+    }
 }
 
-fn proposal_filters(filters: ProposalFilters, args_len: &mut u32) -> impl QueryPart {
+fn proposal_filters(
+    filters: ProposalFilters,
+    cursor: Option<(ProposalCursor, Direction)>,
+    args_len: &mut u32,
+) -> impl QueryPart {
     WhereAndConditions((
+        cursor.map(|(cursor, direction)| {
+            keyset_condition(
+                "timestamp_block, id, dao_root",
+                direction,
+                vec![
+                    CustomBuildType::Int(cursor.timestamp_block.into()),
+                    CustomBuildType::Int(cursor.id.into()),
+                    CustomBuildType::String(cursor.dao_root),
+                ],
+                args_len,
+            )
+        }),
+        filters.dao_root.map(|dao_root| {
+            *args_len += 1;
+            (format!("dao_root = ${}", *args_len), dao_root)
+        }),
         filters.start_time_ge.map(|time| {
             *args_len += 1;
             (format!("start_time >= ${}", *args_len), time)
@@ -269,6 +578,9 @@ fn proposal_filters(filters: ProposalFilters, args_len: &mut u32) -> impl QueryP
             *args_len += 1;
             (format!("address = ${}", *args_len), proposal_address)
         }),
+        filters
+            .query
+            .map(|query| search_query_filter(query, args_len)),
         filters.state.map(|state| {
             let now = Utc::now().timestamp();
             match state {
@@ -344,15 +656,45 @@ fn proposal_filters(filters: ProposalFilters, args_len: &mut u32) -> impl QueryP
     ))
 }
 
-fn proposals_ordering(ordering: Option<ProposalsOrdering>) -> &'static str {
+fn proposals_ordering(ordering: Option<ProposalsOrdering>) -> String {
     let ProposalsOrdering { column, direction } = ordering.unwrap_or_default();
 
-    match (column, direction) {
-        (ProposalColumn::CreatedAt, Direction::Ascending) => "ORDER BY timestamp_block",
-        (ProposalColumn::CreatedAt, Direction::Descending) => "ORDER BY timestamp_block DESC",
-    }
+    let column = match column {
+        ProposalColumn::CreatedAt => None,
+        ProposalColumn::EndTime => Some("end_time"),
+        ProposalColumn::TotalVotes => Some("(for_votes + against_votes)"),
+        ProposalColumn::ForVotes => Some("for_votes"),
+        ProposalColumn::AgainstVotes => Some("against_votes"),
+        ProposalColumn::QuorumProgress => Some("(for_votes / NULLIF(quorum_votes, 0))"),
+    };
+
+    ordering_clause(column, &PROPOSALS_TIE_BREAKER, direction)
 }
 
-fn max_limit(limit: i32) -> i32 {
-    std::cmp::min(limit, 100)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn orderings_match_indexes(pool: PgPool) {
+        let columns = [
+            ProposalColumn::CreatedAt,
+            ProposalColumn::EndTime,
+            ProposalColumn::TotalVotes,
+            ProposalColumn::ForVotes,
+            ProposalColumn::AgainstVotes,
+            ProposalColumn::QuorumProgress,
+        ];
+
+        for column in columns {
+            for direction in [Direction::Ascending, Direction::Descending] {
+                let query = format!(
+                    "SELECT {} FROM proposals {} LIMIT 10",
+                    PROPOSAL_COLUMNS,
+                    proposals_ordering(Some(ProposalsOrdering { column, direction }))
+                );
+                assert!(is_ordered_by_index(&pool, &query).await, "{}", query);
+            }
+        }
+    }
 }
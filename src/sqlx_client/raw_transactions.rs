@@ -1,5 +1,11 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use sqlx::{Postgres, Transaction};
+
 use crate::models::*;
 use crate::sqlx_client::*;
+use crate::utils::*;
 
 impl SqlxClient {
     pub async fn create_raw_transaction(
@@ -19,29 +25,204 @@ impl SqlxClient {
         Ok(())
     }
 
-    pub async fn get_raw_transactions_by_state(
+    /// Failed transactions which are due for the next attempt
+    pub async fn get_raw_transactions_to_retry(
         &self,
-        state: RawTransactionState,
+        now: i64,
+        limit: i32,
     ) -> Result<Vec<RawTransactionFromDb>, anyhow::Error> {
-        sqlx::query_as!(
-            RawTransactionFromDb,
-            r#"
-            SELECT transaction, transaction_hash, timestamp_block, timestamp_lt, created_at, state as "state: _"
-            FROM raw_transactions_service
-            WHERE state = $1
-            ORDER BY timestamp_block"#,
-            state as RawTransactionState,
+        let transactions = sqlx::query(
+            "SELECT transaction, transaction_hash, timestamp_block, timestamp_lt, created_at, state \
+            FROM raw_transactions_service \
+            WHERE state = $1 AND (next_retry_at IS NULL OR next_retry_at <= $2) \
+            ORDER BY timestamp_lt \
+            LIMIT $3",
         )
+        .bind(RawTransactionState::Fail)
+        .bind(now)
+        .bind(limit)
         .fetch_all(&self.pool)
-        .await
-        .map_err(anyhow::Error::from)
+        .await?;
+
+        Ok(transactions
+            .into_iter()
+            .map(RowReader::from_row)
+            .map(|mut x| RawTransactionFromDb {
+                transaction: x.read_next(),
+                transaction_hash: x.read_next(),
+                timestamp_block: x.read_next(),
+                timestamp_lt: x.read_next(),
+                created_at: x.read_next(),
+                state: x.read_next(),
+            })
+            .collect())
+    }
+
+    /// Stores the error and schedules the next attempt with exponential backoff.
+    /// Returns the new state of the transaction
+    pub async fn record_raw_transaction_failure(
+        &self,
+        transaction_hash: &[u8],
+        error: String,
+        retry_policy: RetryPolicy,
+    ) -> Result<Option<RawTransactionState>, anyhow::Error> {
+        let now = Utc::now().timestamp_millis();
+
+        let state: Option<(RawTransactionState,)> = sqlx::query_as(
+            r#"
+            UPDATE raw_transactions_service SET
+                attempts = attempts + 1,
+                last_error = $2,
+                state = CASE WHEN attempts + 1 >= $3
+                    THEN 'DeadLetter'::raw_transaction_state_type
+                    ELSE 'Fail'::raw_transaction_state_type END,
+                next_retry_at = $4 + LEAST($5 * POWER(2, attempts), $6)::BIGINT
+            WHERE transaction_hash = $1
+            RETURNING state"#,
+        )
+        .bind(transaction_hash)
+        .bind(error)
+        .bind(retry_policy.max_attempts)
+        .bind(now)
+        .bind(retry_policy.base_delay_ms)
+        .bind(retry_policy.max_delay_ms)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(state.map(|(state,)| state))
+    }
+
+    pub async fn search_failed_raw_transactions(
+        &self,
+        input: FailedRawTransactionsSearch,
+    ) -> Result<impl Iterator<Item = FailedRawTransactionFromDb> + Send + Sync, anyhow::Error> {
+        let mut query = OwnedPartBuilder::new().starts_with(
+            "SELECT transaction, transaction_hash, timestamp_block, timestamp_lt, created_at, state, \
+                attempts, last_error, next_retry_at \
+            FROM raw_transactions_service",
+        );
+
+        let mut args_len = 0;
+
+        push_failed_raw_transaction_filters(&mut query, input.data, &mut args_len);
+        query
+            .push("ORDER BY timestamp_lt, transaction_hash")
+            .push_with_arg(
+                {
+                    format!("LIMIT ${}", {
+                        args_len += 1;
+                        args_len
+                    })
+                },
+                max_limit(input.limit),
+            )
+            .push_with_arg(
+                {
+                    format!("OFFSET ${}", {
+                        args_len += 1;
+                        args_len
+                    })
+                },
+                input.offset,
+            );
+
+        let (query, args) = query.split();
+
+        let transactions = sqlx::query_with(&query, args).fetch_all(&self.pool).await?;
+
+        Ok(transactions
+            .into_iter()
+            .map(RowReader::from_row)
+            .map(read_failed_raw_transaction))
+    }
+
+    pub async fn failed_raw_transactions_total_count(
+        &self,
+        input: FailedRawTransactionFilters,
+    ) -> Result<i64, anyhow::Error> {
+        let mut query =
+            OwnedPartBuilder::new().starts_with("SELECT COUNT(*) FROM raw_transactions_service");
+
+        let mut args_len = 0;
+
+        push_failed_raw_transaction_filters(&mut query, input, &mut args_len);
+
+        let (query, args) = query.split();
+
+        let total_count: i64 = sqlx::query_with(&query, args)
+            .fetch_one(&self.pool)
+            .await
+            .map(RowReader::from_row)
+            .map(|mut x| x.read_next())
+            .unwrap_or_default();
+
+        Ok(total_count)
+    }
+
+    pub async fn get_failed_raw_transaction(
+        &self,
+        transaction_hash: &[u8],
+    ) -> Result<Option<FailedRawTransactionFromDb>, anyhow::Error> {
+        let transaction = sqlx::query(
+            "SELECT transaction, transaction_hash, timestamp_block, timestamp_lt, created_at, state, \
+                attempts, last_error, next_retry_at \
+            FROM raw_transactions_service \
+            WHERE transaction_hash = $1",
+        )
+        .bind(transaction_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(transaction
+            .map(RowReader::from_row)
+            .map(read_failed_raw_transaction))
+    }
+
+    /// Schedules a failed or dead letter transaction for an immediate retry
+    pub async fn requeue_raw_transaction(
+        &self,
+        transaction_hash: &[u8],
+    ) -> Result<Option<FailedRawTransactionFromDb>, anyhow::Error> {
+        let transaction = sqlx::query(
+            "UPDATE raw_transactions_service SET state = $2, attempts = 0, next_retry_at = NULL \
+            WHERE transaction_hash = $1 AND state IN ('Fail', 'DeadLetter') \
+            RETURNING transaction, transaction_hash, timestamp_block, timestamp_lt, created_at, state, \
+                attempts, last_error, next_retry_at",
+        )
+        .bind(transaction_hash)
+        .bind(RawTransactionState::Fail)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(transaction
+            .map(RowReader::from_row)
+            .map(read_failed_raw_transaction))
+    }
+
+    /// Schedules for retry transactions which were stored but never processed.
+    /// Compared by block time, as lt is ordered only within an account
+    pub async fn requeue_unprocessed_raw_transactions(
+        &self,
+        timestamp_block_le: i32,
+    ) -> Result<u64, anyhow::Error> {
+        let result = sqlx::query(
+            "UPDATE raw_transactions_service SET state = $1, next_retry_at = NULL \
+            WHERE state IN ('Idle', 'InProgress') AND timestamp_block <= $2",
+        )
+        .bind(RawTransactionState::Fail)
+        .bind(timestamp_block_le)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
     }
 
     pub async fn update_raw_transactions(
         &self,
+        tx: &mut Transaction<'_, Postgres>,
         transaction_hash: &[u8],
         state: RawTransactionState,
-    ) -> Result<RawTransactionFromDb, anyhow::Error> {
+    ) -> Result<Option<RawTransactionFromDb>, anyhow::Error> {
         sqlx::query_as!(
             RawTransactionFromDb,
             r#"
@@ -56,8 +237,261 @@ impl SqlxClient {
             state as RawTransactionState,
             transaction_hash,
         )
-        .fetch_one(&self.pool)
+        .fetch_optional(&mut *tx)
         .await
         .map_err(From::from)
     }
+
+    pub async fn search_raw_transactions(
+        &self,
+        input: RawTransactionsSearch,
+    ) -> Result<Vec<RawTransactionFromDb>, anyhow::Error> {
+        let mut query = OwnedPartBuilder::new().starts_with(
+            "SELECT transaction, transaction_hash, timestamp_block, timestamp_lt, created_at, state \
+            FROM raw_transactions_service",
+        );
+
+        let mut args_len = 0;
+
+        query
+            .push_part(raw_transaction_filters(input.data, &mut args_len))
+            .push("ORDER BY timestamp_lt, transaction_hash")
+            .push_with_arg(
+                {
+                    format!("LIMIT ${}", {
+                        args_len += 1;
+                        args_len
+                    })
+                },
+                input.limit,
+            )
+            .push_with_arg(
+                {
+                    format!("OFFSET ${}", {
+                        args_len += 1;
+                        args_len
+                    })
+                },
+                input.offset,
+            );
+
+        let (query, args) = query.split();
+
+        let transactions = sqlx::query_with(&query, args).fetch_all(&self.pool).await?;
+
+        Ok(transactions
+            .into_iter()
+            .map(RowReader::from_row)
+            .map(|mut x| RawTransactionFromDb {
+                transaction: x.read_next(),
+                transaction_hash: x.read_next(),
+                timestamp_block: x.read_next(),
+                timestamp_lt: x.read_next(),
+                created_at: x.read_next(),
+                state: x.read_next(),
+            })
+            .collect())
+    }
+
+    /// Removes everything derived from raw transactions. Known contracts are cached contract
+    /// states rather than indexed data, and webhook deliveries carry their payloads,
+    /// so both are kept and pending deliveries are still sent
+    pub async fn clear_indexed_data(&self) -> Result<(), anyhow::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "TRUNCATE proposals, votes, dao_config_history, governance_audit_events, pending_actions, \
+            applied_events, daily_voting_stats, proposal_state_transitions RESTART IDENTITY",
+        )
+        .execute(&mut tx)
+        .await?;
+
+        // event ids keep growing, so streams resumed after the reindex don't skip events,
+        // but they receive the replayed history again with new ids
+        sqlx::query("TRUNCATE governance_events, staged_governance_events")
+            .execute(&mut tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Removes what the transactions of the range derived, so they are applied again on replay.
+    /// Unlocks and proposal states set by events outside the range are kept as pending actions,
+    /// which are applied once the votes and proposals are created again
+    pub async fn clear_indexed_range(
+        &self,
+        filters: RawTransactionFilters,
+    ) -> Result<(), anyhow::Error> {
+        let mut query = OwnedPartBuilder::new()
+            .starts_with("SELECT transaction_hash FROM raw_transactions_service");
+
+        let mut args_len = 0;
+
+        query.push_part(raw_transaction_filters(filters, &mut args_len));
+
+        let (query, args) = query.split();
+
+        let mut tx = self.pool.begin().await?;
+
+        let transaction_hashes: Vec<Vec<u8>> =
+            sqlx::query_as_with::<_, (Vec<u8>,), _>(&query, args)
+                .fetch_all(&mut tx)
+                .await?
+                .into_iter()
+                .map(|(transaction_hash,)| transaction_hash)
+                .collect();
+
+        for table in [
+            "applied_events",
+            "dao_config_history",
+            "governance_audit_events",
+        ] {
+            sqlx::query(&format!(
+                "DELETE FROM {} WHERE transaction_hash = ANY($1)",
+                table
+            ))
+            .bind(&transaction_hashes)
+            .execute(&mut tx)
+            .await?;
+        }
+
+        let votes: Vec<(String, i32, String, bool, i32)> = sqlx::query_as(
+            "DELETE FROM votes WHERE transaction_hash = ANY($1) \
+            RETURNING dao_root, proposal_id, voter, locked, timestamp_block",
+        )
+        .bind(&transaction_hashes)
+        .fetch_all(&mut tx)
+        .await?;
+
+        // states entered after the creation, `NULL` if not entered
+        let proposals = sqlx::query(
+            "DELETE FROM proposals WHERE transaction_hash = ANY($1) \
+            RETURNING dao_root, address, timestamp_block, execution_time, \
+                CASE WHEN queued THEN COALESCE(queued_at, timestamp_block) END, \
+                CASE WHEN executed THEN COALESCE(executed_at, timestamp_block) END, \
+                CASE WHEN canceled THEN COALESCE(canceled_at, timestamp_block) END",
+        )
+        .bind(&transaction_hashes)
+        .fetch_all(&mut tx)
+        .await?;
+
+        let mut pending_actions = Vec::new();
+        let mut days = HashSet::new();
+        let mut tallies = HashSet::new();
+
+        for (dao_root, proposal_id, voter, locked, timestamp_block) in votes {
+            // the unlock time is not stored, the vote time is used instead
+            if !locked {
+                pending_actions.push(CreatePendingAction::UnlockVote {
+                    dao_root: dao_root.clone(),
+                    proposal_id,
+                    voter,
+                    timestamp_block,
+                });
+            }
+            days.insert((dao_root.clone(), timestamp_block));
+            tallies.insert((dao_root, proposal_id));
+        }
+
+        for mut x in proposals.into_iter().map(RowReader::<Postgres>::from_row) {
+            let dao_root: String = x.read_next();
+            let proposal_address: String = x.read_next();
+            let timestamp_block: i32 = x.read_next();
+            let execution_time: i64 = x.read_next();
+            let queued_at: Option<i32> = x.read_next();
+            let executed_at: Option<i32> = x.read_next();
+            let canceled_at: Option<i32> = x.read_next();
+
+            if let Some(timestamp_block) = queued_at {
+                pending_actions.push(CreatePendingAction::Queued {
+                    proposal_address: proposal_address.clone(),
+                    timestamp_block,
+                    execution_time,
+                });
+            }
+            if let Some(timestamp_block) = executed_at {
+                pending_actions.push(CreatePendingAction::Executed {
+                    proposal_address: proposal_address.clone(),
+                    timestamp_block,
+                });
+            }
+            if let Some(timestamp_block) = canceled_at {
+                pending_actions.push(CreatePendingAction::Canceled {
+                    proposal_address,
+                    timestamp_block,
+                });
+            }
+            days.insert((dao_root, timestamp_block));
+        }
+
+        for action in pending_actions {
+            self.create_pending_action(&mut tx, action).await?;
+        }
+        // replayed votes are added to the tallies again
+        for (dao_root, proposal_id) in tallies {
+            self.recompute_proposal_votes(&mut tx, dao_root, proposal_id)
+                .await?;
+        }
+        for (dao_root, timestamp_block) in days {
+            self.update_daily_voting_stats(&mut tx, &dao_root, timestamp_block)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+fn raw_transaction_filters(filters: RawTransactionFilters, args_len: &mut u32) -> impl QueryPart {
+    WhereAndConditions((
+        filters.timestamp_block_ge.map(|time| {
+            *args_len += 1;
+            (format!("timestamp_block >= ${}", *args_len), time)
+        }),
+        filters.timestamp_block_le.map(|time| {
+            *args_len += 1;
+            (format!("timestamp_block <= ${}", *args_len), time)
+        }),
+        filters.timestamp_lt_ge.map(|lt| {
+            *args_len += 1;
+            (format!("timestamp_lt >= ${}", *args_len), lt)
+        }),
+        filters.timestamp_lt_le.map(|lt| {
+            *args_len += 1;
+            (format!("timestamp_lt <= ${}", *args_len), lt)
+        }),
+    ))
+}
+
+fn push_failed_raw_transaction_filters(
+    query: &mut OwnedPartBuilder,
+    filters: FailedRawTransactionFilters,
+    args_len: &mut u32,
+) {
+    match filters.state {
+        Some(state) => {
+            *args_len += 1;
+            query.push_with_arg(format!("WHERE state = ${}", *args_len), state);
+        }
+        None => {
+            query.push("WHERE state IN ('Fail', 'DeadLetter')");
+        }
+    }
+}
+
+fn read_failed_raw_transaction(mut x: RowReader<sqlx::Postgres>) -> FailedRawTransactionFromDb {
+    FailedRawTransactionFromDb {
+        transaction: x.read_next(),
+        transaction_hash: x.read_next(),
+        timestamp_block: x.read_next(),
+        timestamp_lt: x.read_next(),
+        created_at: x.read_next(),
+        state: x.read_next(),
+        attempts: x.read_next(),
+        last_error: x.read_next(),
+        next_retry_at: x.read_next(),
+    }
 }
@@ -0,0 +1,159 @@
+use anyhow::Result;
+use sqlx::{Postgres, Transaction};
+
+use crate::models::*;
+use crate::sqlx_client::*;
+use crate::utils::*;
+
+const SECONDS_PER_DAY: i32 = 86400;
+
+impl SqlxClient {
+    /// Recomputes the daily aggregates of the day containing `timestamp_block`
+    pub async fn update_daily_voting_stats(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        dao_root: &str,
+        timestamp_block: i32,
+    ) -> Result<()> {
+        let day_start = timestamp_block - timestamp_block.rem_euclid(SECONDS_PER_DAY);
+
+        sqlx::query(
+            r#"INSERT INTO daily_voting_stats (dao_root, day_start, votes_count, proposals_count, voters_count, vote_weight)
+            SELECT $1, $2, votes.votes_count, proposals.proposals_count, votes.voters_count, votes.vote_weight
+            FROM (SELECT COUNT(*) AS votes_count, COUNT(DISTINCT voter) AS voters_count, COALESCE(SUM(votes), 0) AS vote_weight
+                  FROM votes WHERE dao_root = $1 AND timestamp_block >= $2 AND timestamp_block < $3) AS votes,
+                 (SELECT COUNT(*) AS proposals_count
+                  FROM proposals WHERE dao_root = $1 AND timestamp_block >= $2 AND timestamp_block < $3) AS proposals
+            ON CONFLICT (dao_root, day_start) DO UPDATE SET
+                votes_count = EXCLUDED.votes_count,
+                proposals_count = EXCLUDED.proposals_count,
+                voters_count = EXCLUDED.voters_count,
+                vote_weight = EXCLUDED.vote_weight"#,
+        )
+        .bind(dao_root)
+        .bind(day_start)
+        .bind(day_start + SECONDS_PER_DAY)
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns a point for every bucket of the range, empty buckets included
+    pub async fn get_timeseries(
+        &self,
+        filters: TimeseriesFilters,
+    ) -> Result<Vec<TimeseriesPointFromDb>> {
+        let (first, last) = filters.bucket_range()?;
+
+        let (query, args) = if filters.is_daily_aggregate() {
+            daily_timeseries_query(filters, first, last)
+        } else {
+            raw_timeseries_query(filters, first, last)
+        }
+        .split();
+
+        let points = sqlx::query_with(&query, args)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(RowReader::from_row)
+            .map(|mut x| TimeseriesPointFromDb {
+                bucket: x.read_next(),
+                votes_count: x.read_next(),
+                proposals_count: x.read_next(),
+                voters_count: x.read_next(),
+                vote_weight: x.read_next(),
+            })
+            .collect();
+
+        Ok(points)
+    }
+}
+
+fn daily_timeseries_query(filters: TimeseriesFilters, first: i32, last: i32) -> OwnedPartBuilder {
+    let mut query = OwnedPartBuilder::new().starts_with(
+        "SELECT buckets.bucket, \
+            COALESCE(stats.votes_count, 0)::BIGINT, \
+            COALESCE(stats.proposals_count, 0)::BIGINT, \
+            COALESCE(stats.voters_count, 0)::BIGINT, \
+            COALESCE(stats.vote_weight, 0) \
+        FROM generate_series($1, $2, $3) AS buckets (bucket) \
+        LEFT JOIN daily_voting_stats AS stats \
+            ON stats.day_start = buckets.bucket AND stats.dao_root = $4 \
+        ORDER BY buckets.bucket",
+    );
+    query
+        .push_arg(first)
+        .push_arg(last)
+        .push_arg(filters.interval.size())
+        .push_arg(filters.dao_root.unwrap_or_default());
+
+    query
+}
+
+/// Votes and proposals are bucketed separately, the voter filter matches
+/// proposers of the proposals
+fn raw_timeseries_query(filters: TimeseriesFilters, first: i32, last: i32) -> OwnedPartBuilder {
+    let size = filters.interval.size();
+
+    let mut query = OwnedPartBuilder::new();
+    query
+        .push_arg(first)
+        .push_arg(last)
+        .push_arg(size)
+        .push_arg(filters.interval.offset())
+        .push_arg(last + size);
+    let mut args_len = 5;
+
+    let mut vote_conditions = String::new();
+    let mut proposal_conditions = String::new();
+    if let Some(dao_root) = filters.dao_root {
+        args_len += 1;
+        query.push_arg(dao_root);
+        vote_conditions += &format!(" AND dao_root = ${}", args_len);
+        proposal_conditions += &format!(" AND dao_root = ${}", args_len);
+    }
+    if let Some(proposal_id) = filters.proposal_id {
+        args_len += 1;
+        query.push_arg(proposal_id);
+        vote_conditions += &format!(" AND proposal_id = ${}", args_len);
+        proposal_conditions += &format!(" AND id = ${}", args_len);
+    }
+    if let Some(voter) = filters.voter {
+        args_len += 1;
+        query.push_arg(voter);
+        vote_conditions += &format!(" AND voter = ${}", args_len);
+        proposal_conditions += &format!(" AND proposer = ${}", args_len);
+    }
+
+    query.push(format!(
+        "WITH vote_buckets AS ( \
+            SELECT timestamp_block - (timestamp_block - $4) % $3 AS bucket, \
+                COUNT(*) AS votes_count, \
+                COUNT(DISTINCT voter) AS voters_count, \
+                SUM(votes) AS vote_weight \
+            FROM votes \
+            WHERE timestamp_block >= $1 AND timestamp_block < $5{} \
+            GROUP BY 1 \
+        ), proposal_buckets AS ( \
+            SELECT timestamp_block - (timestamp_block - $4) % $3 AS bucket, \
+                COUNT(*) AS proposals_count \
+            FROM proposals \
+            WHERE timestamp_block >= $1 AND timestamp_block < $5{} \
+            GROUP BY 1 \
+        ) \
+        SELECT buckets.bucket, \
+            COALESCE(vote_buckets.votes_count, 0), \
+            COALESCE(proposal_buckets.proposals_count, 0), \
+            COALESCE(vote_buckets.voters_count, 0), \
+            COALESCE(vote_buckets.vote_weight, 0) \
+        FROM generate_series($1, $2, $3) AS buckets (bucket) \
+        LEFT JOIN vote_buckets USING (bucket) \
+        LEFT JOIN proposal_buckets USING (bucket) \
+        ORDER BY buckets.bucket",
+        vote_conditions, proposal_conditions
+    ));
+
+    query
+}
@@ -5,6 +5,14 @@ use crate::models::*;
 use crate::sqlx_client::*;
 use crate::utils::*;
 
+/// Makes the order of the voter's votes deterministic
+const VOTERS_TIE_BREAKER: [&str; 4] = [
+    "votes.timestamp_block",
+    "votes.proposal_id",
+    "votes.voter",
+    "votes.dao_root",
+];
+
 impl SqlxClient {
     pub async fn search_proposals_with_votes(
         &self,
@@ -18,15 +26,31 @@ impl SqlxClient {
             proposals.voting_delay, proposals.for_votes, proposals.against_votes, proposals.quorum_votes, \
             proposals.message_hash, proposals.transaction_hash, proposals.timestamp_block, proposals.actions, \
             proposals.executed, proposals.canceled, proposals.queued, proposals.executed_at, proposals.canceled_at, \
-            proposals.queued_at, proposals.updated_at, proposals.created_at,\
+            proposals.queued_at, proposals.updated_at, proposals.created_at, proposals.dao_root, \
             votes.proposal_id, votes.voter, votes.support, votes.reason, votes.votes, votes.locked, votes.message_hash, \
-            votes.transaction_hash, votes.timestamp_block, votes.created_at \
-            FROM proposals INNER JOIN votes on proposals.id = votes.proposal_id");
+            votes.transaction_hash, votes.timestamp_block, votes.created_at, votes.dao_root \
+            FROM proposals INNER JOIN votes on proposals.dao_root = votes.dao_root AND proposals.id = votes.proposal_id");
 
         let mut args_len = 0;
 
+        if input.cursor.is_some() && !input.supports_cursor() {
+            anyhow::bail!("Cursor is only supported for the createdAt ordering");
+        }
+        let cursor = input
+            .cursor
+            .as_deref()
+            .map(decode_cursor::<VoteCursor>)
+            .transpose()?
+            .map(|cursor| (cursor, input.data.ordering.unwrap_or_default().direction));
+        let offset = if cursor.is_some() { 0 } else { input.offset };
+
         query
-            .push_part(voter_filters(address, input.data.filters, &mut args_len))
+            .push_part(voter_filters(
+                address,
+                input.data.filters,
+                cursor,
+                &mut args_len,
+            ))
             .push(voters_ordering(input.data.ordering))
             .push_with_arg(
                 {
@@ -44,7 +68,7 @@ impl SqlxClient {
                         args_len
                     })
                 },
-                input.offset,
+                offset,
             );
 
         let (query, args) = query.split();
@@ -79,6 +103,7 @@ impl SqlxClient {
                     queued_at: x.read_next(),
                     updated_at: x.read_next(),
                     created_at: x.read_next(),
+                    dao_root: x.read_next(),
                 },
                 VoteFromDb {
                     proposal_id: x.read_next(),
@@ -91,6 +116,7 @@ impl SqlxClient {
                     transaction_hash: x.read_next(),
                     timestamp_block: x.read_next(),
                     created_at: x.read_next(),
+                    dao_root: x.read_next(),
                 },
             )
         }))
@@ -104,10 +130,10 @@ impl SqlxClient {
         let mut args_len = 0;
 
         let mut query = OwnedPartBuilder::new().starts_with(
-            "SELECT COUNT(*) FROM proposals INNER JOIN votes on proposals.id = votes.proposal_id",
+            "SELECT COUNT(*) FROM proposals INNER JOIN votes on proposals.dao_root = votes.dao_root AND proposals.id = votes.proposal_id",
         );
 
-        query.push_part(voter_filters(address, input, &mut args_len));
+        query.push_part(voter_filters(address, input, None, &mut args_len));
 
         let (query, args) = query.split();
 
@@ -130,12 +156,26 @@ impl SqlxClient {
 
         let mut args_len = 0;
 
-        if let Some(voters) = input.data.filters.voters {
-            let mut first = true;
-            for voter in voters {
-                if std::mem::take(&mut first) {
-                    query.push("WHERE");
-                } else {
+        let mut first = true;
+        if let Some(dao_root) = input.data.filters.dao_root {
+            first = false;
+            query.push_with_arg(
+                format!("WHERE dao_root = ${}", {
+                    args_len += 1;
+                    args_len
+                }),
+                dao_root,
+            );
+        }
+
+        if let Some(voters) = input.data.filters.voters.filter(|x| !x.is_empty()) {
+            if std::mem::take(&mut first) {
+                query.push("WHERE (");
+            } else {
+                query.push("AND (");
+            }
+            for (i, voter) in voters.into_iter().enumerate() {
+                if i > 0 {
                     query.push("OR");
                 }
                 query.push_with_arg(
@@ -146,6 +186,7 @@ impl SqlxClient {
                     voter,
                 );
             }
+            query.push(")");
         }
         query.push("GROUP BY voter");
 
@@ -183,12 +224,34 @@ impl SqlxClient {
     }
 }
 
-fn voter_filters(address: String, filters: VoterFilters, args_len: &mut u32) -> impl QueryPart {
+fn voter_filters(
+    address: String,
+    filters: VoterFilters,
+    cursor: Option<(VoteCursor, Direction)>,
+    args_len: &mut u32,
+) -> impl QueryPart {
     WhereAndConditions((
         {
             *args_len += 1;
             Some((format!("voter = ${}", *args_len), address))
         },
+        cursor.map(|(cursor, direction)| {
+            keyset_condition(
+                "votes.timestamp_block, votes.proposal_id, votes.voter, votes.dao_root",
+                direction,
+                vec![
+                    CustomBuildType::Int(cursor.timestamp_block.into()),
+                    CustomBuildType::Int(cursor.proposal_id.into()),
+                    CustomBuildType::String(cursor.voter),
+                    CustomBuildType::String(cursor.dao_root),
+                ],
+                args_len,
+            )
+        }),
+        filters.dao_root.map(|dao_root| {
+            *args_len += 1;
+            (format!("votes.dao_root = ${}", *args_len), dao_root)
+        }),
         filters.start_time_ge.map(|time| {
             *args_len += 1;
             (format!("start_time >= ${}", *args_len), time)
@@ -330,13 +393,16 @@ fn voter_filters(address: String, filters: VoterFilters, args_len: &mut u32) ->
     ))
 }
 
-fn voters_ordering(ordering: Option<VotersOrdering>) -> &'static str {
+fn voters_ordering(ordering: Option<VotersOrdering>) -> String {
     let VotersOrdering { column, direction } = ordering.unwrap_or_default();
 
-    match (column, direction) {
-        (VoterColumn::CreatedAt, Direction::Ascending) => "ORDER BY votes.timestamp_block",
-        (VoterColumn::CreatedAt, Direction::Descending) => "ORDER BY votes.timestamp_block DESC",
-    }
+    let column = match column {
+        VoterColumn::CreatedAt => None,
+        VoterColumn::Votes => Some("votes.votes"),
+        VoterColumn::EndTime => Some("proposals.end_time"),
+    };
+
+    ordering_clause(column, &VOTERS_TIE_BREAKER, direction)
 }
 
 fn voters_proposals_ordering(ordering: VotersProposalsOrdering) -> &'static str {
@@ -347,7 +413,3 @@ fn voters_proposals_ordering(ordering: VotersProposalsOrdering) -> &'static str
         (VotersProposalColumn::Count, Direction::Descending) => "ORDER BY count DESC",
     }
 }
-
-fn max_limit(limit: i32) -> i32 {
-    std::cmp::min(limit, 100)
-}
@@ -1,40 +1,90 @@
 use anyhow::Result;
+use sqlx::{Postgres, Transaction};
 
 use crate::models::*;
 use crate::sqlx_client::*;
 use crate::utils::*;
 
+/// Makes the votes order deterministic
+const VOTES_TIE_BREAKER: [&str; 4] = ["timestamp_block", "proposal_id", "voter", "dao_root"];
+
 impl SqlxClient {
-    pub async fn create_vote(&self, vote: CreateVote) -> Result<()> {
+    /// Returns `false` if the vote is already stored
+    pub async fn create_vote(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        vote: CreateVote,
+    ) -> Result<bool> {
+        let cast_event = CreateGovernanceEvent::vote_cast(&vote);
         let locked = true;
 
-        sqlx::query!(
-            r#"INSERT INTO votes (proposal_id, voter, support, reason, votes, message_hash, transaction_hash, timestamp_block, locked)
-                          VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
-            vote.proposal_id,
-            vote.voter,
-            vote.support,
-            vote.reason,
-            vote.votes,
-            vote.message_hash,
-            vote.transaction_hash,
-            vote.timestamp_block,
-            locked
+        let result = sqlx::query(
+            r#"INSERT INTO votes (proposal_id, voter, support, reason, votes, message_hash, transaction_hash, timestamp_block, locked, dao_root)
+                          VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                          ON CONFLICT DO NOTHING"#,
+        )
+        .bind(vote.proposal_id)
+        .bind(&vote.voter)
+        .bind(vote.support)
+        .bind(vote.reason)
+        .bind(vote.votes)
+        .bind(vote.message_hash)
+        .bind(vote.transaction_hash)
+        .bind(vote.timestamp_block)
+        .bind(locked)
+        .bind(&vote.dao_root)
+        .execute(&mut *tx)
+        .await?;
+
+        // a duplicate vote keeps the pending unlock for the stored one
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        self.update_daily_voting_stats(tx, &vote.dao_root, vote.timestamp_block)
+            .await?;
+        self.create_governance_event(tx, cast_event).await?;
+
+        // the vote is already unlocked if its unlock arrived earlier
+        let pending_unlock: Option<(i32,)> = sqlx::query_as(
+            "DELETE FROM pending_actions \
+            WHERE kind = 'UnlockVote' AND dao_root = $1 AND proposal_id = $2 AND voter = $3 \
+            RETURNING timestamp_block",
         )
-            .execute(&self.pool)
+        .bind(&vote.dao_root)
+        .bind(vote.proposal_id)
+        .bind(&vote.voter)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some((timestamp_block,)) = pending_unlock {
+            let unlock = UnlockVote {
+                dao_root: vote.dao_root,
+                proposal_id: vote.proposal_id,
+                voter: vote.voter,
+            };
+            self.unlock_vote(tx, unlock.clone()).await?;
+            self.create_governance_event(
+                tx,
+                CreateGovernanceEvent::vote_unlocked(&unlock, timestamp_block),
+            )
             .await?;
+        }
 
-        Ok(())
+        Ok(true)
     }
 
-    pub async fn unlock_vote(&self, vote: UnlockVote) -> Result<VoteFromDb> {
+    pub async fn unlock_vote(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        vote: UnlockVote,
+    ) -> Result<Option<VoteFromDb>> {
         let locked = false;
 
-        sqlx::query_as!(
-            VoteFromDb,
+        sqlx::query(
             r#"
             UPDATE votes SET locked = $1
-            WHERE proposal_id = $2 AND voter = $3
+            WHERE dao_root = $2 AND proposal_id = $3 AND voter = $4
             RETURNING proposal_id,
                 voter,
                 support,
@@ -44,32 +94,59 @@ impl SqlxClient {
                 message_hash,
                 transaction_hash,
                 timestamp_block,
-                created_at"#,
-            locked,
-            vote.proposal_id,
-            vote.voter,
+                created_at,
+                dao_root"#,
         )
-        .fetch_one(&self.pool)
+        .bind(locked)
+        .bind(vote.dao_root)
+        .bind(vote.proposal_id)
+        .bind(vote.voter)
+        .fetch_optional(&mut *tx)
         .await
+        .map(|x| x.map(|x| read_vote(&mut RowReader::from_row(x))))
         .map_err(From::from)
     }
 
     pub async fn search_votes(
         &self,
         input: VotesSearch,
-    ) -> Result<impl Iterator<Item = VoteFromDb> + Send + Sync> {
+    ) -> Result<impl Iterator<Item = (VoteFromDb, Option<SearchMatchFromDb>)> + Send + Sync> {
         let mut args_len = 0;
 
         let mut query = OwnedPartBuilder::new().starts_with(
             "SELECT \
                 proposal_id, voter, support, reason, votes, locked, message_hash, transaction_hash, \
-                timestamp_block, created_at \
-            FROM votes",
+                timestamp_block, created_at, dao_root",
         );
 
+        let ordering = if input.is_ordered_by_rank() {
+            ordering_clause(
+                Some("search_rank"),
+                &VOTES_TIE_BREAKER,
+                Direction::Descending,
+            )
+        } else {
+            votes_ordering(input.data.ordering)
+        };
+
+        if input.cursor.is_some() && !input.supports_cursor() {
+            anyhow::bail!("Cursor is only supported for the createdAt ordering");
+        }
+        let cursor = input
+            .cursor
+            .as_deref()
+            .map(decode_cursor::<VoteCursor>)
+            .transpose()?
+            .map(|cursor| (cursor, input.data.ordering.unwrap_or_default().direction));
+        let offset = if cursor.is_some() { 0 } else { input.offset };
+
+        let search = input.data.filters.query.clone();
+        push_search_match_columns(&mut query, "reason", search, &mut args_len);
+
         query
-            .push_part(vote_filters(input.data.filters, &mut args_len))
-            .push(votes_ordering(input.data.ordering))
+            .push("FROM votes")
+            .push_part(vote_filters(input.data.filters, cursor, &mut args_len))
+            .push(ordering)
             .push_with_arg(
                 {
                     format!("LIMIT ${}", {
@@ -86,28 +163,63 @@ impl SqlxClient {
                         args_len
                     })
                 },
-                input.offset,
+                offset,
             );
 
         let (query, args) = query.split();
 
         let votes = sqlx::query_with(&query, args).fetch_all(&self.pool).await?;
 
-        Ok(votes
-            .into_iter()
-            .map(RowReader::from_row)
-            .map(|mut x| VoteFromDb {
-                proposal_id: x.read_next(),
-                voter: x.read_next(),
-                support: x.read_next(),
-                reason: x.read_next(),
-                votes: x.read_next(),
-                locked: x.read_next(),
-                message_hash: x.read_next(),
-                transaction_hash: x.read_next(),
-                timestamp_block: x.read_next(),
-                created_at: x.read_next(),
-            }))
+        Ok(votes.into_iter().map(RowReader::from_row).map(|mut x| {
+            let vote = read_vote(&mut x);
+            (vote, read_search_match(&mut x))
+        }))
+    }
+
+    pub async fn proposal_votes_count(&self, dao_root: String, proposal_id: i32) -> Result<i64> {
+        sqlx::query_as::<_, (i64,)>(
+            "SELECT COUNT(*) FROM votes WHERE dao_root = $1 AND proposal_id = $2",
+        )
+        .bind(dao_root)
+        .bind(proposal_id)
+        .fetch_one(&self.pool)
+        .await
+        .map(|x| x.0)
+        .map_err(From::from)
+    }
+
+    /// Running totals of the proposal votes, one row per block time
+    pub async fn proposal_tally_history(
+        &self,
+        dao_root: String,
+        proposal_id: i32,
+    ) -> Result<Vec<ProposalTallyFromDb>> {
+        sqlx::query(
+            "SELECT timestamp_block, \
+                SUM(COALESCE(SUM(votes) FILTER (WHERE support), 0)) OVER w, \
+                SUM(COALESCE(SUM(votes) FILTER (WHERE NOT support), 0)) OVER w, \
+                (SUM(COUNT(*)) OVER w)::BIGINT \
+            FROM votes WHERE dao_root = $1 AND proposal_id = $2 \
+            GROUP BY timestamp_block \
+            WINDOW w AS (ORDER BY timestamp_block) \
+            ORDER BY timestamp_block",
+        )
+        .bind(dao_root)
+        .bind(proposal_id)
+        .fetch_all(&self.pool)
+        .await
+        .map(|rows| {
+            rows.into_iter()
+                .map(RowReader::from_row)
+                .map(|mut x| ProposalTallyFromDb {
+                    timestamp_block: x.read_next(),
+                    for_votes: x.read_next(),
+                    against_votes: x.read_next(),
+                    voters_count: x.read_next(),
+                })
+                .collect()
+        })
+        .map_err(From::from)
     }
 
     pub async fn votes_total_count(&self, input: VoteFilters) -> Result<i64> {
@@ -115,7 +227,7 @@ impl SqlxClient {
 
         let mut query = OwnedPartBuilder::new().starts_with("SELECT COUNT(*) FROM votes");
 
-        query.push_part(vote_filters(input, &mut args_len));
+        query.push_part(vote_filters(input, None, &mut args_len));
 
         let (query, args) = query.split();
 
@@ -128,10 +240,121 @@ impl SqlxClient {
 
         Ok(total_count)
     }
+
+    /// Pages of several searches in a single query, in the order of `inputs`.
+    /// Cursors and ordering by relevance are not supported
+    pub async fn search_votes_batch(
+        &self,
+        inputs: Vec<VotesSearch>,
+    ) -> Result<Vec<Vec<VoteFromDb>>> {
+        let mut pages = inputs.iter().map(|_| Vec::new()).collect::<Vec<_>>();
+        if inputs.is_empty() {
+            return Ok(pages);
+        }
+
+        let mut args_len = 0;
+
+        let mut query = OwnedPartBuilder::new().starts_with(
+            "SELECT batch_index, \
+                proposal_id, voter, support, reason, votes, locked, message_hash, transaction_hash, \
+                timestamp_block, created_at, dao_root \
+            FROM (",
+        );
+
+        for (index, input) in inputs.into_iter().enumerate() {
+            if input.cursor.is_some() {
+                anyhow::bail!("Cursor is not supported for batched votes");
+            }
+
+            let ordering = votes_ordering(input.data.ordering);
+            if index > 0 {
+                query.push("UNION ALL");
+            }
+
+            query
+                .push(format!(
+                    "(SELECT {} AS batch_index, ROW_NUMBER() OVER ({}) AS batch_row, \
+                    proposal_id, voter, support, reason, votes, locked, message_hash, transaction_hash, \
+                    timestamp_block, created_at, dao_root \
+                    FROM votes",
+                    index, ordering
+                ))
+                .push_part(vote_filters(input.data.filters, None, &mut args_len))
+                .push(ordering)
+                .push_with_arg(
+                    {
+                        format!("LIMIT ${}", {
+                            args_len += 1;
+                            args_len
+                        })
+                    },
+                    max_limit(input.limit),
+                )
+                .push_with_arg(
+                    {
+                        format!("OFFSET ${})", {
+                            args_len += 1;
+                            args_len
+                        })
+                    },
+                    input.offset,
+                );
+        }
+
+        query.push(") batch ORDER BY batch_index, batch_row");
+
+        let (query, args) = query.split();
+
+        let votes = sqlx::query_with(&query, args).fetch_all(&self.pool).await?;
+
+        for mut x in votes.into_iter().map(RowReader::from_row) {
+            let index: i32 = x.read_next();
+            pages[index as usize].push(read_vote(&mut x));
+        }
+
+        Ok(pages)
+    }
+}
+
+fn read_vote(x: &mut RowReader<sqlx::Postgres>) -> VoteFromDb {
+    VoteFromDb {
+        proposal_id: x.read_next(),
+        voter: x.read_next(),
+        support: x.read_next(),
+        reason: x.read_next(),
+        votes: x.read_next(),
+        locked: x.read_next(),
+        message_hash: x.read_next(),
+        transaction_hash: x.read_next(),
+        timestamp_block: x.read_next(),
+        created_at: x.read_next(),
+        dao_root: x.read_next(),
+    }
 }
 
-fn vote_filters(filters: VoteFilters, args_len: &mut u32) -> impl QueryPart {
+fn vote_filters(
+    filters: VoteFilters,
+    cursor: Option<(VoteCursor, Direction)>,
+    args_len: &mut u32,
+) -> impl QueryPart {
     WhereAndConditions((
+        cursor.map(|(cursor, direction)| {
+            keyset_condition(
+                "timestamp_block, proposal_id, voter, dao_root",
+                direction,
+                vec![
+                    CustomBuildType::Int(cursor.timestamp_block.into()),
+                    CustomBuildType::Int(cursor.proposal_id.into()),
+                    CustomBuildType::String(cursor.voter),
+                    CustomBuildType::String(cursor.dao_root),
+                ],
+                args_len,
+            )
+        }),
+        filters.dao_root.map(|dao_root| {
+            *args_len += 1;
+            (format!("dao_root = ${}", *args_len), dao_root)
+        }),
         filters.voter.map(|address| {
             *args_len += 1;
             (format!("voter = ${}", *args_len), address)
@@ -148,18 +371,35 @@ fn vote_filters(filters: VoteFilters, args_len: &mut u32) -> impl QueryPart {
             *args_len += 1;
             (format!("locked = ${}", *args_len), locked)
         }),
+        filters
+            .query
+            .map(|query| search_query_filter(query, args_len)),
     ))
 }
 
-fn votes_ordering(ordering: Option<VotesOrdering>) -> &'static str {
+fn votes_ordering(ordering: Option<VotesOrdering>) -> String {
     let VotesOrdering { column, direction } = ordering.unwrap_or_default();
 
-    match (column, direction) {
-        (VoteColumn::CreatedAt, Direction::Ascending) => "ORDER BY timestamp_block",
-        (VoteColumn::CreatedAt, Direction::Descending) => "ORDER BY timestamp_block DESC",
+    match column {
+        VoteColumn::CreatedAt => ordering_clause(None, &VOTES_TIE_BREAKER, direction),
+        VoteColumn::Votes => ordering_clause(Some("votes"), &VOTES_TIE_BREAKER, direction),
     }
 }
 
-fn max_limit(limit: i32) -> i32 {
-    std::cmp::min(limit, 100)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn orderings_match_indexes(pool: PgPool) {
+        for column in [VoteColumn::CreatedAt, VoteColumn::Votes] {
+            for direction in [Direction::Ascending, Direction::Descending] {
+                let query = format!(
+                    "SELECT * FROM votes {} LIMIT 10",
+                    votes_ordering(Some(VotesOrdering { column, direction }))
+                );
+                assert!(is_ordered_by_index(&pool, &query).await, "{}", query);
+            }
+        }
+    }
 }
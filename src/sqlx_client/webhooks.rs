@@ -0,0 +1,284 @@
+use anyhow::Result;
+use chrono::Utc;
+use sqlx::{Postgres, Transaction};
+
+use crate::models::*;
+use crate::sqlx_client::*;
+use crate::utils::*;
+
+const WEBHOOK_COLUMNS: &str = "id, url, secret, dao_root, kinds, min_vote_weight, created_at";
+
+const WEBHOOK_DELIVERY_COLUMNS: &str = "id, webhook_id, event_id, payload, state, attempts, \
+    last_error, response_status, next_attempt_at, created_at, updated_at";
+
+impl SqlxClient {
+    pub async fn create_webhook(&self, webhook: CreateWebhook) -> Result<WebhookFromDb> {
+        let webhook = sqlx::query(&format!(
+            "INSERT INTO webhooks (url, secret, dao_root, kinds, min_vote_weight) \
+            VALUES ($1, $2, $3, $4, $5) \
+            RETURNING {}",
+            WEBHOOK_COLUMNS
+        ))
+        .bind(webhook.url)
+        .bind(webhook.secret)
+        .bind(webhook.dao_root)
+        .bind(webhook.kinds)
+        .bind(webhook.min_vote_weight)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(read_webhook(RowReader::from_row(webhook)))
+    }
+
+    pub async fn get_webhooks(&self) -> Result<Vec<WebhookFromDb>> {
+        let webhooks = sqlx::query(&format!(
+            "SELECT {} FROM webhooks ORDER BY id",
+            WEBHOOK_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(webhooks
+            .into_iter()
+            .map(RowReader::from_row)
+            .map(read_webhook)
+            .collect())
+    }
+
+    /// Deliveries of the webhook are removed with it
+    pub async fn delete_webhook(&self, id: i64) -> Result<Option<WebhookFromDb>> {
+        let webhook = sqlx::query(&format!(
+            "DELETE FROM webhooks WHERE id = $1 RETURNING {}",
+            WEBHOOK_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(webhook.map(RowReader::from_row).map(read_webhook))
+    }
+
+    /// Schedules the event for every matching webhook. The payload has the
+    /// same shape as the events stream data
+    pub async fn enqueue_webhook_deliveries(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        event_id: i64,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"INSERT INTO webhook_deliveries (webhook_id, event_id, payload)
+            SELECT webhooks.id, events.id, jsonb_build_object(
+                'id', events.id,
+                'kind', events.kind,
+                'daoRoot', events.dao_root,
+                'proposalId', events.proposal_id,
+                'voter', events.voter,
+                'data', events.data,
+                'createdAt', events.timestamp_block)
+            FROM governance_events AS events
+            JOIN webhooks
+                ON (webhooks.dao_root IS NULL OR webhooks.dao_root = events.dao_root)
+                AND (cardinality(webhooks.kinds) = 0 OR events.kind = ANY(webhooks.kinds))
+                AND (events.kind <> 'VoteCast' OR webhooks.min_vote_weight IS NULL
+                    OR (events.data->>'votes')::NUMERIC >= webhooks.min_vote_weight)
+            WHERE events.id = $1
+            ON CONFLICT DO NOTHING"#,
+        )
+        .bind(event_id)
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_due_webhook_deliveries(
+        &self,
+        now: i64,
+        limit: i32,
+    ) -> Result<Vec<DueWebhookDeliveryFromDb>> {
+        let deliveries = sqlx::query(
+            "SELECT webhook_deliveries.id, event_id, url, secret, payload \
+            FROM webhook_deliveries \
+            JOIN webhooks ON webhooks.id = webhook_deliveries.webhook_id \
+            WHERE state IN ('Pending', 'Fail') AND next_attempt_at <= $1 \
+            ORDER BY next_attempt_at, webhook_deliveries.id \
+            LIMIT $2",
+        )
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(deliveries
+            .into_iter()
+            .map(RowReader::from_row)
+            .map(|mut x| DueWebhookDeliveryFromDb {
+                id: x.read_next(),
+                event_id: x.read_next(),
+                url: x.read_next(),
+                secret: x.read_next(),
+                payload: x.read_next(),
+            })
+            .collect())
+    }
+
+    pub async fn mark_webhook_delivered(&self, id: i64, response_status: i32) -> Result<()> {
+        sqlx::query(
+            "UPDATE webhook_deliveries SET \
+                state = 'Success', attempts = attempts + 1, response_status = $2, \
+                last_error = NULL, updated_at = $3 \
+            WHERE id = $1",
+        )
+        .bind(id)
+        .bind(response_status)
+        .bind(Utc::now().timestamp_millis())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Schedules a retry with the exponential backoff, returns the new state
+    pub async fn record_webhook_delivery_failure(
+        &self,
+        id: i64,
+        response_status: Option<i32>,
+        error: String,
+        retry_policy: RetryPolicy,
+    ) -> Result<Option<WebhookDeliveryState>> {
+        let now = Utc::now().timestamp_millis();
+
+        let state: Option<(WebhookDeliveryState,)> = sqlx::query_as(
+            r#"
+            UPDATE webhook_deliveries SET
+                attempts = attempts + 1,
+                response_status = $2,
+                last_error = $3,
+                state = CASE WHEN attempts + 1 >= $4
+                    THEN 'DeadLetter'::webhook_delivery_state_type
+                    ELSE 'Fail'::webhook_delivery_state_type END,
+                next_attempt_at = $5 + LEAST($6 * POWER(2, attempts), $7)::BIGINT,
+                updated_at = $5
+            WHERE id = $1
+            RETURNING state"#,
+        )
+        .bind(id)
+        .bind(response_status)
+        .bind(error)
+        .bind(retry_policy.max_attempts)
+        .bind(now)
+        .bind(retry_policy.base_delay_ms)
+        .bind(retry_policy.max_delay_ms)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(state.map(|(state,)| state))
+    }
+
+    pub async fn search_webhook_deliveries(
+        &self,
+        input: WebhookDeliveriesSearch,
+    ) -> Result<impl Iterator<Item = WebhookDeliveryFromDb> + Send + Sync> {
+        let mut query = OwnedPartBuilder::new().starts_with(format!(
+            "SELECT {} FROM webhook_deliveries",
+            WEBHOOK_DELIVERY_COLUMNS
+        ));
+
+        let mut args_len = 0;
+
+        query
+            .push_part(webhook_delivery_filters(input.data, &mut args_len))
+            .push("ORDER BY id DESC")
+            .push_with_arg(
+                {
+                    format!("LIMIT ${}", {
+                        args_len += 1;
+                        args_len
+                    })
+                },
+                max_limit(input.limit),
+            )
+            .push_with_arg(
+                {
+                    format!("OFFSET ${}", {
+                        args_len += 1;
+                        args_len
+                    })
+                },
+                input.offset,
+            );
+
+        let (query, args) = query.split();
+
+        let deliveries = sqlx::query_with(&query, args).fetch_all(&self.pool).await?;
+
+        Ok(deliveries
+            .into_iter()
+            .map(RowReader::from_row)
+            .map(read_webhook_delivery))
+    }
+
+    pub async fn webhook_deliveries_total_count(
+        &self,
+        input: WebhookDeliveryFilters,
+    ) -> Result<i64> {
+        let mut query =
+            OwnedPartBuilder::new().starts_with("SELECT COUNT(*) FROM webhook_deliveries");
+
+        let mut args_len = 0;
+
+        query.push_part(webhook_delivery_filters(input, &mut args_len));
+
+        let (query, args) = query.split();
+
+        let total_count: i64 = sqlx::query_with(&query, args)
+            .fetch_one(&self.pool)
+            .await
+            .map(RowReader::from_row)
+            .map(|mut x| x.read_next())
+            .unwrap_or_default();
+
+        Ok(total_count)
+    }
+}
+
+fn read_webhook(mut x: RowReader<Postgres>) -> WebhookFromDb {
+    WebhookFromDb {
+        id: x.read_next(),
+        url: x.read_next(),
+        secret: x.read_next(),
+        dao_root: x.read_next(),
+        kinds: x.read_next(),
+        min_vote_weight: x.read_next(),
+        created_at: x.read_next(),
+    }
+}
+
+fn read_webhook_delivery(mut x: RowReader<Postgres>) -> WebhookDeliveryFromDb {
+    WebhookDeliveryFromDb {
+        id: x.read_next(),
+        webhook_id: x.read_next(),
+        event_id: x.read_next(),
+        payload: x.read_next(),
+        state: x.read_next(),
+        attempts: x.read_next(),
+        last_error: x.read_next(),
+        response_status: x.read_next(),
+        next_attempt_at: x.read_next(),
+        created_at: x.read_next(),
+        updated_at: x.read_next(),
+    }
+}
+
+fn webhook_delivery_filters(filters: WebhookDeliveryFilters, args_len: &mut u32) -> impl QueryPart {
+    WhereAndConditions((
+        filters.webhook_id.map(|webhook_id| {
+            *args_len += 1;
+            (format!("webhook_id = ${}", *args_len), webhook_id)
+        }),
+        filters.state.map(|state| {
+            *args_len += 1;
+            (format!("state = ${}", *args_len), state)
+        }),
+    ))
+}
@@ -0,0 +1,137 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::Result;
+use ton_block::MsgAddressInt;
+
+use super::*;
+use crate::models::*;
+use crate::sqlx_client::*;
+
+/// Stores answers of immutable getters in `known_contracts`
+/// so they are requested only once per contract
+pub struct CachedContractStateProvider {
+    inner: Arc<dyn ContractStateProvider>,
+    sqlx_client: SqlxClient,
+}
+
+impl CachedContractStateProvider {
+    pub fn new(inner: Arc<dyn ContractStateProvider>, sqlx_client: SqlxClient) -> Self {
+        Self { inner, sqlx_client }
+    }
+
+    async fn save_proposal(&self, proposal_address: &MsgAddressInt, dao_root: &MsgAddressInt) {
+        if let Err(err) = self
+            .sqlx_client
+            .create_known_contract(CreateKnownContract {
+                address: proposal_address.to_string(),
+                kind: KnownContractKind::Proposal,
+                dao_root: dao_root.to_string(),
+                owner: None,
+            })
+            .await
+        {
+            log::error!(
+                "Failed to save known proposal {}: {}",
+                proposal_address,
+                err
+            );
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ContractStateProvider for CachedContractStateProvider {
+    async fn get_dao_root(&self, proposal_address: &MsgAddressInt) -> Result<MsgAddressInt> {
+        if let Some(contract) = self
+            .sqlx_client
+            .get_known_contract(proposal_address.to_string(), KnownContractKind::Proposal)
+            .await?
+        {
+            return parse_address(&contract.dao_root);
+        }
+
+        let dao_root = self.inner.get_dao_root(proposal_address).await?;
+        self.save_proposal(proposal_address, &dao_root).await;
+
+        Ok(dao_root)
+    }
+
+    async fn expected_proposal_address(
+        &self,
+        dao_root: &MsgAddressInt,
+        proposal_id: u32,
+    ) -> Result<MsgAddressInt> {
+        let proposal_address = self
+            .inner
+            .expected_proposal_address(dao_root, proposal_id)
+            .await?;
+        self.save_proposal(&proposal_address, dao_root).await;
+
+        Ok(proposal_address)
+    }
+
+    async fn get_proposal_overview(
+        &self,
+        proposal_address: &MsgAddressInt,
+    ) -> Result<ProposalOverview> {
+        self.inner.get_proposal_overview(proposal_address).await
+    }
+
+    async fn get_proposal_config(
+        &self,
+        proposal_address: &MsgAddressInt,
+    ) -> Result<ProposalConfig> {
+        self.inner.get_proposal_config(proposal_address).await
+    }
+
+    async fn get_dao_config(&self, dao_root: &MsgAddressInt) -> Result<ProposalConfig> {
+        self.inner.get_dao_config(dao_root).await
+    }
+
+    async fn get_user_data_details(&self, user_data_address: &MsgAddressInt) -> Result<GetDetails> {
+        self.inner.get_user_data_details(user_data_address).await
+    }
+
+    async fn get_user_data_owner(
+        &self,
+        user_data_address: &MsgAddressInt,
+    ) -> Result<UserDataOwner> {
+        if let Some(contract) = self
+            .sqlx_client
+            .get_known_contract(user_data_address.to_string(), KnownContractKind::UserData)
+            .await?
+        {
+            if let Some(owner) = contract.owner {
+                return Ok(UserDataOwner {
+                    user: parse_address(&owner)?,
+                    dao_root: parse_address(&contract.dao_root)?,
+                });
+            }
+        }
+
+        let owner = self.inner.get_user_data_owner(user_data_address).await?;
+        if let Err(err) = self
+            .sqlx_client
+            .create_known_contract(CreateKnownContract {
+                address: user_data_address.to_string(),
+                kind: KnownContractKind::UserData,
+                dao_root: owner.dao_root.to_string(),
+                owner: Some(owner.user.to_string()),
+            })
+            .await
+        {
+            log::error!(
+                "Failed to save known user data {}: {}",
+                user_data_address,
+                err
+            );
+        }
+
+        Ok(owner)
+    }
+}
+
+fn parse_address(address: &str) -> Result<MsgAddressInt> {
+    MsgAddressInt::from_str(address).map_err(|e| anyhow::anyhow!("{}: {}", address, e))
+}
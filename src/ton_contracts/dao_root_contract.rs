@@ -1,5 +1,7 @@
 use nekoton_abi::*;
 
+use crate::models::*;
+
 /// External responsible function
 pub fn expected_proposal_address() -> &'static ton_abi::Function {
     crate::once!(ton_abi::Function, || {
@@ -10,3 +12,12 @@ pub fn expected_proposal_address() -> &'static ton_abi::Function {
             .build()
     })
 }
+
+pub fn proposal_configuration() -> &'static ton_abi::Function {
+    crate::once!(ton_abi::Function, || {
+        FunctionBuilder::new("proposalConfiguration")
+            .time_header()
+            .output("proposalConfiguration", ProposalConfig::param_type())
+            .build()
+    })
+}
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use ton_block::MsgAddressInt;
+use ton_types::UInt256;
+
+use super::*;
+use crate::models::*;
+
+/// In-memory contract states loaded from a JSON fixture, for tests and local runs
+///
+/// ```json
+/// {
+///   "proposals": [{
+///     "address": "0:...", "daoRoot": "0:...", "id": 1, "proposer": "0:...",
+///     "description": "", "startTime": 0, "endTime": 0, "executionTime": 0,
+///     "quorumVotes": "100",
+///     "config": {
+///       "votingDelay": 0, "votingPeriod": 0, "quorumVotes": "100",
+///       "timeLock": 0, "threshold": "10", "gracePeriod": 0
+///     }
+///   }],
+///   "userData": [{ "address": "0:...", "daoRoot": "0:...", "user": "0:..." }],
+///   "daoRoots": [{ "address": "0:...", "config": { ... } }]
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FixtureContractStateProvider {
+    proposals: HashMap<MsgAddressInt, ProposalFixture>,
+    user_data: HashMap<MsgAddressInt, UserDataFixture>,
+    dao_roots: HashMap<MsgAddressInt, DaoRootFixture>,
+}
+
+impl FixtureContractStateProvider {
+    pub fn from_json(data: &str) -> Result<Self> {
+        let fixture: Fixture = serde_json::from_str(data)?;
+
+        let mut provider = Self::default();
+        for proposal in fixture.proposals {
+            provider
+                .proposals
+                .insert(parse_address(&proposal.address)?, proposal);
+        }
+        for user_data in fixture.user_data {
+            provider
+                .user_data
+                .insert(parse_address(&user_data.address)?, user_data);
+        }
+        for dao_root in fixture.dao_roots {
+            provider
+                .dao_roots
+                .insert(parse_address(&dao_root.address)?, dao_root);
+        }
+
+        Ok(provider)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    fn proposal(&self, address: &MsgAddressInt) -> Result<&ProposalFixture> {
+        self.proposals
+            .get(address)
+            .with_context(|| format!("Unknown proposal {}", address))
+    }
+}
+
+#[async_trait::async_trait]
+impl ContractStateProvider for FixtureContractStateProvider {
+    async fn get_dao_root(&self, proposal_address: &MsgAddressInt) -> Result<MsgAddressInt> {
+        parse_address(&self.proposal(proposal_address)?.dao_root)
+    }
+
+    async fn expected_proposal_address(
+        &self,
+        dao_root: &MsgAddressInt,
+        proposal_id: u32,
+    ) -> Result<MsgAddressInt> {
+        let dao_root = dao_root.to_string();
+        self.proposals
+            .iter()
+            .find(|(_, x)| x.dao_root == dao_root && x.id == proposal_id)
+            .map(|(address, _)| address.clone())
+            .with_context(|| format!("Unknown proposal {} of {}", proposal_id, dao_root))
+    }
+
+    async fn get_proposal_overview(
+        &self,
+        proposal_address: &MsgAddressInt,
+    ) -> Result<ProposalOverview> {
+        let proposal = self.proposal(proposal_address)?;
+        Ok(ProposalOverview {
+            proposer: parse_address(&proposal.proposer)?,
+            description: proposal.description.clone(),
+            start_time: proposal.start_time,
+            end_time: proposal.end_time,
+            execution_time: proposal.execution_time,
+            for_votes: 0,
+            against_votes: 0,
+            quorum_votes: proposal.quorum_votes.parse()?,
+            state: 0,
+        })
+    }
+
+    async fn get_proposal_config(
+        &self,
+        proposal_address: &MsgAddressInt,
+    ) -> Result<ProposalConfig> {
+        self.proposal(proposal_address)?.config.parse()
+    }
+
+    async fn get_dao_config(&self, dao_root: &MsgAddressInt) -> Result<ProposalConfig> {
+        self.dao_roots
+            .get(dao_root)
+            .with_context(|| format!("Unknown dao root {}", dao_root))?
+            .config
+            .parse()
+    }
+
+    async fn get_user_data_details(&self, user_data_address: &MsgAddressInt) -> Result<GetDetails> {
+        let user_data = self
+            .user_data
+            .get(user_data_address)
+            .with_context(|| format!("Unknown user data {}", user_data_address))?;
+        Ok(GetDetails {
+            token_balance: 0,
+            relay_lock_until: 0,
+            current_version: 0,
+            reward_rounds: Vec::new(),
+            relay_eth_address: [0; 20],
+            eth_address_confirmed: false,
+            relay_ton_pubkey: UInt256::default(),
+            ton_pubkey_confirmed: false,
+            slashed: false,
+            root: MsgAddressInt::default(),
+            user: parse_address(&user_data.user)?,
+            dao_root: parse_address(&user_data.dao_root)?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Fixture {
+    #[serde(default)]
+    proposals: Vec<ProposalFixture>,
+    #[serde(default)]
+    user_data: Vec<UserDataFixture>,
+    #[serde(default)]
+    dao_roots: Vec<DaoRootFixture>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProposalFixture {
+    address: String,
+    dao_root: String,
+    id: u32,
+    proposer: String,
+    #[serde(default)]
+    description: String,
+    start_time: u32,
+    end_time: u32,
+    #[serde(default)]
+    execution_time: u32,
+    quorum_votes: String,
+    config: ProposalConfigFixture,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProposalConfigFixture {
+    voting_delay: u32,
+    voting_period: u32,
+    quorum_votes: String,
+    time_lock: u32,
+    threshold: String,
+    grace_period: u32,
+}
+
+impl ProposalConfigFixture {
+    fn parse(&self) -> Result<ProposalConfig> {
+        Ok(ProposalConfig {
+            voting_delay: self.voting_delay,
+            voting_period: self.voting_period,
+            quorum_votes: self.quorum_votes.parse()?,
+            time_lock: self.time_lock,
+            threshold: self.threshold.parse()?,
+            grace_period: self.grace_period,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UserDataFixture {
+    address: String,
+    dao_root: String,
+    user: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DaoRootFixture {
+    address: String,
+    config: ProposalConfigFixture,
+}
+
+fn parse_address(address: &str) -> Result<MsgAddressInt> {
+    MsgAddressInt::from_str(address).map_err(|e| anyhow::anyhow!("{}: {}", address, e))
+}
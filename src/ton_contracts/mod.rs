@@ -1,7 +1,13 @@
+pub use self::cached_state_provider::*;
 pub use self::dao_root_contract::*;
+pub use self::fixture_state_provider::*;
 pub use self::proposal_contract::*;
+pub use self::state_provider::*;
 pub use self::userdata_contract::*;
 
+mod cached_state_provider;
 mod dao_root_contract;
+mod fixture_state_provider;
 mod proposal_contract;
+mod state_provider;
 mod userdata_contract;
@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use nekoton_abi::*;
+use ton_block::MsgAddressInt;
+use transaction_consumer::TransactionConsumer;
+
+use super::*;
+use crate::models::*;
+use crate::utils::*;
+
+/// Contract getters used by the indexer
+#[async_trait::async_trait]
+pub trait ContractStateProvider: Send + Sync {
+    async fn get_dao_root(&self, proposal_address: &MsgAddressInt) -> Result<MsgAddressInt>;
+
+    async fn expected_proposal_address(
+        &self,
+        dao_root: &MsgAddressInt,
+        proposal_id: u32,
+    ) -> Result<MsgAddressInt>;
+
+    async fn get_proposal_overview(
+        &self,
+        proposal_address: &MsgAddressInt,
+    ) -> Result<ProposalOverview>;
+
+    async fn get_proposal_config(&self, proposal_address: &MsgAddressInt)
+        -> Result<ProposalConfig>;
+
+    /// Current proposal configuration of the DAO
+    async fn get_dao_config(&self, dao_root: &MsgAddressInt) -> Result<ProposalConfig>;
+
+    async fn get_user_data_details(&self, user_data_address: &MsgAddressInt) -> Result<GetDetails>;
+
+    async fn get_user_data_owner(
+        &self,
+        user_data_address: &MsgAddressInt,
+    ) -> Result<UserDataOwner> {
+        let details = self.get_user_data_details(user_data_address).await?;
+        Ok(UserDataOwner {
+            user: details.user,
+            dao_root: details.dao_root,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UserDataOwner {
+    pub user: MsgAddressInt,
+    pub dao_root: MsgAddressInt,
+}
+
+#[async_trait::async_trait]
+impl ContractStateProvider for TransactionConsumer {
+    async fn get_dao_root(&self, proposal_address: &MsgAddressInt) -> Result<MsgAddressInt> {
+        let function_output = self
+            .run_local(proposal_address, get_dao_root(), &[])
+            .await?
+            .context("none function output")?;
+
+        Ok(function_output.tokens.unwrap_or_default().unpack_first()?)
+    }
+
+    async fn expected_proposal_address(
+        &self,
+        dao_root: &MsgAddressInt,
+        proposal_id: u32,
+    ) -> Result<MsgAddressInt> {
+        let function_output = self
+            .run_local(
+                dao_root,
+                expected_proposal_address(),
+                &[answer_id(), proposal_id.token_value().named("proposalId")],
+            )
+            .await?
+            .context("none function output")?;
+
+        Ok(function_output.tokens.unwrap_or_default().unpack_first()?)
+    }
+
+    async fn get_proposal_overview(
+        &self,
+        proposal_address: &MsgAddressInt,
+    ) -> Result<ProposalOverview> {
+        // the proposal may be not deployed yet
+        let function_output =
+            poll_run_local(self, proposal_address, get_overview(), &[answer_id()], 60).await?;
+
+        Ok(function_output.tokens.unwrap_or_default().unpack()?)
+    }
+
+    async fn get_proposal_config(
+        &self,
+        proposal_address: &MsgAddressInt,
+    ) -> Result<ProposalConfig> {
+        let function_output =
+            poll_run_local(self, proposal_address, get_config(), &[answer_id()], 60).await?;
+
+        Ok(function_output.tokens.unwrap_or_default().unpack_first()?)
+    }
+
+    async fn get_dao_config(&self, dao_root: &MsgAddressInt) -> Result<ProposalConfig> {
+        let function_output = self
+            .run_local(dao_root, proposal_configuration(), &[])
+            .await?
+            .context("none function output")?;
+
+        Ok(function_output.tokens.unwrap_or_default().unpack_first()?)
+    }
+
+    async fn get_user_data_details(&self, user_data_address: &MsgAddressInt) -> Result<GetDetails> {
+        let function_output = self
+            .run_local(user_data_address, get_user_data_details(), &[answer_id()])
+            .await?
+            .context("none function output")?;
+
+        Ok(function_output.tokens.unwrap_or_default().unpack_first()?)
+    }
+}
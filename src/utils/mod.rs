@@ -6,13 +6,30 @@ pub use self::row_reader::*;
 mod part_builder;
 mod row_reader;
 
+/// Upper bound of the page size
+pub const MAX_LIMIT: i32 = 100;
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Paginated<T> {
     pub limit: i32,
     pub offset: i32,
+    /// Opaque position after the previous page, replaces `offset` when set
+    pub cursor: Option<String>,
     pub data: T,
 }
 
+impl<T> Paginated<T> {
+    pub fn with_cursor(mut self, cursor: Option<String>) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    /// Whether the page may be followed by another one
+    pub fn is_full(&self, len: usize) -> bool {
+        len > 0 && len as i32 >= std::cmp::min(self.limit, MAX_LIMIT)
+    }
+}
+
 pub trait AsPaginated: Sized {
     fn paginated(self, limit: i32, offset: i32) -> Paginated<Self>;
 }
@@ -22,6 +39,7 @@ impl<T> AsPaginated for T {
         Paginated {
             limit,
             offset,
+            cursor: None,
             data: self,
         }
     }
@@ -46,6 +64,13 @@ impl<T, O> AsOrdered<O> for T {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total_count: Option<i64>,
+    pub next_cursor: Option<String>,
+}
+
 #[macro_export]
 macro_rules! once {
     ($ty:path, || $expr:expr) => {{
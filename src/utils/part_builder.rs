@@ -144,6 +144,7 @@ impl_multiple_args!(0: T0, 1: T1);
 pub enum CustomBuildType {
     Int(i64),
     Bool(bool),
+    String(String),
 }
 
 pub struct CustomBuild<I>(pub String, pub I);
@@ -157,6 +158,7 @@ where
             match param {
                 CustomBuildType::Int(param) => builder.push_arg(param),
                 CustomBuildType::Bool(param) => builder.push_arg(param),
+                CustomBuildType::String(param) => builder.push_arg(param),
             };
         }
 
@@ -245,5 +247,9 @@ impl_where_condition_tuple!(
     8: T8,
     9: T9,
     10: T10,
-    11: T11
+    11: T11,
+    12: T12,
+    13: T13,
+    14: T14,
+    15: T15
 );
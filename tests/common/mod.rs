@@ -0,0 +1,425 @@
+// shared by test crates which use different parts of it
+#![allow(dead_code)]
+
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use dao_api::indexer::*;
+use dao_api::models::*;
+use dao_api::sqlx_client::SqlxClient;
+use dao_api::ton_contracts::{ContractStateProvider, FixtureContractStateProvider};
+use futures::{SinkExt, StreamExt};
+use indexer_lib::TransactionExt;
+use nekoton_abi::*;
+use nekoton_utils::TrustMe;
+use sqlx::PgPool;
+use ton_abi::{Token, TokenValue};
+use ton_block::{
+    AccountStatus, ExtOutMessageHeader, ExternalInboundMessageHeader, Message, MsgAddressExt,
+    MsgAddressInt, Transaction,
+};
+use ton_types::{BuilderData, IBitstring, SliceData};
+
+pub const DAO_ROOT: &str = "0:1111111111111111111111111111111111111111111111111111111111111111";
+pub const PROPOSAL: &str = "0:2222222222222222222222222222222222222222222222222222222222222222";
+pub const USER_DATA_1: &str = "0:3333333333333333333333333333333333333333333333333333333333333333";
+pub const USER_DATA_2: &str = "0:4444444444444444444444444444444444444444444444444444444444444444";
+pub const VOTER_1: &str = "0:5555555555555555555555555555555555555555555555555555555555555555";
+pub const VOTER_2: &str = "0:6666666666666666666666666666666666666666666666666666666666666666";
+pub const PROPOSER: &str = "0:7777777777777777777777777777777777777777777777777777777777777777";
+
+pub const RETRY_POLICY: RetryPolicy = RetryPolicy {
+    max_attempts: 2,
+    base_delay_ms: 60_000,
+    max_delay_ms: 3_600_000,
+};
+
+pub fn fixture_state_provider() -> FixtureContractStateProvider {
+    FixtureContractStateProvider::from_json(include_str!("../fixtures/contracts.json")).trust_me()
+}
+
+/// Fixture getters which count how many times they were called
+pub struct CountingStateProvider {
+    inner: FixtureContractStateProvider,
+    calls: AtomicUsize,
+    dao_config_unavailable: bool,
+}
+
+impl Default for CountingStateProvider {
+    fn default() -> Self {
+        Self {
+            inner: fixture_state_provider(),
+            calls: AtomicUsize::new(0),
+            dao_config_unavailable: false,
+        }
+    }
+}
+
+impl CountingStateProvider {
+    /// `get_dao_config` fails, like a getter of an unreachable node
+    pub fn without_dao_config(mut self) -> Self {
+        self.dao_config_unavailable = true;
+        self
+    }
+
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    fn inner(&self) -> &FixtureContractStateProvider {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        &self.inner
+    }
+}
+
+#[async_trait::async_trait]
+impl ContractStateProvider for CountingStateProvider {
+    async fn get_dao_root(
+        &self,
+        proposal_address: &MsgAddressInt,
+    ) -> anyhow::Result<MsgAddressInt> {
+        self.inner().get_dao_root(proposal_address).await
+    }
+
+    async fn expected_proposal_address(
+        &self,
+        dao_root: &MsgAddressInt,
+        proposal_id: u32,
+    ) -> anyhow::Result<MsgAddressInt> {
+        self.inner()
+            .expected_proposal_address(dao_root, proposal_id)
+            .await
+    }
+
+    async fn get_proposal_overview(
+        &self,
+        proposal_address: &MsgAddressInt,
+    ) -> anyhow::Result<ProposalOverview> {
+        self.inner().get_proposal_overview(proposal_address).await
+    }
+
+    async fn get_proposal_config(
+        &self,
+        proposal_address: &MsgAddressInt,
+    ) -> anyhow::Result<ProposalConfig> {
+        self.inner().get_proposal_config(proposal_address).await
+    }
+
+    async fn get_dao_config(&self, dao_root: &MsgAddressInt) -> anyhow::Result<ProposalConfig> {
+        if self.dao_config_unavailable {
+            anyhow::bail!("dao config is unavailable");
+        }
+        self.inner().get_dao_config(dao_root).await
+    }
+
+    async fn get_user_data_details(
+        &self,
+        user_data_address: &MsgAddressInt,
+    ) -> anyhow::Result<GetDetails> {
+        self.inner().get_user_data_details(user_data_address).await
+    }
+}
+
+pub struct TestIndexer {
+    pub sqlx_client: SqlxClient,
+    all_events: AllEvents,
+    state_provider: FixtureContractStateProvider,
+    lt: u64,
+    dry_run: bool,
+    transactions: Vec<Transaction>,
+}
+
+impl TestIndexer {
+    pub fn new(pool: PgPool) -> Self {
+        // dao roots can be set only once per process
+        let _ = init_dao_roots(&[DAO_ROOT.to_owned()]);
+
+        Self {
+            sqlx_client: SqlxClient::new(pool),
+            all_events: AllEvents::new(),
+            state_provider: fixture_state_provider(),
+            lt: 1_000_000,
+            dry_run: false,
+            transactions: Vec::new(),
+        }
+    }
+
+    /// Only creates the transactions, without running them through the indexer
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    pub fn transactions(&self) -> &[Transaction] {
+        &self.transactions
+    }
+
+    /// Emits the event from the contract and runs it through the indexer
+    pub async fn process(&mut self, abi: &str, contract: &str, event: &str, tokens: Vec<Token>) {
+        self.lt += 1000;
+        let transaction = event_transaction(abi, &address(contract), event, self.lt, &tokens);
+        self.transactions.push(transaction.clone());
+        if !self.dry_run {
+            self.apply(transaction).await;
+        }
+    }
+
+    /// Next transactions are created from this block time
+    pub fn set_time(&mut self, time: u32) {
+        self.lt = (time as u64 - 1) * 1000;
+    }
+
+    /// Runs the last processed transaction through the indexer again
+    pub async fn replay_last(&mut self) {
+        let transaction = self.transactions.last().cloned().trust_me();
+        self.apply(transaction).await;
+    }
+
+    /// Replays the stored raw transactions
+    pub async fn reindex(&self, filters: RawTransactionFilters) {
+        let state_provider: Arc<dyn ContractStateProvider> = Arc::new(fixture_state_provider());
+
+        reindex(
+            self.sqlx_client.clone(),
+            state_provider,
+            RETRY_POLICY,
+            filters,
+        )
+        .await
+        .trust_me();
+    }
+
+    /// Runs the transactions of the source through the indexer until it has caught up
+    pub async fn replay_from(&self, source: Box<dyn TransactionSource>) {
+        let TransactionSourceChannels {
+            mut rx_parsed_events,
+            mut tx_commit,
+            notify_for_services,
+        } = source.start();
+
+        loop {
+            tokio::select! {
+                biased;
+                Some(batch) = rx_parsed_events.next() => {
+                    for (_, raw_transaction) in batch {
+                        self.apply(raw_transaction.data).await;
+                    }
+                    tx_commit.send(()).await.trust_me();
+                }
+                _ = notify_for_services.notified() => break,
+            }
+        }
+    }
+
+    async fn apply(&self, transaction: Transaction) {
+        let transaction_hash = transaction.tx_hash().trust_me();
+
+        self.sqlx_client
+            .create_raw_transaction(transaction.clone().try_into().trust_me())
+            .await
+            .trust_me();
+
+        parse_new_event(
+            transaction,
+            transaction_hash,
+            &self.sqlx_client,
+            &self.all_events,
+            &self.state_provider,
+        )
+        .await
+        .trust_me();
+    }
+
+    pub async fn proposal_created(&mut self, dao_root: &str, proposal_id: u32) {
+        self.process(
+            DAO_ROOT_ABI,
+            dao_root,
+            "ProposalCreated",
+            proposal_created_tokens(proposal_id),
+        )
+        .await
+    }
+
+    pub async fn vote_cast(
+        &mut self,
+        user_data: &str,
+        proposal_id: u32,
+        support: bool,
+        votes: u128,
+    ) {
+        self.process(
+            USERDATA_ABI,
+            user_data,
+            "VoteCast",
+            vec![
+                proposal_id.token_value().named("proposal_id"),
+                support.token_value().named("support"),
+                votes.token_value().named("votes"),
+                String::new().token_value().named("reason"),
+            ],
+        )
+        .await
+    }
+
+    pub async fn unlock_casted_votes(&mut self, user_data: &str, proposal_id: u32) {
+        self.process(
+            USERDATA_ABI,
+            user_data,
+            "UnlockCastedVotes",
+            vec![proposal_id.token_value().named("proposal_id")],
+        )
+        .await
+    }
+
+    pub async fn queued(&mut self, execution_time: u32) {
+        self.process(
+            PROPOSAL_ABI,
+            PROPOSAL,
+            "Queued",
+            vec![execution_time.token_value().named("executionTime")],
+        )
+        .await
+    }
+
+    pub async fn executed(&mut self) {
+        self.process(PROPOSAL_ABI, PROPOSAL, "Executed", Vec::new())
+            .await
+    }
+
+    pub async fn voting_delay_updated(&mut self, old: u32, new: u32) {
+        self.process(
+            DAO_ROOT_ABI,
+            DAO_ROOT,
+            "ProposalVotingDelayUpdated",
+            voting_delay_updated_tokens(old, new),
+        )
+        .await
+    }
+
+    pub async fn grace_period_updated(&mut self, old: u32, new: u32) {
+        self.process(
+            DAO_ROOT_ABI,
+            DAO_ROOT,
+            "ProposalGracePeriodUpdated",
+            vec![
+                old.token_value().named("oldGracePeriod"),
+                new.token_value().named("newGracePeriod"),
+            ],
+        )
+        .await
+    }
+
+    /// Emitted by both the DaoRoot and the Proposal
+    pub async fn requested_admin_transfer(&mut self, old_admin: &str, new_admin: &str) {
+        self.process(
+            DAO_ROOT_ABI,
+            DAO_ROOT,
+            "RequestedAdminTransfer",
+            vec![
+                address(old_admin).token_value().named("oldAdmin"),
+                address(new_admin).token_value().named("newAdmin"),
+            ],
+        )
+        .await
+    }
+
+    pub async fn proposal_code_upgraded(&mut self, abi: &str, contract: &str, version: u16) {
+        self.process(
+            abi,
+            contract,
+            "ProposalCodeUpgraded",
+            vec![version.token_value().named("newVersion")],
+        )
+        .await
+    }
+
+    pub async fn canceled(&mut self) {
+        self.process(PROPOSAL_ABI, PROPOSAL, "Canceled", Vec::new())
+            .await
+    }
+}
+
+pub fn address(address: &str) -> MsgAddressInt {
+    MsgAddressInt::from_str(address).trust_me()
+}
+
+pub fn proposal_created_tokens(proposal_id: u32) -> Vec<Token> {
+    vec![
+        proposal_id.token_value().named("proposalId"),
+        address(PROPOSER).token_value().named("proposer"),
+        Token::new(
+            "tonActions",
+            TokenValue::Array(TonAction::param_type(), Vec::new()),
+        ),
+        Token::new(
+            "ethActions",
+            TokenValue::Array(EthAction::param_type(), Vec::new()),
+        ),
+        "Test proposal"
+            .to_owned()
+            .token_value()
+            .named("description"),
+    ]
+}
+
+pub fn voting_delay_updated_tokens(old: u32, new: u32) -> Vec<Token> {
+    vec![
+        old.token_value().named("oldVotingDelay"),
+        new.token_value().named("newVotingDelay"),
+    ]
+}
+
+/// Builds an external transaction of the contract with a single emitted event
+fn event_transaction(
+    abi: &str,
+    contract: &MsgAddressInt,
+    event: &str,
+    lt: u64,
+    tokens: &[Token],
+) -> Transaction {
+    events_transaction(abi, contract, &[(event, tokens.to_vec())], lt)
+}
+
+/// Builds an external transaction of the contract which emits the events in order
+pub fn events_transaction(
+    abi: &str,
+    contract: &MsgAddressInt,
+    events: &[(&str, Vec<Token>)],
+    lt: u64,
+) -> Transaction {
+    let contract_abi = ton_abi::Contract::load(abi).trust_me();
+
+    let in_msg = Message::with_ext_in_header(ExternalInboundMessageHeader {
+        src: MsgAddressExt::AddrNone,
+        dst: contract.clone(),
+        import_fee: Default::default(),
+    });
+
+    let mut transaction =
+        Transaction::with_address_and_status(contract.address(), AccountStatus::AccStateActive);
+    transaction.set_logical_time(lt);
+    transaction.set_now((lt / 1000) as u32);
+    transaction.write_in_msg(Some(&in_msg)).trust_me();
+
+    for (event, tokens) in events {
+        let event = contract_abi.events.get(*event).trust_me();
+
+        let mut body = BuilderData::new();
+        body.append_u32(event.id).trust_me();
+        let body = TokenValue::pack_values_into_chain(tokens, vec![body], &event.abi_version)
+            .trust_me()
+            .into_cell()
+            .trust_me();
+
+        let mut out_msg = Message::with_ext_out_header(ExtOutMessageHeader::with_addresses(
+            contract.clone(),
+            MsgAddressExt::AddrNone,
+        ));
+        out_msg.set_body(SliceData::from(body));
+
+        transaction.add_out_message(&out_msg).trust_me();
+    }
+
+    transaction
+}
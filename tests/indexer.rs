@@ -0,0 +1,1050 @@
+use std::sync::Arc;
+
+use dao_api::indexer::{
+    parse_new_event, record_proposal_state_transitions, FileTransactionSource, DAO_ROOT_ABI,
+    PROPOSAL_ABI,
+};
+use dao_api::models::{
+    AllEvents, AuditEventFilters, CreateDaoConfig, CreateGovernanceEvent, CreatePendingAction,
+    CreateVote, DaoConfigEventKind, DaoConfigUpdate, GovernanceAuditEventKind,
+    GovernanceEventFilters, GovernanceEventKind, KnownContractKind, RawTransactionFilters,
+    TimeseriesFilters, TimeseriesInterval, UnlockVote, VoteFilters,
+};
+use dao_api::sqlx_client::SqlxClient;
+use dao_api::ton_contracts::{CachedContractStateProvider, ContractStateProvider};
+use dao_api::utils::{AsOrdered, AsPaginated};
+use indexer_lib::TransactionExt;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use ton_block::Serializable;
+
+use self::common::*;
+
+mod common;
+
+async fn proposal_state(pool: &PgPool) -> (Decimal, Decimal, bool, bool, bool, i64) {
+    sqlx::query_as(
+        "SELECT for_votes, against_votes, queued, executed, canceled, execution_time \
+        FROM proposals WHERE dao_root = $1 AND id = 1",
+    )
+    .bind(DAO_ROOT)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+async fn vote_locked(pool: &PgPool, voter: &str) -> bool {
+    let (locked,): (bool,) = sqlx::query_as(
+        "SELECT locked FROM votes WHERE dao_root = $1 AND proposal_id = 1 AND voter = $2",
+    )
+    .bind(DAO_ROOT)
+    .bind(voter)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    locked
+}
+
+async fn dao_config_history(pool: &PgPool) -> Vec<(i32, Option<i64>, Option<i64>, Option<i64>)> {
+    sqlx::query_as(
+        "SELECT timestamp_block, voting_delay, voting_period, grace_period \
+        FROM dao_config_history WHERE dao_root = $1 ORDER BY timestamp_block",
+    )
+    .bind(DAO_ROOT)
+    .fetch_all(pool)
+    .await
+    .unwrap()
+}
+
+async fn pending_actions_count(pool: &PgPool) -> i64 {
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM pending_actions")
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    count
+}
+
+#[sqlx::test]
+async fn proposal_lifecycle(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer.vote_cast(USER_DATA_1, 1, true, 300).await;
+    indexer.vote_cast(USER_DATA_2, 1, false, 100).await;
+    indexer.queued(1640200000).await;
+    indexer.executed().await;
+    indexer.unlock_casted_votes(USER_DATA_1, 1).await;
+
+    let (for_votes, against_votes, queued, executed, canceled, execution_time) =
+        proposal_state(&pool).await;
+    assert_eq!(for_votes, Decimal::from(300));
+    assert_eq!(against_votes, Decimal::from(100));
+    assert!(queued);
+    assert!(executed);
+    assert!(!canceled);
+    assert_eq!(execution_time, 1640200000);
+
+    assert!(!vote_locked(&pool, VOTER_1).await);
+    assert!(vote_locked(&pool, VOTER_2).await);
+}
+
+#[sqlx::test]
+async fn file_source_replays_transactions(pool: PgPool) {
+    let mut recorder = TestIndexer::new(pool.clone()).dry_run();
+
+    recorder.proposal_created(DAO_ROOT, 1).await;
+    recorder.vote_cast(USER_DATA_1, 1, true, 300).await;
+    recorder.vote_cast(USER_DATA_2, 1, false, 100).await;
+    recorder.queued(1640200000).await;
+
+    let bocs = recorder
+        .transactions()
+        .iter()
+        .map(|x| x.write_to_bytes().unwrap())
+        .collect::<Vec<_>>();
+
+    let dir = std::env::temp_dir().join(format!("file-source-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir(&dir).unwrap();
+
+    // the proposal is created by the second file
+    std::fs::write(
+        dir.join("a.jsonl"),
+        format!(
+            "\"{}\"\n{{\"boc\": \"{}\"}}\n",
+            base64::encode(&bocs[3]),
+            base64::encode(&bocs[2])
+        ),
+    )
+    .unwrap();
+    std::fs::write(dir.join("b.boc"), &bocs[0]).unwrap();
+    std::fs::write(
+        dir.join("c.jsonl"),
+        format!("\"{}\"\n", base64::encode(&bocs[1])),
+    )
+    .unwrap();
+
+    let indexer = TestIndexer::new(pool.clone());
+    indexer
+        .replay_from(Box::new(FileTransactionSource { dir: dir.clone() }))
+        .await;
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let (for_votes, against_votes, queued, executed, canceled, execution_time) =
+        proposal_state(&pool).await;
+    assert_eq!(for_votes, Decimal::from(300));
+    assert_eq!(against_votes, Decimal::from(100));
+    assert!(queued);
+    assert!(!executed);
+    assert!(!canceled);
+    assert_eq!(execution_time, 1640200000);
+
+    assert_eq!(pending_actions_count(&pool).await, 0);
+}
+
+#[sqlx::test]
+async fn failed_transaction_leaves_no_partial_writes(pool: PgPool) {
+    let indexer = TestIndexer::new(pool.clone());
+    let sqlx_client = &indexer.sqlx_client;
+    let all_events = AllEvents::new();
+
+    let transaction = events_transaction(
+        DAO_ROOT_ABI,
+        &address(DAO_ROOT),
+        &[
+            ("ProposalCreated", proposal_created_tokens(1)),
+            (
+                "ProposalVotingDelayUpdated",
+                voting_delay_updated_tokens(86400, 100),
+            ),
+        ],
+        1_640_000_000_000,
+    );
+    let transaction_hash = transaction.tx_hash().unwrap();
+    sqlx_client
+        .create_raw_transaction(transaction.clone().try_into().unwrap())
+        .await
+        .unwrap();
+
+    let count = |table: &str| {
+        let query = format!("SELECT COUNT(*) FROM {}", table);
+        let pool = pool.clone();
+        async move {
+            let (count,): (i64,) = sqlx::query_as(&query).fetch_one(&pool).await.unwrap();
+            count
+        }
+    };
+
+    // the config change fails after the proposal is created
+    let getters = CountingStateProvider::default().without_dao_config();
+    assert!(parse_new_event(
+        transaction.clone(),
+        transaction_hash,
+        sqlx_client,
+        &all_events,
+        &getters,
+    )
+    .await
+    .is_err());
+
+    assert_eq!(count("proposals").await, 0);
+    assert_eq!(count("applied_events").await, 0);
+    assert_eq!(count("governance_events").await, 0);
+    assert!(dao_config_history(&pool).await.is_empty());
+
+    // the retry applies both events
+    parse_new_event(
+        transaction,
+        transaction_hash,
+        sqlx_client,
+        &all_events,
+        &CountingStateProvider::default(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(count("proposals").await, 1);
+    assert_eq!(count("applied_events").await, 2);
+    assert_eq!(dao_config_history(&pool).await.len(), 1);
+}
+
+#[sqlx::test]
+async fn dao_config_changes_are_merged(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.set_time(1000);
+    indexer.voting_delay_updated(86400, 100).await;
+    indexer.grace_period_updated(86400, 200).await;
+
+    // the first change is completed from the contract config
+    assert_eq!(
+        dao_config_history(&pool).await,
+        [
+            (1000, Some(100), Some(172800), Some(86400)),
+            (1001, Some(100), Some(172800), Some(200)),
+        ]
+    );
+
+    let config = indexer
+        .sqlx_client
+        .get_current_dao_config(Some(DAO_ROOT.to_owned()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(config.voting_delay, Some(100));
+    assert_eq!(config.grace_period, Some(200));
+    assert_eq!(config.quorum_votes, Some(Decimal::from(500000000000u64)));
+    assert_eq!(config.threshold, Some(Decimal::from(100000000000u64)));
+    assert_eq!(config.time_lock, Some(86400));
+}
+
+#[sqlx::test]
+async fn out_of_order_dao_config_changes(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.set_time(2000);
+    indexer.grace_period_updated(86400, 200).await;
+    indexer.set_time(1000);
+    indexer.voting_delay_updated(86400, 100).await;
+
+    // the earlier change doesn't take parameters of the later one
+    assert_eq!(
+        dao_config_history(&pool).await,
+        [
+            (1000, Some(100), Some(172800), Some(86400)),
+            (2000, Some(86400), Some(172800), Some(200)),
+        ]
+    );
+
+    let config = indexer
+        .sqlx_client
+        .get_current_dao_config(Some(DAO_ROOT.to_owned()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(config.timestamp_block, 2000);
+    assert_eq!(config.grace_period, Some(200));
+}
+
+#[sqlx::test]
+async fn current_dao_config_requires_root_for_several_daos(pool: PgPool) {
+    const OTHER_DAO_ROOT: &str =
+        "0:8888888888888888888888888888888888888888888888888888888888888888";
+
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.voting_delay_updated(86400, 100).await;
+
+    let sqlx_client = &indexer.sqlx_client;
+
+    let config = sqlx_client
+        .get_current_dao_config(None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(config.dao_root, DAO_ROOT);
+
+    let mut tx = sqlx_client.begin().await.unwrap();
+    sqlx_client
+        .create_dao_config(
+            &mut tx,
+            CreateDaoConfig {
+                dao_root: OTHER_DAO_ROOT.to_owned(),
+                kind: DaoConfigEventKind::ProposalVotingDelayUpdated,
+                config: DaoConfigUpdate {
+                    voting_delay: Some(200),
+                    ..Default::default()
+                },
+                message_hash: vec![1; 32],
+                transaction_hash: vec![1; 32],
+                timestamp_block: 1640000000,
+            },
+        )
+        .await
+        .unwrap();
+    tx.commit().await.unwrap();
+
+    assert!(sqlx_client.get_current_dao_config(None).await.is_err());
+
+    let config = sqlx_client
+        .get_current_dao_config(Some(OTHER_DAO_ROOT.to_owned()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(config.voting_delay, Some(200));
+}
+
+#[sqlx::test]
+async fn dao_stats_are_refreshed(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer.vote_cast(USER_DATA_1, 1, true, 300).await;
+    indexer.vote_cast(USER_DATA_2, 1, false, 100).await;
+    indexer.canceled().await;
+
+    indexer.sqlx_client.refresh_dao_stats().await.unwrap();
+    let stats = indexer
+        .sqlx_client
+        .get_dao_stats(Some(DAO_ROOT.to_owned()))
+        .await
+        .unwrap();
+
+    assert_eq!(stats.proposals_count, 1);
+    assert_eq!(stats.canceled_count, 1);
+    assert_eq!(stats.voters_count, 2);
+    assert_eq!(stats.votes_count, 2);
+    assert_eq!(stats.average_participation, Decimal::from(2));
+    assert_eq!(stats.average_for_ratio, Decimal::new(75, 2));
+    assert_eq!(stats.locked_votes, Decimal::from(400));
+}
+
+#[sqlx::test]
+async fn dao_stats_count_voters_once_across_roots(pool: PgPool) {
+    const OTHER_DAO_ROOT: &str =
+        "0:8888888888888888888888888888888888888888888888888888888888888888";
+
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer.vote_cast(USER_DATA_1, 1, true, 300).await;
+    indexer.vote_cast(USER_DATA_2, 1, false, 100).await;
+
+    let sqlx_client = &indexer.sqlx_client;
+
+    let mut tx = sqlx_client.begin().await.unwrap();
+    sqlx_client
+        .create_vote(
+            &mut tx,
+            CreateVote {
+                dao_root: OTHER_DAO_ROOT.to_owned(),
+                proposal_id: 1,
+                voter: VOTER_1.to_owned(),
+                support: true,
+                reason: String::new(),
+                votes: Decimal::from(200),
+                message_hash: vec![1; 32],
+                transaction_hash: vec![1; 32],
+                timestamp_block: 1640000000,
+            },
+        )
+        .await
+        .unwrap();
+    tx.commit().await.unwrap();
+
+    sqlx_client.refresh_dao_stats().await.unwrap();
+
+    let stats = sqlx_client
+        .get_dao_stats(Some(OTHER_DAO_ROOT.to_owned()))
+        .await
+        .unwrap();
+    assert_eq!(stats.voters_count, 1);
+
+    let stats = sqlx_client.get_dao_stats(None).await.unwrap();
+    assert_eq!(stats.voters_count, 2);
+    assert_eq!(stats.votes_count, 3);
+}
+
+#[sqlx::test]
+async fn timeseries_are_bucketed(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer.vote_cast(USER_DATA_1, 1, true, 300).await;
+    indexer.vote_cast(USER_DATA_2, 1, false, 100).await;
+
+    let filters = |interval, proposal_id| TimeseriesFilters {
+        interval,
+        from: 0,
+        to: 2 * 86400,
+        dao_root: Some(DAO_ROOT.to_owned()),
+        proposal_id,
+        voter: None,
+    };
+
+    // read from the daily aggregates
+    let daily = indexer
+        .sqlx_client
+        .get_timeseries(filters(TimeseriesInterval::Day, None))
+        .await
+        .unwrap();
+    // read from the votes and proposals
+    let raw = indexer
+        .sqlx_client
+        .get_timeseries(filters(TimeseriesInterval::Day, Some(1)))
+        .await
+        .unwrap();
+
+    for points in [daily, raw] {
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].bucket, 0);
+        assert_eq!(points[0].votes_count, 2);
+        assert_eq!(points[0].proposals_count, 1);
+        assert_eq!(points[0].voters_count, 2);
+        assert_eq!(points[0].vote_weight, Decimal::from(400));
+        assert_eq!(points[1].bucket, 86400);
+        assert_eq!(points[1].votes_count, 0);
+    }
+}
+
+#[sqlx::test]
+async fn proposal_tally_is_cumulative(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer.vote_cast(USER_DATA_1, 1, true, 300).await;
+    indexer.vote_cast(USER_DATA_2, 1, false, 100).await;
+
+    let tally = indexer
+        .sqlx_client
+        .proposal_tally_history(DAO_ROOT.to_owned(), 1)
+        .await
+        .unwrap();
+
+    assert_eq!(tally.len(), 2);
+    assert!(tally[0].timestamp_block < tally[1].timestamp_block);
+    assert_eq!(tally[0].for_votes, Decimal::from(300));
+    assert_eq!(tally[0].against_votes, Decimal::ZERO);
+    assert_eq!(tally[0].voters_count, 1);
+    assert_eq!(tally[1].for_votes, Decimal::from(300));
+    assert_eq!(tally[1].against_votes, Decimal::from(100));
+    assert_eq!(tally[1].voters_count, 2);
+}
+
+#[sqlx::test]
+async fn governance_events_are_logged(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    // the unlock is applied together with the vote
+    indexer.unlock_casted_votes(USER_DATA_1, 1).await;
+    indexer.vote_cast(USER_DATA_1, 1, true, 300).await;
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer.canceled().await;
+
+    let events = indexer
+        .sqlx_client
+        .get_governance_events_after(GovernanceEventFilters::default(), 0, 10)
+        .await
+        .unwrap();
+    let kinds = events.iter().map(|x| x.kind).collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            GovernanceEventKind::VoteCast,
+            GovernanceEventKind::VoteUnlocked,
+            GovernanceEventKind::ProposalCreated,
+            GovernanceEventKind::Canceled,
+        ]
+    );
+
+    let voter_events = indexer
+        .sqlx_client
+        .get_governance_events_after(
+            GovernanceEventFilters {
+                voter: Some(VOTER_1.to_owned()),
+                ..Default::default()
+            },
+            events[0].id,
+            10,
+        )
+        .await
+        .unwrap();
+    assert_eq!(voter_events.len(), 1);
+    assert_eq!(voter_events[0].kind, GovernanceEventKind::VoteUnlocked);
+}
+
+#[sqlx::test]
+async fn governance_events_are_committed_in_id_order(pool: PgPool) {
+    let sqlx_client = SqlxClient::new(pool);
+    let event = |voter: &str| {
+        CreateGovernanceEvent::vote_unlocked(
+            &UnlockVote {
+                dao_root: DAO_ROOT.to_owned(),
+                proposal_id: 1,
+                voter: voter.to_owned(),
+            },
+            1640000000,
+        )
+    };
+
+    let mut first = sqlx_client.begin().await.unwrap();
+    sqlx_client
+        .create_governance_event(&mut first, event(VOTER_1))
+        .await
+        .unwrap();
+
+    // staging doesn't wait for other writers
+    let mut second = sqlx_client.begin().await.unwrap();
+    sqlx_client
+        .create_governance_event(&mut second, event(VOTER_2))
+        .await
+        .unwrap();
+
+    sqlx_client
+        .publish_governance_events(&mut first)
+        .await
+        .unwrap();
+
+    let second = {
+        let sqlx_client = sqlx_client.clone();
+        tokio::spawn(async move {
+            sqlx_client
+                .publish_governance_events(&mut second)
+                .await
+                .unwrap();
+            second.commit().await.unwrap();
+        })
+    };
+
+    // the second publisher waits for the first one to commit
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let events = sqlx_client
+        .get_governance_events_after(GovernanceEventFilters::default(), 0, 10)
+        .await
+        .unwrap();
+    assert!(events.is_empty());
+
+    first.commit().await.unwrap();
+    second.await.unwrap();
+
+    let voters = sqlx_client
+        .get_governance_events_after(GovernanceEventFilters::default(), 0, 10)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|x| x.voter.unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(voters, [VOTER_1, VOTER_2]);
+}
+
+#[sqlx::test]
+async fn proposal_state_transitions_are_recorded_once(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer.vote_cast(USER_DATA_1, 1, true, 300).await;
+
+    let sqlx_client = &indexer.sqlx_client;
+
+    // voting is open
+    let recorded = record_proposal_state_transitions(sqlx_client, 1640050000)
+        .await
+        .unwrap();
+    assert_eq!(recorded, 1);
+
+    // the quorum is not reached
+    let recorded = record_proposal_state_transitions(sqlx_client, 1640200000)
+        .await
+        .unwrap();
+    assert_eq!(recorded, 1);
+
+    let recorded = record_proposal_state_transitions(sqlx_client, 1640300000)
+        .await
+        .unwrap();
+    assert_eq!(recorded, 0);
+
+    let events = sqlx_client
+        .get_governance_events_after(GovernanceEventFilters::default(), 0, 10)
+        .await
+        .unwrap();
+    let states = events
+        .iter()
+        .filter(|x| x.kind == GovernanceEventKind::StateChanged)
+        .map(|x| (x.data["state"].clone(), x.timestamp_block))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        states,
+        [
+            (serde_json::json!("Active"), 1640000000),
+            (serde_json::json!("Failed"), 1640100000),
+        ]
+    );
+
+    assert!(sqlx_client
+        .get_proposals_awaiting_transitions(1640300000, 10)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[sqlx::test]
+async fn succeeded_proposal_awaits_only_when_queued(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer.vote_cast(USER_DATA_1, 1, true, 600000000000).await;
+
+    let sqlx_client = &indexer.sqlx_client;
+
+    let recorded = record_proposal_state_transitions(sqlx_client, 1640200000)
+        .await
+        .unwrap();
+    assert_eq!(recorded, 2);
+
+    // nothing happens by the clock until the proposal is queued
+    assert!(sqlx_client
+        .get_proposals_awaiting_transitions(1650000000, 10)
+        .await
+        .unwrap()
+        .is_empty());
+
+    indexer.queued(1640200000).await;
+
+    let sqlx_client = &indexer.sqlx_client;
+    assert!(sqlx_client
+        .get_proposals_awaiting_transitions(1640286400, 10)
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        sqlx_client
+            .get_proposals_awaiting_transitions(1640286401, 10)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    let recorded = record_proposal_state_transitions(sqlx_client, 1640300000)
+        .await
+        .unwrap();
+    assert_eq!(recorded, 1);
+
+    assert!(sqlx_client
+        .get_proposals_awaiting_transitions(1650000000, 10)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[sqlx::test]
+async fn votes_are_searched_in_batch(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer.vote_cast(USER_DATA_1, 1, true, 300).await;
+    indexer.vote_cast(USER_DATA_2, 1, false, 100).await;
+
+    let search = |filters: VoteFilters, limit| filters.ordered(None).paginated(limit, 0);
+    let pages = indexer
+        .sqlx_client
+        .search_votes_batch(vec![
+            search(
+                VoteFilters {
+                    voter: Some(VOTER_2.to_owned()),
+                    ..Default::default()
+                },
+                10,
+            ),
+            search(
+                VoteFilters {
+                    dao_root: Some(DAO_ROOT.to_owned()),
+                    proposal_id: Some(1),
+                    ..Default::default()
+                },
+                1,
+            ),
+            search(
+                VoteFilters {
+                    proposal_id: Some(2),
+                    ..Default::default()
+                },
+                10,
+            ),
+        ])
+        .await
+        .unwrap();
+
+    let voters = pages
+        .iter()
+        .map(|page| page.iter().map(|x| x.voter.as_str()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(voters, [vec![VOTER_2], vec![VOTER_2], vec![]]);
+
+    let proposals = indexer
+        .sqlx_client
+        .get_proposals_by_ids(vec![(DAO_ROOT.to_owned(), 1), (DAO_ROOT.to_owned(), 2)])
+        .await
+        .unwrap();
+    assert_eq!(proposals.len(), 1);
+}
+
+#[sqlx::test]
+async fn canceled_proposal(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer.canceled().await;
+
+    let (_, _, queued, executed, canceled, _) = proposal_state(&pool).await;
+    assert!(!queued);
+    assert!(!executed);
+    assert!(canceled);
+}
+
+#[sqlx::test]
+async fn out_of_order_actions(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.vote_cast(USER_DATA_1, 1, true, 300).await;
+    indexer.queued(1640200000).await;
+    indexer.executed().await;
+    indexer.unlock_casted_votes(USER_DATA_2, 1).await;
+    assert_eq!(pending_actions_count(&pool).await, 3);
+
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer.vote_cast(USER_DATA_2, 1, false, 100).await;
+
+    let (for_votes, against_votes, queued, executed, _, execution_time) =
+        proposal_state(&pool).await;
+    assert_eq!(for_votes, Decimal::from(300));
+    assert_eq!(against_votes, Decimal::from(100));
+    assert!(queued);
+    assert!(executed);
+    assert_eq!(execution_time, 1640200000);
+
+    assert!(vote_locked(&pool, VOTER_1).await);
+    assert!(!vote_locked(&pool, VOTER_2).await);
+    assert_eq!(pending_actions_count(&pool).await, 0);
+}
+
+#[sqlx::test]
+async fn replayed_transactions_are_applied_once(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer.replay_last().await;
+    indexer.vote_cast(USER_DATA_1, 1, true, 300).await;
+    indexer.replay_last().await;
+
+    let (for_votes, against_votes, ..) = proposal_state(&pool).await;
+    assert_eq!(for_votes, Decimal::from(300));
+    assert_eq!(against_votes, Decimal::ZERO);
+
+    // votes stored without a ledger entry are recounted instead of added
+    sqlx::query("DELETE FROM applied_events")
+        .execute(&pool)
+        .await
+        .unwrap();
+    indexer.replay_last().await;
+
+    let (for_votes, ..) = proposal_state(&pool).await;
+    assert_eq!(for_votes, Decimal::from(300));
+}
+
+#[sqlx::test]
+async fn duplicate_vote_keeps_pending_unlock(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer.vote_cast(USER_DATA_1, 1, true, 300).await;
+
+    let sqlx_client = &indexer.sqlx_client;
+    let mut tx = sqlx_client.begin().await.unwrap();
+    sqlx_client
+        .create_pending_action(
+            &mut tx,
+            CreatePendingAction::UnlockVote {
+                dao_root: DAO_ROOT.to_owned(),
+                proposal_id: 1,
+                voter: VOTER_1.to_owned(),
+                timestamp_block: 1640000000,
+            },
+        )
+        .await
+        .unwrap();
+    tx.commit().await.unwrap();
+
+    // the vote is stored again without a ledger entry
+    sqlx::query("DELETE FROM applied_events")
+        .execute(&pool)
+        .await
+        .unwrap();
+    indexer.replay_last().await;
+
+    assert!(vote_locked(&pool, VOTER_1).await);
+    assert_eq!(pending_actions_count(&pool).await, 1);
+}
+
+#[sqlx::test]
+async fn ranged_reindex_replays_range(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.set_time(1000);
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer.vote_cast(USER_DATA_1, 1, true, 300).await;
+    indexer.vote_cast(USER_DATA_2, 1, false, 100).await;
+    indexer.unlock_casted_votes(USER_DATA_2, 1).await;
+    indexer.queued(1640200000).await;
+
+    // rows stored by a buggy parser
+    sqlx::query("UPDATE votes SET votes = 1")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE proposals SET description = '', for_votes = 1, against_votes = 1")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // the last vote is fixed, its unlock is kept
+    indexer
+        .reindex(RawTransactionFilters {
+            timestamp_block_ge: Some(1002),
+            timestamp_block_le: Some(1002),
+            ..Default::default()
+        })
+        .await;
+
+    let (for_votes, against_votes, ..) = proposal_state(&pool).await;
+    assert_eq!(for_votes, Decimal::from(1));
+    assert_eq!(against_votes, Decimal::from(100));
+    assert!(vote_locked(&pool, VOTER_1).await);
+    assert!(!vote_locked(&pool, VOTER_2).await);
+
+    // the proposal is fixed, its later states are kept
+    indexer
+        .reindex(RawTransactionFilters {
+            timestamp_block_le: Some(1000),
+            ..Default::default()
+        })
+        .await;
+
+    let (description,): (String,) =
+        sqlx::query_as("SELECT description FROM proposals WHERE dao_root = $1 AND id = 1")
+            .bind(DAO_ROOT)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(description, "Test proposal");
+
+    let (for_votes, against_votes, queued, _, _, execution_time) = proposal_state(&pool).await;
+    assert_eq!(for_votes, Decimal::from(1));
+    assert_eq!(against_votes, Decimal::from(100));
+    assert!(queued);
+    assert_eq!(execution_time, 1640200000);
+    assert_eq!(pending_actions_count(&pool).await, 0);
+
+    // a full reindex fixes the rest
+    indexer.reindex(RawTransactionFilters::default()).await;
+
+    let (for_votes, against_votes, ..) = proposal_state(&pool).await;
+    assert_eq!(for_votes, Decimal::from(300));
+    assert_eq!(against_votes, Decimal::from(100));
+}
+
+#[sqlx::test]
+async fn shared_events_are_applied_by_emitter(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer
+        .proposal_code_upgraded(DAO_ROOT_ABI, DAO_ROOT, 2)
+        .await;
+    indexer
+        .proposal_code_upgraded(PROPOSAL_ABI, PROPOSAL, 2)
+        .await;
+
+    let events = indexer
+        .sqlx_client
+        .search_audit_events(AuditEventFilters::default().ordered(None).paginated(10, 0))
+        .await
+        .unwrap()
+        .map(|x| (x.kind, x.contract_address))
+        .collect::<Vec<_>>();
+    assert_eq!(events.len(), 2);
+    assert!(events.contains(&(
+        GovernanceAuditEventKind::ProposalCodeUpgraded,
+        DAO_ROOT.to_owned()
+    )));
+    assert!(events.contains(&(
+        GovernanceAuditEventKind::ProposalUpgraded,
+        PROPOSAL.to_owned()
+    )));
+}
+
+#[sqlx::test]
+async fn audit_events_are_attributed_to_dao_root(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer.requested_admin_transfer(PROPOSER, VOTER_1).await;
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer
+        .proposal_code_upgraded(PROPOSAL_ABI, PROPOSAL, 2)
+        .await;
+
+    let sqlx_client = &indexer.sqlx_client;
+    let filters = |dao_root: &str| AuditEventFilters {
+        dao_root: Some(dao_root.to_owned()),
+        ..Default::default()
+    };
+
+    // the proposal event belongs to the root of the proposal
+    let events = sqlx_client
+        .search_audit_events(filters(DAO_ROOT).ordered(None).paginated(10, 0))
+        .await
+        .unwrap()
+        .collect::<Vec<_>>();
+    let kinds = events
+        .iter()
+        .map(|x| (x.kind, x.contract_address.as_str(), x.dao_root.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            (
+                GovernanceAuditEventKind::RequestedAdminTransfer,
+                DAO_ROOT,
+                DAO_ROOT
+            ),
+            (
+                GovernanceAuditEventKind::ProposalUpgraded,
+                PROPOSAL,
+                DAO_ROOT
+            ),
+        ]
+    );
+    assert_eq!(
+        events[0].data,
+        serde_json::json!({ "oldAdmin": PROPOSER, "newAdmin": VOTER_1 })
+    );
+    assert_eq!(
+        sqlx_client
+            .audit_events_total_count(filters(DAO_ROOT))
+            .await
+            .unwrap(),
+        2
+    );
+
+    let other_root = "0:8888888888888888888888888888888888888888888888888888888888888888";
+    assert_eq!(
+        sqlx_client
+            .audit_events_total_count(filters(other_root))
+            .await
+            .unwrap(),
+        0
+    );
+
+    let kind_filters = AuditEventFilters {
+        kind: Some(GovernanceAuditEventKind::ProposalUpgraded),
+        ..filters(DAO_ROOT)
+    };
+    assert_eq!(
+        sqlx_client
+            .audit_events_total_count(kind_filters)
+            .await
+            .unwrap(),
+        1
+    );
+}
+
+#[sqlx::test]
+async fn contract_owners_are_cached(pool: PgPool) {
+    let sqlx_client = SqlxClient::new(pool.clone());
+    let getters = Arc::new(CountingStateProvider::default());
+    let cached = CachedContractStateProvider::new(getters.clone(), sqlx_client.clone());
+
+    let proposal = address(PROPOSAL);
+    let user_data = address(USER_DATA_1);
+
+    // the first lookup misses
+    let dao_root = cached.get_dao_root(&proposal).await.unwrap();
+    assert_eq!(dao_root, address(DAO_ROOT));
+    assert_eq!(getters.calls(), 1);
+
+    let owner = cached.get_user_data_owner(&user_data).await.unwrap();
+    assert_eq!(owner.user, address(VOTER_1));
+    assert_eq!(getters.calls(), 2);
+
+    // the next ones are answered from known_contracts, even after a restart
+    let cached = CachedContractStateProvider::new(getters.clone(), sqlx_client.clone());
+
+    let dao_root = cached.get_dao_root(&proposal).await.unwrap();
+    assert_eq!(dao_root, address(DAO_ROOT));
+
+    let owner = cached.get_user_data_owner(&user_data).await.unwrap();
+    assert_eq!(owner.user, address(VOTER_1));
+    assert_eq!(owner.dao_root, address(DAO_ROOT));
+    assert_eq!(getters.calls(), 2);
+
+    let contract = sqlx_client
+        .get_known_contract(USER_DATA_1.to_owned(), KnownContractKind::UserData)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(contract.dao_root, DAO_ROOT);
+    assert_eq!(contract.owner.as_deref(), Some(VOTER_1));
+
+    // the expected address of a new proposal is saved as well
+    sqlx::query("DELETE FROM known_contracts")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let expected = cached
+        .expected_proposal_address(&address(DAO_ROOT), 1)
+        .await
+        .unwrap();
+    assert_eq!(expected, proposal);
+    assert_eq!(getters.calls(), 3);
+
+    cached.get_dao_root(&proposal).await.unwrap();
+    assert_eq!(getters.calls(), 3);
+}
+
+#[sqlx::test]
+async fn unknown_dao_root_is_skipped(pool: PgPool) {
+    let mut indexer = TestIndexer::new(pool.clone());
+
+    indexer
+        .proposal_created(
+            "0:8888888888888888888888888888888888888888888888888888888888888888",
+            1,
+        )
+        .await;
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM proposals")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+}
@@ -0,0 +1,163 @@
+use dao_api::models::*;
+use dao_api::sqlx_client::SqlxClient;
+use sqlx::PgPool;
+
+const RETRY_POLICY: RetryPolicy = RetryPolicy {
+    max_attempts: 2,
+    base_delay_ms: 60_000,
+    max_delay_ms: 3_600_000,
+};
+
+async fn failed_raw_transaction(pool: PgPool) -> (SqlxClient, Vec<u8>) {
+    let sqlx_client = SqlxClient::new(pool);
+    let transaction_hash = vec![1; 32];
+
+    sqlx_client
+        .create_raw_transaction(RawTransactionFromDb {
+            transaction: vec![0; 8],
+            transaction_hash: transaction_hash.clone(),
+            timestamp_block: 1640000000,
+            timestamp_lt: 1_000_000,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    (sqlx_client, transaction_hash)
+}
+
+#[sqlx::test]
+async fn failed_transaction_becomes_dead_letter(pool: PgPool) {
+    let (sqlx_client, transaction_hash) = failed_raw_transaction(pool).await;
+
+    let state = sqlx_client
+        .record_raw_transaction_failure(&transaction_hash, "first".to_owned(), RETRY_POLICY)
+        .await
+        .unwrap();
+    assert_eq!(state, Some(RawTransactionState::Fail));
+
+    let transaction = sqlx_client
+        .get_failed_raw_transaction(&transaction_hash)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(transaction.attempts, 1);
+    assert_eq!(transaction.last_error.as_deref(), Some("first"));
+    assert!(transaction.next_retry_at.is_some());
+
+    // not due for a retry yet
+    let due = sqlx_client
+        .get_raw_transactions_to_retry(transaction.created_at, 100)
+        .await
+        .unwrap();
+    assert!(due.is_empty());
+
+    let state = sqlx_client
+        .record_raw_transaction_failure(&transaction_hash, "second".to_owned(), RETRY_POLICY)
+        .await
+        .unwrap();
+    assert_eq!(state, Some(RawTransactionState::DeadLetter));
+}
+
+#[sqlx::test]
+async fn dead_letter_transaction_is_requeued(pool: PgPool) {
+    let (sqlx_client, transaction_hash) = failed_raw_transaction(pool).await;
+
+    for _ in 0..RETRY_POLICY.max_attempts {
+        sqlx_client
+            .record_raw_transaction_failure(&transaction_hash, "error".to_owned(), RETRY_POLICY)
+            .await
+            .unwrap();
+    }
+
+    let transaction = sqlx_client
+        .requeue_raw_transaction(&transaction_hash)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(transaction.state, RawTransactionState::Fail);
+    assert_eq!(transaction.attempts, 0);
+    assert_eq!(transaction.next_retry_at, None);
+
+    let due = sqlx_client
+        .get_raw_transactions_to_retry(transaction.created_at, 100)
+        .await
+        .unwrap();
+    assert_eq!(due.len(), 1);
+}
+
+#[sqlx::test]
+async fn checkpoint_never_moves_back(pool: PgPool) {
+    let sqlx_client = SqlxClient::new(pool);
+
+    sqlx_client
+        .update_indexer_checkpoint("kafka", 2_000_000, 1640000100)
+        .await
+        .unwrap();
+    // redelivered batch
+    sqlx_client
+        .update_indexer_checkpoint("kafka", 1_000_000, 1640000000)
+        .await
+        .unwrap();
+
+    let checkpoint = sqlx_client
+        .get_indexer_checkpoint("kafka")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(checkpoint.timestamp_lt, 2_000_000);
+    assert_eq!(checkpoint.timestamp_block, 1640000100);
+}
+
+#[sqlx::test]
+async fn unprocessed_transactions_are_requeued_by_block_time(pool: PgPool) {
+    let sqlx_client = SqlxClient::new(pool);
+
+    // lt of the first account is far ahead of the second one
+    let stored = [
+        (vec![1; 32], 1640000000, 5_000_000),
+        (vec![2; 32], 1640000100, 1_000_000),
+    ];
+    for (transaction_hash, timestamp_block, timestamp_lt) in stored.clone() {
+        sqlx_client
+            .create_raw_transaction(RawTransactionFromDb {
+                transaction: vec![0; 8],
+                transaction_hash,
+                timestamp_block,
+                timestamp_lt,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+    }
+
+    // the checkpoint covers only the first transaction
+    sqlx_client
+        .update_indexer_checkpoint("kafka", 5_000_000, 1640000000)
+        .await
+        .unwrap();
+    let checkpoint = sqlx_client
+        .get_indexer_checkpoint("kafka")
+        .await
+        .unwrap()
+        .unwrap();
+
+    let requeued = sqlx_client
+        .requeue_unprocessed_raw_transactions(checkpoint.timestamp_block)
+        .await
+        .unwrap();
+    assert_eq!(requeued, 1);
+
+    for (transaction_hash, state) in [
+        (&stored[0].0, RawTransactionState::Fail),
+        // the later transaction is redelivered with its batch
+        (&stored[1].0, RawTransactionState::Idle),
+    ] {
+        let transaction = sqlx_client
+            .get_failed_raw_transaction(transaction_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(transaction.state, state);
+    }
+}
@@ -0,0 +1,284 @@
+use dao_api::models::*;
+use dao_api::sqlx_client::SqlxClient;
+use dao_api::utils::*;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+
+const DAO_ROOT: &str = "0:1111111111111111111111111111111111111111111111111111111111111111";
+
+fn new_proposal(id: i32, description: &str) -> CreateProposal {
+    CreateProposal {
+        dao_root: DAO_ROOT.to_owned(),
+        id,
+        address: format!("0:{:064x}", id),
+        proposer: format!("0:{:064x}", 100 + id),
+        description: description.to_owned(),
+        start_time: 1640000000,
+        end_time: 1640100000,
+        execution_time: 0,
+        grace_period: 0,
+        time_lock: 0,
+        voting_delay: 0,
+        quorum_votes: Decimal::from(100),
+        message_hash: vec![id as u8; 32],
+        transaction_hash: vec![id as u8; 32],
+        timestamp_block: 1640000000,
+        actions: ProposalActions {
+            ton_actions: Vec::new(),
+            eth_actions: Vec::new(),
+        },
+    }
+}
+
+async fn insert_proposal(sqlx_client: &SqlxClient, proposal: CreateProposal) {
+    let mut tx = sqlx_client.begin().await.unwrap();
+    sqlx_client
+        .create_proposal(&mut tx, proposal)
+        .await
+        .unwrap();
+    tx.commit().await.unwrap();
+}
+
+async fn create_proposal(sqlx_client: &SqlxClient, id: i32, description: &str) {
+    insert_proposal(sqlx_client, new_proposal(id, description)).await;
+}
+
+#[sqlx::test]
+async fn proposals_are_found_by_description(pool: PgPool) {
+    let sqlx_client = SqlxClient::new(pool);
+
+    create_proposal(&sqlx_client, 1, "Increase the bridge relay rewards").await;
+    create_proposal(&sqlx_client, 2, "Update the quorum of the DAO").await;
+
+    let filters = ProposalFilters {
+        query: Some("relay rewards".to_owned()),
+        ..Default::default()
+    };
+
+    let proposals = sqlx_client
+        .search_proposals(filters.clone().ordered(None).paginated(10, 0))
+        .await
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(proposals.len(), 1);
+
+    let (proposal, search_match) = &proposals[0];
+    assert_eq!(proposal.id, 1);
+
+    let search_match = search_match.as_ref().unwrap();
+    assert!(search_match.rank > 0.0);
+    assert!(search_match.snippet.contains("<b>relay</b>"));
+
+    assert_eq!(sqlx_client.proposals_total_count(filters).await.unwrap(), 1);
+}
+
+#[sqlx::test]
+async fn search_match_is_empty_without_query(pool: PgPool) {
+    let sqlx_client = SqlxClient::new(pool);
+
+    create_proposal(&sqlx_client, 1, "Increase the bridge relay rewards").await;
+
+    let proposals = sqlx_client
+        .search_proposals(ProposalFilters::default().ordered(None).paginated(10, 0))
+        .await
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(proposals.len(), 1);
+    assert!(proposals[0].1.is_none());
+}
+
+#[sqlx::test]
+async fn proposals_are_paginated_by_cursor(pool: PgPool) {
+    let sqlx_client = SqlxClient::new(pool);
+
+    // same block, so the order depends on the id tie-breaker
+    for id in 1..=3 {
+        create_proposal(&sqlx_client, id, "Proposal").await;
+    }
+
+    let first_page = sqlx_client
+        .search_proposals(ProposalFilters::default().ordered(None).paginated(2, 0))
+        .await
+        .unwrap()
+        .map(|(proposal, _)| proposal)
+        .collect::<Vec<_>>();
+    assert_eq!(first_page.iter().map(|x| x.id).collect::<Vec<_>>(), [3, 2]);
+
+    let cursor = encode_cursor(&ProposalCursor::from(first_page.last().unwrap()));
+    let second_page = sqlx_client
+        .search_proposals(
+            ProposalFilters::default()
+                .ordered(None)
+                .paginated(2, 0)
+                .with_cursor(Some(cursor)),
+        )
+        .await
+        .unwrap()
+        .map(|(proposal, _)| proposal.id)
+        .collect::<Vec<_>>();
+    assert_eq!(second_page, [1]);
+}
+
+#[sqlx::test]
+async fn proposal_orderings_are_tie_broken(pool: PgPool) {
+    const OTHER_DAO_ROOT: &str =
+        "0:8888888888888888888888888888888888888888888888888888888888888888";
+
+    let sqlx_client = SqlxClient::new(pool);
+
+    // no votes and the same block, so only the tie-breaker orders them
+    create_proposal(&sqlx_client, 2, "Proposal").await;
+    create_proposal(&sqlx_client, 1, "Proposal").await;
+    insert_proposal(
+        &sqlx_client,
+        CreateProposal {
+            dao_root: OTHER_DAO_ROOT.to_owned(),
+            address: format!("0:{:064x}", 1001),
+            ..new_proposal(1, "Proposal")
+        },
+    )
+    .await;
+
+    let page = |direction, limit, offset| {
+        let sqlx_client = sqlx_client.clone();
+        async move {
+            let ordering = ProposalsOrdering {
+                column: ProposalColumn::ForVotes,
+                direction,
+            };
+            sqlx_client
+                .search_proposals(
+                    ProposalFilters::default()
+                        .ordered(Some(ordering))
+                        .paginated(limit, offset),
+                )
+                .await
+                .unwrap()
+                .map(|(proposal, _)| (proposal.dao_root, proposal.id))
+                .collect::<Vec<_>>()
+        }
+    };
+
+    let ascending = [
+        (DAO_ROOT.to_owned(), 1),
+        (OTHER_DAO_ROOT.to_owned(), 1),
+        (DAO_ROOT.to_owned(), 2),
+    ];
+    assert_eq!(page(Direction::Ascending, 10, 0).await, ascending);
+
+    let mut descending = ascending.to_vec();
+    descending.reverse();
+    assert_eq!(page(Direction::Descending, 10, 0).await, descending);
+
+    // pages don't overlap
+    let mut pages = page(Direction::Descending, 2, 0).await;
+    pages.extend(page(Direction::Descending, 2, 2).await);
+    assert_eq!(pages, descending);
+}
+
+#[sqlx::test]
+async fn vote_orderings_are_tie_broken(pool: PgPool) {
+    let sqlx_client = SqlxClient::new(pool);
+
+    let voter = |n: u8| format!("0:{:064x}", n);
+
+    // the same weight and block, so only the tie-breaker orders them
+    for (proposal_id, voter) in [(2, voter(1)), (1, voter(2)), (1, voter(1))] {
+        let mut tx = sqlx_client.begin().await.unwrap();
+        sqlx_client
+            .create_vote(
+                &mut tx,
+                CreateVote {
+                    dao_root: DAO_ROOT.to_owned(),
+                    proposal_id,
+                    voter,
+                    support: true,
+                    reason: String::new(),
+                    votes: Decimal::from(100),
+                    message_hash: vec![proposal_id as u8; 32],
+                    transaction_hash: vec![proposal_id as u8; 32],
+                    timestamp_block: 1640000000,
+                },
+            )
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+    }
+
+    let search = |direction| {
+        let sqlx_client = sqlx_client.clone();
+        async move {
+            let ordering = VotesOrdering {
+                column: VoteColumn::Votes,
+                direction,
+            };
+            sqlx_client
+                .search_votes(
+                    VoteFilters::default()
+                        .ordered(Some(ordering))
+                        .paginated(10, 0),
+                )
+                .await
+                .unwrap()
+                .map(|(vote, _)| (vote.proposal_id, vote.voter))
+                .collect::<Vec<_>>()
+        }
+    };
+
+    let ascending = [(1, voter(1)), (1, voter(2)), (2, voter(1))];
+    assert_eq!(search(Direction::Ascending).await, ascending);
+
+    let mut descending = ascending.to_vec();
+    descending.reverse();
+    assert_eq!(search(Direction::Descending).await, descending);
+}
+
+#[sqlx::test]
+async fn proposal_is_found_by_id_and_address(pool: PgPool) {
+    const OTHER_DAO_ROOT: &str =
+        "0:8888888888888888888888888888888888888888888888888888888888888888";
+
+    let sqlx_client = SqlxClient::new(pool);
+
+    create_proposal(&sqlx_client, 1, "Increase the bridge relay rewards").await;
+    insert_proposal(
+        &sqlx_client,
+        CreateProposal {
+            dao_root: OTHER_DAO_ROOT.to_owned(),
+            address: format!("0:{:064x}", 1001),
+            timestamp_block: 1640000100,
+            ..new_proposal(1, "Update the quorum of the DAO")
+        },
+    )
+    .await;
+
+    // both roots have the proposal 1
+    let proposal = sqlx_client
+        .get_proposal(Some(DAO_ROOT.to_owned()), 1)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(proposal.dao_root, DAO_ROOT);
+
+    let proposal = sqlx_client.get_proposal(None, 1).await.unwrap().unwrap();
+    assert_eq!(proposal.dao_root, OTHER_DAO_ROOT);
+
+    assert!(sqlx_client
+        .get_proposal(Some(DAO_ROOT.to_owned()), 2)
+        .await
+        .unwrap()
+        .is_none());
+
+    let proposal = sqlx_client
+        .get_proposal_by_address(format!("0:{:064x}", 1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!((proposal.dao_root.as_str(), proposal.id), (DAO_ROOT, 1));
+
+    assert!(sqlx_client
+        .get_proposal_by_address(format!("0:{:064x}", 2))
+        .await
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,161 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use dao_api::indexer::{dispatch_webhook_deliveries, webhook_client};
+use dao_api::models::*;
+use dao_api::sqlx_client::SqlxClient;
+use dao_api::utils::*;
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::PgPool;
+use warp::hyper::body::Bytes;
+use warp::Filter;
+
+use self::common::*;
+
+mod common;
+
+const RETRY_POLICY: RetryPolicy = RetryPolicy {
+    max_attempts: 3,
+    base_delay_ms: 60_000,
+    max_delay_ms: 3_600_000,
+};
+
+type ReceivedRequests = Arc<Mutex<Vec<(i64, String, Bytes)>>>;
+
+/// Local endpoint accepting requests at `/ok` and failing them at `/fail`
+fn webhook_stub() -> (SocketAddr, ReceivedRequests) {
+    let received = ReceivedRequests::default();
+
+    let ok = {
+        let received = received.clone();
+        warp::path!("ok")
+            .and(warp::post())
+            .and(warp::header::<i64>("x-dao-timestamp"))
+            .and(warp::header::<String>("x-dao-signature"))
+            .and(warp::body::bytes())
+            .map(move |timestamp, signature, body| {
+                received.lock().unwrap().push((timestamp, signature, body));
+                warp::reply()
+            })
+    };
+    let fail = warp::path!("fail").and(warp::post()).map(|| {
+        warp::reply::with_status(warp::reply(), warp::http::StatusCode::INTERNAL_SERVER_ERROR)
+    });
+
+    let (addr, server) = warp::serve(ok.or(fail)).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+
+    (addr, received)
+}
+
+async fn create_webhook(
+    sqlx_client: &SqlxClient,
+    url: String,
+    kinds: Vec<GovernanceEventKind>,
+) -> WebhookFromDb {
+    sqlx_client
+        .create_webhook(CreateWebhook {
+            url,
+            secret: "secret".to_owned(),
+            dao_root: Some(DAO_ROOT.to_owned()),
+            kinds,
+            min_vote_weight: Some(Decimal::from(200)),
+        })
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn webhooks_receive_signed_events(pool: PgPool) {
+    let (addr, received) = webhook_stub();
+    let sqlx_client = SqlxClient::new(pool.clone());
+    let mut indexer = TestIndexer::new(pool);
+
+    create_webhook(
+        &sqlx_client,
+        format!("http://{}/ok", addr),
+        vec![
+            GovernanceEventKind::ProposalCreated,
+            GovernanceEventKind::VoteCast,
+        ],
+    )
+    .await;
+    let failing = create_webhook(
+        &sqlx_client,
+        format!("http://{}/fail", addr),
+        vec![GovernanceEventKind::Canceled],
+    )
+    .await;
+
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer.vote_cast(USER_DATA_1, 1, true, 300).await;
+    // below the minimal vote weight
+    indexer.vote_cast(USER_DATA_2, 1, false, 100).await;
+    indexer.canceled().await;
+
+    // the stub listens on the loopback
+    let client = webhook_client().allow_private_targets();
+    let attempted = dispatch_webhook_deliveries(&sqlx_client, &client, RETRY_POLICY)
+        .await
+        .unwrap();
+    assert_eq!(attempted, 3);
+
+    let received = received.lock().unwrap().clone();
+    let mut kinds = Vec::new();
+    for (timestamp, signature, body) in received {
+        assert_eq!(
+            signature,
+            format!(
+                "sha256={}",
+                sign_webhook_payload("secret", timestamp, &body)
+            )
+        );
+        let payload: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        kinds.push(payload["kind"].clone());
+    }
+    assert_eq!(kinds, [json!("ProposalCreated"), json!("VoteCast")]);
+
+    let failed = sqlx_client
+        .search_webhook_deliveries(
+            WebhookDeliveryFilters {
+                webhook_id: Some(failing.id),
+                state: None,
+            }
+            .paginated(10, 0),
+        )
+        .await
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].state, WebhookDeliveryState::Fail);
+    assert_eq!(failed[0].response_status, Some(500));
+
+    // the failed delivery is not due for a retry yet
+    let attempted = dispatch_webhook_deliveries(&sqlx_client, &client, RETRY_POLICY)
+        .await
+        .unwrap();
+    assert_eq!(attempted, 0);
+}
+
+#[sqlx::test]
+async fn replayed_events_are_not_sent_again(pool: PgPool) {
+    let sqlx_client = SqlxClient::new(pool.clone());
+    let mut indexer = TestIndexer::new(pool);
+
+    create_webhook(
+        &sqlx_client,
+        "https://example.com/hook".to_owned(),
+        vec![GovernanceEventKind::ProposalCreated],
+    )
+    .await;
+
+    indexer.proposal_created(DAO_ROOT, 1).await;
+    indexer.reindex(RawTransactionFilters::default()).await;
+
+    let deliveries = sqlx_client
+        .webhook_deliveries_total_count(WebhookDeliveryFilters::default())
+        .await
+        .unwrap();
+    assert_eq!(deliveries, 1);
+}